@@ -3,10 +3,35 @@ use std::error::Error;
 use crate::parameters::{AnsiMode, DetailLevel, Encoding, Style, SerdeFormatterParameters};
 
 use integra8::formatters::models::report::ComponentRunReport;
-use integra8::formatters::models::summary::RunSummary;
-use integra8::formatters::models::TestParameters;
+use integra8::formatters::models::summary::{FlakinessSummary, RunSummary};
+use integra8::formatters::models::{ComponentListing, TestParameters};
 use integra8::formatters::{OutputFormatter, OutputFormatterFactory};
 
+use serde::Serialize;
+
+/// The document written once the run is complete.
+/// A run is written as the list of its reports, unless there is more than the reports to write,
+/// in which case it is written as a summary with the reports as its `reports` field.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum RunCompleteDocument<'a> {
+    Reports(Vec<&'a ComponentRunReport>),
+    Summary(RunSummaryDocument<'a>),
+}
+
+#[derive(Serialize)]
+struct RunSummaryDocument<'a> {
+    /// The report of each component of the last run, in the order they were declared
+    reports: Vec<&'a ComponentRunReport>,
+
+    /// The seed the order of the tests and suites was shuffled with, when it was shuffled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shuffle_seed: Option<u64>,
+
+    /// The results of each component across all runs, when the schedule was run more than once
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flakiness: Option<&'a FlakinessSummary>,
+}
 
 pub struct SerdeFormatter {
 
@@ -66,14 +91,38 @@ impl OutputFormatterFactory for SerdeFormatter {
 
 impl OutputFormatter for SerdeFormatter {
     fn write_run_complete(&mut self, summary: &RunSummary) -> Result<(), Box<dyn Error>> {
-        let mut all = summary.all().collect::<Vec<&ComponentRunReport>>();
-        all.sort_unstable_by_key(|x| x.description.id().as_unique_number());
+        let mut reports = summary.all().collect::<Vec<&ComponentRunReport>>();
+        reports.sort_unstable_by_key(|x| x.description.id().as_unique_number());
+
+        let document = match (summary.shuffle_seed(), summary.flakiness()) {
+            (None, None) => RunCompleteDocument::Reports(reports),
+            (shuffle_seed, flakiness) => RunCompleteDocument::Summary(RunSummaryDocument {
+                reports,
+                shuffle_seed,
+                flakiness,
+            }),
+        };
+
+        #[cfg(feature = "yaml")]
+        println!("{}",  serde_yaml::to_string(&document)?);
+
+        #[cfg(feature = "json")]
+        println!("{}",  serde_json::to_string(&document)?);
+        Ok(())
+    }
+
+    fn write_run_notice(&mut self, notice: &str) -> Result<(), Box<dyn Error>> {
+        // Notices are written to stderr, so stdout only contains the serialized results
+        eprintln!("{}", notice);
+        Ok(())
+    }
 
+    fn write_component_list(&mut self, listing: &ComponentListing) -> Result<(), Box<dyn Error>> {
         #[cfg(feature = "yaml")]
-        println!("{}",  serde_yaml::to_string(&all)?);
+        println!("{}",  serde_yaml::to_string(listing)?);
 
         #[cfg(feature = "json")]
-        println!("{}",  serde_json::to_string(&all)?);
+        println!("{}",  serde_json::to_string(listing)?);
         Ok(())
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+mod listing;
 pub mod parameters;
 mod progress;
 pub mod render;
@@ -9,14 +10,21 @@ use std::error::Error;
 use std::io::{self, Stdout, Write};
 use std::str::FromStr;
 
+use crate::listing::ListingTree;
 use crate::parameters::{AnsiMode, DetailLevel, Encoding, Style, TreeFormatterParameters};
 use crate::progress::TestProgressFormatter;
+use crate::render::render_human_time;
 use crate::styles::{ProgressBarStyle, TreeStyle};
 use crate::tree::{ResultsNode, ResultsTree};
 
 use integra8::formatters::models::report::ComponentRunReport;
-use integra8::formatters::models::summary::{ComponentTypeCountSummary, RunSummary, SuiteSummary};
-use integra8::formatters::models::{ComponentDescription, ComponentResult, TestParameters};
+use integra8::formatters::models::summary::{
+    ComponentTypeCountSummary, FlakinessSummary, RunSummary, SuiteSummary,
+};
+use integra8::formatters::models::{
+    ComponentDescription, ComponentListing, ComponentResult, ComponentType, FailureReason,
+    TestParameters,
+};
 use integra8::formatters::{OutputFormatter, OutputFormatterFactory};
 
 pub struct TreeFormatter {
@@ -143,6 +151,11 @@ impl OutputFormatter for TreeFormatter {
         Ok(())
     }
 
+    fn write_run_notice(&mut self, notice: &str) -> Result<(), Box<dyn Error>> {
+        writeln!(self.writer, "{}", notice)?;
+        Ok(())
+    }
+
     fn write_component_start(&mut self, desc: &ComponentDescription) -> Result<(), Box<dyn Error>> {
         self.progress_formatter
             .notify_component_start(&mut self.writer, desc)?;
@@ -166,28 +179,47 @@ impl OutputFormatter for TreeFormatter {
         match state.run_result() {
             ComponentResult::Pass(_) => write!(self.writer, "ok")?,
             ComponentResult::Warning(_) => write!(self.writer, "completed with warnings")?,
+            ComponentResult::Fail(FailureReason::RunTimeLimitExceeded) => {
+                write!(self.writer, "FAILED, run time limit exceeded")?
+            }
+            ComponentResult::Fail(_)
+                if state.test_not_run().due_to_run_aborted().total_count() > 0 =>
+            {
+                write!(self.writer, "FAILED, run aborted after too many failures")?
+            }
             ComponentResult::Fail(_) => write!(self.writer, "FAILED")?,
             ComponentResult::DidNotRun(_) => write!(self.writer, "undetermined")?,
         }
 
-        if state.test_warning().has_some() {
+        // Only allowed failures are counted as failed, tests with any other warning passed
+        let allowed_failures = state.test_warning().due_to_allowed_failure().total_count();
+        let passed_with_warnings = state.test_warning().total_count() - allowed_failures;
+        if allowed_failures > 0 {
             writeln!(
                 self.writer,
                 ". {} passed; {} failed ({} allowed); {} skipped",
-                state.test_passed().total_count(),
-                state.test_failed().total_count() + state.test_warning().total_count(),
-                state.test_warning().total_count(),
+                state.test_passed().total_count() + passed_with_warnings,
+                state.test_failed().total_count() + allowed_failures,
+                allowed_failures,
                 state.test_not_run().total_count(),
             )?;
         } else {
             writeln!(
                 self.writer,
                 ". {} passed; {} failed; {} skipped",
-                state.test_passed().total_count(),
+                state.test_passed().total_count() + passed_with_warnings,
                 state.test_failed().total_count(),
                 state.test_not_run().total_count()
             )?;
         };
+
+        if let Some(seed) = state.shuffle_seed() {
+            writeln!(
+                self.writer,
+                "shuffled with seed {}, rerun with `--framework:seed {}` to replay the same order",
+                seed, seed
+            )?;
+        }
         writeln!(self.writer, "")?;
 
         // Just the detail level to capture most relevant details in relation to the result
@@ -202,6 +234,54 @@ impl OutputFormatter for TreeFormatter {
         self.get_tree(state)
             .render_tree(&mut self.writer, &self.tree_style, &detail_level)?;
 
+        if let Some(flakiness) = state.flakiness() {
+            self.write_flakiness(flakiness)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_component_list(&mut self, listing: &ComponentListing) -> Result<(), Box<dyn Error>> {
+        ListingTree::new(listing).render_tree(&mut self.writer, &self.tree_style)?;
+        Ok(())
+    }
+}
+
+impl TreeFormatter {
+    fn write_flakiness(&mut self, flakiness: &FlakinessSummary) -> Result<(), Box<dyn Error>> {
+        writeln!(self.writer, "\nflakiness over {} runs: ", flakiness.runs())?;
+
+        // Suites pass or fail with their children, so only their children are listed
+        for component in flakiness
+            .components()
+            .filter(|c| c.description.component_type() != &ComponentType::Suite)
+        {
+            write!(
+                self.writer,
+                "{}{}: {} passed; {} failed; {} timed out; {} skipped",
+                if component.is_flaky() { "FLAKY " } else { "" },
+                component.description.path(),
+                component.passed,
+                component.failed,
+                component.timed_out,
+                component.skipped,
+            )?;
+
+            if let (Some(min), Some(median), Some(max)) = (
+                &component.min_duration,
+                &component.median_duration,
+                &component.max_duration,
+            ) {
+                write!(
+                    self.writer,
+                    " (min {}, median {}, max {})",
+                    render_human_time(min),
+                    render_human_time(median),
+                    render_human_time(max),
+                )?;
+            }
+            writeln!(self.writer)?;
+        }
         Ok(())
     }
 }
@@ -0,0 +1,138 @@
+use std::io::Write;
+
+use integra8::formatters::models::{ComponentListing, ComponentType};
+
+use crate::render::{render_attribute, render_human_time};
+use crate::styles::TreeStyle;
+use crate::writer::{Prefix, PrefixedTextWriter};
+
+pub struct ListingTree<'a> {
+    root: &'a ComponentListing,
+}
+
+impl<'a> ListingTree<'a> {
+    pub fn new(root: &'a ComponentListing) -> Self {
+        Self { root }
+    }
+
+    pub fn render_tree<W: Write>(&self, writer: W, style: &TreeStyle) -> std::io::Result<()> {
+        let mut prefixed_text_writer = PrefixedTextWriter::new(writer);
+        self.render_node_branches(&mut prefixed_text_writer, style, self.root)
+    }
+
+    fn render_node_branches<W: Write>(
+        &self,
+        prefixed_writer: &mut PrefixedTextWriter<W>,
+        style: &TreeStyle,
+        node: &ComponentListing,
+    ) -> std::io::Result<()> {
+        // Attributes are written on the lines following the heading,
+        // so continue the branch if the node has children.
+        prefixed_writer.push(match node.components.is_empty() {
+            true => Prefix::next_with(&style.branch.no_branch),
+            false => Prefix::next_with(&style.branch.no_child),
+        });
+        let res = self.render_node(prefixed_writer, style, node);
+        prefixed_writer.pop();
+        res?;
+
+        if let Some((last_child, children)) = node.components.split_last() {
+            for child in children {
+                prefixed_writer
+                    .push(Prefix::with(&style.branch.child).then_next(&style.branch.no_child));
+                let res = self.render_node_branches(prefixed_writer, style, child);
+                prefixed_writer.pop();
+                res?;
+            }
+
+            prefixed_writer
+                .push(Prefix::with(&style.branch.last_child).then_next(&style.branch.no_branch));
+            let res = self.render_node_branches(prefixed_writer, style, last_child);
+            prefixed_writer.pop();
+            res?;
+        }
+        Ok(())
+    }
+
+    fn render_node<W: Write>(
+        &self,
+        prefixed_writer: &mut PrefixedTextWriter<W>,
+        style: &TreeStyle,
+        node: &ComponentListing,
+    ) -> std::io::Result<()> {
+        prefixed_writer.writeln(style.node.listing_heading(node))?;
+        prefixed_writer.push(Prefix::with(&style.branch.attribute_indent));
+
+        let description = &node.description;
+        render_attribute(prefixed_writer, style, "path", description.path().as_str())?;
+
+        if let Some(text) = description.description() {
+            render_attribute(prefixed_writer, style, "description", text)?;
+        }
+
+        if let Some(doc) = description.doc() {
+            render_attribute(prefixed_writer, style, "doc", doc)?;
+        }
+
+        // Suites are declared by their module, which has no meaningful location
+        if description.component_type() != &ComponentType::Suite {
+            render_attribute(
+                prefixed_writer,
+                style,
+                "src",
+                &description.location().hotlink_text(),
+            )?;
+        }
+
+        if !description.tags().is_empty() {
+            render_attribute(
+                prefixed_writer,
+                style,
+                "tags",
+                &description.tags().to_string(),
+            )?;
+        }
+
+        for (key, value) in description.metadata().iter() {
+            render_attribute(prefixed_writer, style, key, value)?;
+        }
+
+        let attributes = &node.attributes;
+        if let Some(time_limit) = &attributes.time_limit {
+            render_attribute(
+                prefixed_writer,
+                style,
+                "time limit",
+                &render_human_time(time_limit),
+            )?;
+        }
+
+        if let Some(warning_time_limit) = &attributes.warning_time_limit {
+            render_attribute(
+                prefixed_writer,
+                style,
+                "warning time limit",
+                &render_human_time(warning_time_limit),
+            )?;
+        }
+
+        render_attribute(
+            prefixed_writer,
+            style,
+            "concurrency",
+            &format!("{:?}", attributes.concurrency_mode),
+        )?;
+
+        if attributes.ignore {
+            render_attribute(prefixed_writer, style, "ignore", "true")?;
+        }
+
+        if attributes.allow_fail {
+            render_attribute(prefixed_writer, style, "allow fail", "true")?;
+        }
+
+        prefixed_writer.write_newline()?;
+        prefixed_writer.pop();
+        Ok(())
+    }
+}
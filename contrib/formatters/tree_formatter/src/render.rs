@@ -8,7 +8,7 @@ use crate::writer::{Prefix, PrefixedTextWriter};
 use integra8::formatters::models::report::ComponentRunReport;
 use integra8::formatters::models::ComponentResult;
 use integra8::formatters::models::ComponentType;
-use integra8::formatters::models::{FailureReason, WarningReason};
+use integra8::formatters::models::{DidNotRunReason, FailureReason, WarningReason};
 
 pub fn render_component_heading<W: Write>(
     output_formatter: &mut PrefixedTextWriter<W>,
@@ -25,6 +25,16 @@ pub fn render_component_heading<W: Write>(
                 render_human_time(&report.timing.duration())
             ),
         ),
+        ComponentResult::Fail(FailureReason::RunTimeLimitExceeded) => {
+            style.node.component_heading_with_remark(
+                report,
+                report.description.friendly_name(),
+                &format!(
+                    "run time limit exceeded {}",
+                    render_human_time(&report.timing.duration())
+                ),
+            )
+        }
         ComponentResult::Warning(WarningReason::FailureAllowed) => {
             style.node.component_heading_with_remark(
                 report,
@@ -42,6 +52,30 @@ pub fn render_component_heading<W: Write>(
                 ),
             )
         }
+        ComponentResult::Warning(WarningReason::PassedAfterRetry) => {
+            style.node.component_heading_with_remark(
+                report,
+                report.description.friendly_name(),
+                &format!("passed after {} attempts", report.attempts.len()),
+            )
+        }
+        ComponentResult::Warning(WarningReason::UserWarning) => {
+            style.node.component_heading_with_remark(
+                report,
+                report.description.friendly_name(),
+                "passed with warnings",
+            )
+        }
+        ComponentResult::DidNotRun(DidNotRunReason::Skipped(reason)) => style
+            .node
+            .component_heading_with_remark(report, report.description.friendly_name(), reason),
+        ComponentResult::DidNotRun(DidNotRunReason::RunAborted) => {
+            style.node.component_heading_with_remark(
+                report,
+                report.description.friendly_name(),
+                "run aborted",
+            )
+        }
         ComponentResult::DidNotRun(_) => style
             .node
             .component_heading(report, report.description.friendly_name()),
@@ -78,6 +112,13 @@ pub fn render_node_attributes<W: Write>(
         has_attributes = true;
     }
 
+    if detail_level == &DetailLevel::Verbose {
+        for (key, value) in report.description.metadata().iter() {
+            render_attribute(output_formatter, style, key, value)?;
+            has_attributes = true;
+        }
+    }
+
     // Don't write location for suites
     if report.description.component_type() != &ComponentType::Suite {
         render_attribute(
@@ -104,6 +145,11 @@ pub fn render_node_attributes<W: Write>(
         }
     }
 
+    if let Some(rerun_command) = report.rerun_command() {
+        render_attribute(output_formatter, style, "rerun", &rerun_command)?;
+        has_attributes = true;
+    }
+
     for (key, artifact) in &report.artifacts.map {
         match &artifact.as_string() {
             Ok(val) => render_attribute(output_formatter, style, &key, &val)?,
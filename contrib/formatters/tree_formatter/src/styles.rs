@@ -1,6 +1,6 @@
 use ansi_term::Colour::{Cyan, Green, Purple, Red, Yellow};
 use integra8::formatters::models::report::ComponentRunReport;
-use integra8::formatters::models::{ComponentResult, ComponentType};
+use integra8::formatters::models::{ComponentListing, ComponentResult, ComponentType};
 
 use crate::parameters::AnsiMode;
 use crate::parameters::Encoding;
@@ -321,6 +321,25 @@ impl ComponentTypeStyle {
         format!("{} - {} ({})", icon, heading, remark)
     }
 
+    pub fn listing_heading(&self, listing: &ComponentListing) -> String {
+        let component_type = match listing.description.component_type() {
+            ComponentType::Suite => "Suite",
+            ComponentType::Test => "Test",
+            ComponentType::Setup => "Setup",
+            ComponentType::TearDown => "Tear Down",
+        };
+
+        let heading = format!(
+            "{} - {}",
+            component_type,
+            listing.description.friendly_name()
+        );
+        match listing.attributes.ignore {
+            true => self.format.apply_skipped_formatting(heading),
+            false => heading,
+        }
+    }
+
     pub fn attribute_style(&self, attribute_name: &str) -> String {
         self.format
             .apply_attribute_formatting(&format!("{}:", attribute_name))
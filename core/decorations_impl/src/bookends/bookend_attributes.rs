@@ -1,16 +1,23 @@
 use std::mem;
 use std::time::Duration;
-use syn::{parse_quote, Attribute, Expr, Lit, Path, Result};
+use syn::{parse_quote, Attribute, Expr, Lit, LitStr, Path, Result};
 
 use crate::parse;
+use crate::retry::Retry;
+use crate::skip_if::SkipIf;
 
 pub struct BookendAttributes {
     integra8_path: Option<Path>,
     name: Option<Lit>,
     description: Option<Lit>,
+    doc: Vec<String>,
+    tags: Vec<Lit>,
+    metadata: Vec<(String, LitStr)>,
     time_limit: Option<Duration>,
     parallel_enabled: Option<bool>,
     ignore: Option<bool>,
+    skip_if: Option<SkipIf>,
+    retry: Option<Retry>,
 }
 
 impl BookendAttributes {
@@ -19,9 +26,14 @@ impl BookendAttributes {
             integra8_path: None,
             name: None,
             description: None,
+            doc: Vec::new(),
+            tags: Vec::new(),
+            metadata: Vec::new(),
             ignore: None,
+            skip_if: None,
             time_limit: None,
             parallel_enabled: None,
+            retry: None,
         };
 
         for attr in attrs.drain(..) {
@@ -43,12 +55,36 @@ impl BookendAttributes {
                 continue;
             }
 
+            // /// the doc comment of this setup / teardown
+            if let Some(line) = parse::try_parse_doc(&attr)? {
+                builder.doc.push(line);
+                continue;
+            }
+
+            // #[tag = "smoke"]
+            if let Some(tag) = parse::try_parse_lit(&attr, "tag")? {
+                builder.tags.push(tag);
+                continue;
+            }
+
+            // #[meta(owner = "payments", severity = "p1")]
+            if let Some(metadata) = parse::try_parse_key_value_args(&attr, "meta")? {
+                builder.metadata.extend(metadata);
+                continue;
+            }
+
             // #[time_limit = "1m 30s"]
             if let Some(duration) = parse::try_parse_duration(&attr, "time_limit")? {
                 builder.time_limit = Some(duration);
                 continue;
             }
 
+            // #[retry(times = 3, delay = "2s", backoff = "exponential")]
+            if let Some(retry) = Retry::try_parse(&attr)? {
+                builder.retry = Some(retry);
+                continue;
+            }
+
             // #[parallel]
             if let Some(flag) = parse::try_parse_flag(&attr, "parallel")? {
                 builder.parallel_enabled = Some(flag);
@@ -61,6 +97,12 @@ impl BookendAttributes {
                 continue;
             }
 
+            // #[skip_if(!parameters.app.payments_enabled, "payments are disabled")]
+            if let Some(skip_if) = SkipIf::try_parse(&attr)? {
+                builder.skip_if = Some(skip_if);
+                continue;
+            }
+
             // #[ignore]
             if let Some(flag) = parse::try_parse_flag(&attr, "ignore")? {
                 builder.ignore = Some(flag);
@@ -88,12 +130,37 @@ impl BookendAttributes {
             .unwrap_or_else(|| parse_quote!(None))
     }
 
+    pub fn take_doc(&mut self) -> Expr {
+        let doc = mem::take(&mut self.doc).join("\n");
+        match doc.trim() {
+            "" => parse_quote!(None),
+            doc => parse_quote!(Some(#doc)),
+        }
+    }
+
+    pub fn take_tags(&mut self) -> Expr {
+        let tags = mem::take(&mut self.tags);
+        parse_quote!(&[#(#tags),*])
+    }
+
+    pub fn take_metadata(&mut self) -> Expr {
+        let (keys, values): (Vec<String>, Vec<LitStr>) =
+            mem::take(&mut self.metadata).into_iter().unzip();
+        parse_quote!(&[#((#keys, #values)),*])
+    }
+
     pub fn take_ignore(&mut self) -> Expr {
         mem::take(&mut self.ignore)
             .map(|val| parse_quote!(Some(#val)))
             .unwrap_or_else(|| parse_quote!(None))
     }
 
+    pub fn take_skip_if(&mut self, integra8_path: &Path) -> Expr {
+        mem::take(&mut self.skip_if)
+            .map(|skip_if| skip_if.to_expr(integra8_path))
+            .unwrap_or_else(|| parse_quote!(None))
+    }
+
     pub fn take_time_limit(&mut self) -> Expr {
         match mem::take(&mut self.time_limit) {
             Some(duration) => {
@@ -120,4 +187,10 @@ impl BookendAttributes {
             }
         }
     }
+
+    pub fn take_retry(&mut self, integra8_path: &Path) -> Expr {
+        mem::take(&mut self.retry)
+            .map(|retry| retry.to_expr(integra8_path))
+            .unwrap_or_else(|| parse_quote!(None))
+    }
 }
@@ -1,4 +1,6 @@
 mod bookend_attributes;
 mod register_bookend;
 
-pub use register_bookend::{register_setup, register_teardown};
+pub use register_bookend::{
+    register_after_each, register_before_each, register_setup, register_teardown,
+};
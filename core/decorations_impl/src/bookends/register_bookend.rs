@@ -17,9 +17,14 @@ pub fn register_teardown(input_tokens: TokenStream) -> TokenStream {
     let integra8_path = test_attr.take_integra8_path();
     let name_expr = test_attr.take_name();
     let description_expr = test_attr.take_description();
+    let doc_expr = test_attr.take_doc();
+    let tags_expr = test_attr.take_tags();
+    let metadata_expr = test_attr.take_metadata();
     let ignore_expr = test_attr.take_ignore();
+    let skip_if_expr = test_attr.take_skip_if(&integra8_path);
     let time_limit_expr = test_attr.take_time_limit();
     let concurrency_mode_expr = test_attr.take_concurrency_mode(&integra8_path);
+    let retry_expr = test_attr.take_retry(&integra8_path);
 
     // Fn
     let mut teardown_fn = ExecFn::from(decorated_fn, &integra8_path);
@@ -45,10 +50,16 @@ pub fn register_teardown(input_tokens: TokenStream) -> TokenStream {
                         desc: #integra8_path ::decorations::BookEndAttributesDecoration {
                             name: #name_expr,
                             description: #description_expr,
+                            doc: #doc_expr,
+                            tags: #tags_expr,
+                            metadata: #metadata_expr,
                             location: #integra8_path ::src_loc!(),
                             ignore: #ignore_expr,
+                            skip_if: #skip_if_expr,
+                            filtered: None,
                             time_limit: #time_limit_expr,
                             concurrency_mode: #concurrency_mode_expr,
+                            retry: #retry_expr,
                         },
                         bookend_fn: #delegate_expr,
                     }
@@ -72,12 +83,17 @@ pub fn register_setup(input_tokens: TokenStream) -> TokenStream {
     let integra8_path = test_attr.take_integra8_path();
     let name_expr = test_attr.take_name();
     let description_expr = test_attr.take_description();
+    let doc_expr = test_attr.take_doc();
+    let tags_expr = test_attr.take_tags();
+    let metadata_expr = test_attr.take_metadata();
     let ignore_expr = test_attr.take_ignore();
+    let skip_if_expr = test_attr.take_skip_if(&integra8_path);
     let time_limit_expr = test_attr.take_time_limit();
     let concurrency_mode_expr = test_attr.take_concurrency_mode(&integra8_path);
+    let retry_expr = test_attr.take_retry(&integra8_path);
 
     // Fn
-    let mut setup_fn = ExecFn::from(decorated_fn, &integra8_path);
+    let mut setup_fn = ExecFn::from_setup(decorated_fn, &integra8_path);
     let setup_method = setup_fn.take_exec_fn();
     let delegate_expr = setup_fn.take_delegate_expr();
 
@@ -100,10 +116,101 @@ pub fn register_setup(input_tokens: TokenStream) -> TokenStream {
                         desc: #integra8_path ::decorations::BookEndAttributesDecoration {
                             name: #name_expr,
                             description: #description_expr,
+                            doc: #doc_expr,
+                            tags: #tags_expr,
+                            metadata: #metadata_expr,
                             location: #integra8_path ::src_loc!(),
                             ignore: #ignore_expr,
+                            skip_if: #skip_if_expr,
+                            filtered: None,
                             time_limit: #time_limit_expr,
                             concurrency_mode: #concurrency_mode_expr,
+                            retry: #retry_expr,
+                        },
+                        bookend_fn: #delegate_expr,
+                    }
+                )
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
+
+pub fn register_before_each(input_tokens: TokenStream) -> TokenStream {
+    register_each_hook(input_tokens, quote!(BeforeEach))
+}
+
+pub fn register_after_each(input_tokens: TokenStream) -> TokenStream {
+    register_each_hook(input_tokens, quote!(AfterEach))
+}
+
+/// Registers a hook which is run before or after each test within its suite and the suite's nested suites.
+///
+/// # Arguments
+///
+/// * `input_tokens` - The tokens of the decorated function.
+///
+/// * `decoration_variant` - The `ComponentDecoration` variant the hook is registered as.
+///
+fn register_each_hook(
+    input_tokens: TokenStream,
+    decoration_variant: proc_macro2::TokenStream,
+) -> TokenStream {
+    let mut decorated_fn = parse_macro_input!(input_tokens as ItemFn);
+
+    let mut hook_attr = match BookendAttributes::take_from(&mut decorated_fn.attrs) {
+        Ok(hook_attr) => hook_attr,
+        Err(err) => return syn::Error::into_compile_error(err).into(),
+    };
+
+    // Attributes
+    let integra8_path = hook_attr.take_integra8_path();
+    let name_expr = hook_attr.take_name();
+    let description_expr = hook_attr.take_description();
+    let doc_expr = hook_attr.take_doc();
+    let tags_expr = hook_attr.take_tags();
+    let metadata_expr = hook_attr.take_metadata();
+    let ignore_expr = hook_attr.take_ignore();
+    let skip_if_expr = hook_attr.take_skip_if(&integra8_path);
+    let time_limit_expr = hook_attr.take_time_limit();
+    let concurrency_mode_expr = hook_attr.take_concurrency_mode(&integra8_path);
+    let retry_expr = hook_attr.take_retry(&integra8_path);
+
+    // Fn
+    let mut hook_fn = ExecFn::from(decorated_fn, &integra8_path);
+    let hook_method = hook_fn.take_exec_fn();
+    let delegate_expr = hook_fn.take_delegate_expr();
+
+    let hook_name_ident = &hook_method.sig.ident;
+
+    let tokens = quote! {
+        #hook_method
+
+        pub(crate) mod #hook_name_ident {
+
+            use crate::REGISTERED_COMPONENTS;
+
+            #[#integra8_path ::linkme::distributed_slice(REGISTERED_COMPONENTS)]
+            #[linkme(crate = #integra8_path ::linkme)]
+            static REGISTERER_COMPONENTS: fn() -> #integra8_path ::decorations::ComponentDecoration<crate::Parameters> = hook_def;
+
+            pub(crate) fn hook_def() -> #integra8_path ::decorations::ComponentDecoration<crate::Parameters> {
+                #integra8_path ::decorations::ComponentDecoration::#decoration_variant(
+                    #integra8_path ::decorations::BookEndDecoration {
+                        desc: #integra8_path ::decorations::BookEndAttributesDecoration {
+                            name: #name_expr,
+                            description: #description_expr,
+                            doc: #doc_expr,
+                            tags: #tags_expr,
+                            metadata: #metadata_expr,
+                            location: #integra8_path ::src_loc!(),
+                            ignore: #ignore_expr,
+                            skip_if: #skip_if_expr,
+                            filtered: None,
+                            time_limit: #time_limit_expr,
+                            concurrency_mode: #concurrency_mode_expr,
+                            retry: #retry_expr,
                         },
                         bookend_fn: #delegate_expr,
                     }
@@ -1,100 +1,130 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
 use std::mem;
-use syn::{parse_quote, Expr, FnArg, ItemFn, Path};
+use syn::{
+    parse_quote, Expr, FnArg, GenericArgument, ItemFn, LitStr, Path, PathArguments, ReturnType,
+    Type,
+};
 
 pub struct ExecFn {
     exec_fn: Option<ItemFn>,
     delegate_expr: Option<Expr>,
+    state_output: SuiteStateOutput,
 }
 
-impl ExecFn {
-    pub fn from(exec_fn: syn::ItemFn, integra8_path: &Path) -> Self {
-        enum Asyncness {
-            Async,
-            Synchronous,
-        }
+/// How the value returned by a function is stored as suite state
+#[derive(Clone, Copy)]
+enum SuiteStateOutput {
+    /// The function returns `()` or `Result<(), E>`
+    None,
+    /// The function returns the value to store
+    Value,
+    /// The function returns a `Result` containing the value to store
+    Result,
+}
 
-        enum Signature {
-            HasParameters,
-            NoParameters,
-        }
+impl SuiteStateOutput {
+    fn from_return_type(output: &ReturnType) -> Self {
+        let ty = match output {
+            ReturnType::Default => return Self::None,
+            ReturnType::Type(_, ty) => ty,
+        };
 
-        use Asyncness::{Async, Synchronous};
-        use Signature::{HasParameters, NoParameters};
+        match &**ty {
+            Type::Tuple(tuple) if tuple.elems.is_empty() => Self::None,
+            Type::Path(path) => match path.path.segments.last() {
+                Some(segment) if segment.ident == "Result" => match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => match args.args.first() {
+                        Some(GenericArgument::Type(Type::Tuple(tuple)))
+                            if tuple.elems.is_empty() =>
+                        {
+                            Self::None
+                        }
+                        _ => Self::Result,
+                    },
+                    _ => Self::Result,
+                },
+                _ => Self::Value,
+            },
+            _ => Self::Value,
+        }
+    }
+}
 
-        let asyncness = match exec_fn.sig.asyncness.is_some() {
-            true => Async,
-            false => Synchronous,
-        };
+impl ExecFn {
+    pub fn from(exec_fn: syn::ItemFn, integra8_path: &Path) -> Self {
+        Self::new(exec_fn, integra8_path, SuiteStateOutput::None)
+    }
 
-        let parameters = match exec_fn.sig.inputs.is_empty() {
-            true => NoParameters,
-            false => HasParameters,
-        };
+    /// Creates a setup function, which can return a value (either directly or within a `Result`)
+    /// to be stored as the state of its suite.
+    pub fn from_setup(exec_fn: syn::ItemFn, integra8_path: &Path) -> Self {
+        let state_output = SuiteStateOutput::from_return_type(&exec_fn.sig.output);
+        Self::new(exec_fn, integra8_path, state_output)
+    }
 
+    fn new(exec_fn: syn::ItemFn, integra8_path: &Path, state_output: SuiteStateOutput) -> Self {
         let fn_name_ident = &exec_fn.sig.ident;
-        let delegate_expr = match (asyncness, parameters) {
-            (Async, HasParameters) => {
-                match exec_fn.sig.inputs.first().unwrap() {
-                    FnArg::Receiver(_) => {
-                        // This obviously wont work, but should produce a some what meaningful error
-                        /*
-                              7 | #[integration_test]
-                                | ^^^^^^^^^^^^^^^^^^^ incorrect number of function parameters
-                                |
-                                = note: expected fn pointer `fn(crate::components::ExecutionContext<BaseParameters<EmptySettingsExtension, TreeFormatterParameters>>) -> Pin<_>`
-                                                found fn item `fn() -> Pin<_> {test2::test_def::wrap_pin}`
-                        */
-                        parse_quote!(
-                            {
-                                fn #fn_name_ident () -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + 'static>> {
-                                    Box::pin(super:: #fn_name_ident ())
-                                }
-                                #integra8_path ::components::delegates::Delegate::async_with_context(#fn_name_ident)
-                            }
-                        )
-                    }
-                    FnArg::Typed(p) => {
-                        let ty = &p.ty;
-                        parse_quote!(
-                            {
-                                fn #fn_name_ident (ctx : #ty) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + 'static>> {
-                                    Box::pin(super:: #fn_name_ident (ctx))
-                                }
-                                #integra8_path ::components::delegates::Delegate::async_with_context(#fn_name_ident )
-                            }
-                        )
-                    }
-                }
-            }
-            (Async, NoParameters) => {
-                parse_quote!(
-                    {
-                        fn wrap_pin() -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send + 'static>> {
-                            Box::pin(super:: #fn_name_ident ())
-                        }
-
-                        #integra8_path ::components::delegates::Delegate::async_without_context(wrap_pin)
-                    }
-                )
-            }
-            (Synchronous, HasParameters) => {
-                parse_quote!(
-                    #integra8_path ::components::delegates::Delegate::sync_with_context(super:: #fn_name_ident)
-                )
-            }
-            (Synchronous, NoParameters) => {
-                parse_quote!(
-                    #integra8_path ::components::delegates::Delegate::sync_without_context(super:: #fn_name_ident)
-                )
-            }
-        };
+        let delegate_expr = build_delegate_expr(
+            &exec_fn,
+            integra8_path,
+            &parse_quote!(super:: #fn_name_ident),
+            quote!(),
+            &[],
+            state_output,
+        );
 
         Self {
             exec_fn: Some(exec_fn),
             delegate_expr: Some(delegate_expr),
+            state_output,
         }
     }
 
+    /// Returns a delegate which calls the function with the arguments of a test case.
+    /// The case is expected to be registered within a module nested one level deeper then the delegate returned by `take_delegate_expr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `integra8_path` - The path to the integra8 crate.
+    ///
+    /// * `case_args` - The arguments to pass to the function, following the execution context and suite state if the function accepts them.
+    ///
+    pub fn case_delegate_expr(&self, integra8_path: &Path, case_args: &[Expr]) -> Expr {
+        let exec_fn = self.exec_fn.as_ref().unwrap();
+        let fn_name_ident = &exec_fn.sig.ident;
+        build_delegate_expr(
+            exec_fn,
+            integra8_path,
+            &parse_quote!(super::super:: #fn_name_ident),
+            quote!(),
+            case_args,
+            self.state_output,
+        )
+    }
+
+    /// Returns a delegate which calls the function with a record loaded from a data source.
+    /// The record is passed as the last parameter, following the execution context and suite state if the function accepts them.
+    ///
+    /// # Arguments
+    ///
+    /// * `integra8_path` - The path to the integra8 crate.
+    ///
+    /// * `data_source` - The pattern used to locate the data source files.
+    ///
+    pub fn data_source_delegate_expr(&self, integra8_path: &Path, data_source: &LitStr) -> Expr {
+        let exec_fn = self.exec_fn.as_ref().unwrap();
+        let fn_name_ident = &exec_fn.sig.ident;
+        build_delegate_expr(
+            exec_fn,
+            integra8_path,
+            &parse_quote!(super:: #fn_name_ident),
+            quote!(let record = #integra8_path ::decorations::DataSource::new(#data_source).record(&ctx);),
+            &[parse_quote!(record)],
+            self.state_output,
+        )
+    }
+
     pub fn take_exec_fn(&mut self) -> ItemFn {
         mem::take(&mut self.exec_fn).unwrap()
     }
@@ -103,3 +133,197 @@ impl ExecFn {
         mem::take(&mut self.delegate_expr).unwrap()
     }
 }
+
+/// Builds a delegate which calls the function.
+/// Any parameter not provided by `trailing_args` is expected to be either the execution context,
+/// or suite state received as `&T` or `Arc<T>`.
+///
+/// # Arguments
+///
+/// * `exec_fn` - The function being called.
+///
+/// * `integra8_path` - The path to the integra8 crate.
+///
+/// * `fn_path` - The path used to call the function.
+///
+/// * `prelude` - Statements to execute before calling the function, which may use the execution context `ctx`.
+///
+/// * `trailing_args` - The arguments to pass to the function, following the execution context and suite state.
+///
+/// * `state_output` - How the value returned by the function is stored as suite state.
+///
+fn build_delegate_expr(
+    exec_fn: &ItemFn,
+    integra8_path: &Path,
+    fn_path: &Path,
+    mut prelude: TokenStream,
+    trailing_args: &[Expr],
+    state_output: SuiteStateOutput,
+) -> Expr {
+    let delegates = quote!(#integra8_path ::components::delegates);
+    let context_count = exec_fn.sig.inputs.len().saturating_sub(trailing_args.len());
+
+    let mut inputs = quote!(ctx : #integra8_path ::components::ExecutionContext<crate::Parameters>);
+    let mut with_context = context_count > 0 || !prelude.is_empty();
+    let mut args = Vec::new();
+
+    for (i, input) in exec_fn.sig.inputs.iter().take(context_count).enumerate() {
+        match input {
+            FnArg::Receiver(_) => {
+                // This obviously wont work, but should produce a some what meaningful error
+                /*
+                      7 | #[integration_test]
+                        | ^^^^^^^^^^^^^^^^^^^ incorrect number of function parameters
+                        |
+                        = note: expected fn pointer `fn(crate::components::ExecutionContext<BaseParameters<EmptySettingsExtension, TreeFormatterParameters>>) -> Pin<_>`
+                                        found fn item `fn() -> Pin<_> {test2::test_def::wrap_pin}`
+                */
+                inputs = quote!();
+            }
+            FnArg::Typed(p) => match suite_state_type(&p.ty) {
+                Some((ty, by_ref)) => {
+                    let state_ident = format_ident!("state_{}", i);
+                    prelude.extend(quote!(
+                        let #state_ident = match ctx.state.get::<#ty>() {
+                            Ok(value) => value,
+                            Err(err) => return #delegates ::IntoDelegateResult::into_delegate_result(Err::<(), _>(err)),
+                        };
+                    ));
+                    args.push(match by_ref {
+                        true => quote!(&* #state_ident),
+                        false => quote!(#state_ident),
+                    });
+                }
+                None => {
+                    let ty = &p.ty;
+                    inputs = quote!(ctx : #ty);
+                    args.push(quote!(ctx));
+                }
+            },
+        }
+    }
+    args.extend(trailing_args.iter().map(|arg| quote!(#arg)));
+
+    let mut call = quote!(#fn_path (#(#args),*));
+    if exec_fn.sig.asyncness.is_some() {
+        call = quote!(#call.await);
+    }
+
+    let result = match state_output {
+        SuiteStateOutput::None => {
+            quote!(#integra8_path ::into_delegate_result!(#call))
+        }
+        SuiteStateOutput::Value => {
+            quote!(#delegates ::IntoDelegateResult::into_delegate_result(state.insert(#call)))
+        }
+        SuiteStateOutput::Result => quote!(
+            match #call {
+                Ok(value) => #delegates ::IntoDelegateResult::into_delegate_result(state.insert(value)),
+                Err(err) => #integra8_path ::into_delegate_result!(Err::<(), _>(err)),
+            }
+        ),
+    };
+
+    if let SuiteStateOutput::Value | SuiteStateOutput::Result = state_output {
+        // The context may be moved into the call, so the state is taken beforehand
+        prelude.extend(quote!(let state = ctx.state.clone();));
+        with_context = true;
+    }
+
+    if !with_context {
+        inputs = quote!();
+    }
+
+    wrap_delegate_expr(
+        exec_fn,
+        integra8_path,
+        inputs,
+        prelude,
+        result,
+        with_context,
+    )
+}
+
+/// Returns the type of suite state a parameter receives, and true if it is received by reference,
+/// or none if the parameter is not given as `&T` or `Arc<T>`.
+fn suite_state_type(ty: &Type) -> Option<(&Type, bool)> {
+    match ty {
+        Type::Reference(reference) if reference.mutability.is_none() => {
+            Some((&reference.elem, true))
+        }
+        Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            if segment.ident != "Arc" {
+                return None;
+            }
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                    match args.args.first() {
+                        Some(GenericArgument::Type(ty)) => Some((ty, false)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Builds a delegate which wraps a call to the function.
+///
+/// # Arguments
+///
+/// * `exec_fn` - The function being called.
+///
+/// * `integra8_path` - The path to the integra8 crate.
+///
+/// * `inputs` - The parameters of the wrapper function.
+///
+/// * `prelude` - Statements to execute before calling the function.
+///
+/// * `result` - The expression calling the function, and converting its return value into a `DelegateResult`.
+///
+/// * `with_context` - True if the wrapper accepts the execution context.
+///
+fn wrap_delegate_expr(
+    exec_fn: &ItemFn,
+    integra8_path: &Path,
+    inputs: TokenStream,
+    prelude: TokenStream,
+    result: TokenStream,
+    with_context: bool,
+) -> Expr {
+    let fn_name_ident = &exec_fn.sig.ident;
+    let delegates = quote!(#integra8_path ::components::delegates);
+
+    let constructor = match (exec_fn.sig.asyncness.is_some(), with_context) {
+        (true, true) => quote!(async_with_context),
+        (true, false) => quote!(async_without_context),
+        (false, true) => quote!(sync_with_context),
+        (false, false) => quote!(sync_without_context),
+    };
+
+    match exec_fn.sig.asyncness.is_some() {
+        true => parse_quote!(
+            {
+                fn #fn_name_ident (#inputs) -> #delegates ::DelegateFuture {
+                    Box::pin(async move {
+                        #prelude
+                        #result
+                    })
+                }
+                #delegates ::Delegate:: #constructor (#fn_name_ident)
+            }
+        ),
+        false => parse_quote!(
+            {
+                fn #fn_name_ident (#inputs) -> #delegates ::DelegateResult {
+                    #prelude
+                    #result
+                }
+                #delegates ::Delegate:: #constructor (#fn_name_ident)
+            }
+        ),
+    }
+}
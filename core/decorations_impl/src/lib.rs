@@ -5,8 +5,11 @@ use proc_macro::TokenStream;
 mod bookends;
 mod exec_fn;
 mod parse;
+mod retry;
+mod skip_if;
 mod suite;
 mod test;
+mod test_factory;
 
 #[proc_macro_attribute]
 pub fn integration_test(_args_tokens: TokenStream, input_tokens: TokenStream) -> TokenStream {
@@ -27,3 +30,18 @@ pub fn teardown(_args_tokens: TokenStream, input_tokens: TokenStream) -> TokenSt
 pub fn setup(_args_tokens: TokenStream, input_tokens: TokenStream) -> TokenStream {
     bookends::register_setup(input_tokens)
 }
+
+#[proc_macro_attribute]
+pub fn before_each(_args_tokens: TokenStream, input_tokens: TokenStream) -> TokenStream {
+    bookends::register_before_each(input_tokens)
+}
+
+#[proc_macro_attribute]
+pub fn after_each(_args_tokens: TokenStream, input_tokens: TokenStream) -> TokenStream {
+    bookends::register_after_each(input_tokens)
+}
+
+#[proc_macro_attribute]
+pub fn test_factory(_args_tokens: TokenStream, input_tokens: TokenStream) -> TokenStream {
+    test_factory::register_test_factory(input_tokens)
+}
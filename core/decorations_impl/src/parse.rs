@@ -1,6 +1,7 @@
 use std::time::Duration;
 use syn::parse::{Error, ParseStream};
-use syn::{Attribute, Lit, Meta, MetaNameValue, Path, Result, Token};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Path, Result, Token};
 
 // looking for #[integra8(crate = path::to::integra8)]
 pub fn try_parse_integra8_path(attr: &Attribute) -> Result<Option<Path>> {
@@ -46,6 +47,44 @@ pub fn try_parse_duration(attr: &Attribute, attr_name: &'static str) -> Result<O
     }
 }
 
+// looking for
+// "1m 30s"
+pub fn parse_duration_lit(lit: &LitStr) -> Result<Duration> {
+    humantime::parse_duration(&lit.value()).map_err(|e| {
+        Error::new_spanned(
+            lit,
+            format!(
+                "Unable to parse duration string \"{}\",  {:?}",
+                lit.value(),
+                e.to_string()
+            ),
+        )
+    })
+}
+
+// looking for
+// /// a line of the doc comment
+// which the compiler passes on as #[doc = " a line of the doc comment"]
+pub fn try_parse_doc(attr: &Attribute) -> Result<Option<String>> {
+    if !attr.path.is_ident("doc") {
+        return Ok(None);
+    }
+
+    match attr.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue {
+            lit: Lit::Str(lit_str),
+            ..
+        })) => {
+            let line = lit_str.value();
+            // Remove the space following `///`
+            Ok(Some(line.strip_prefix(' ').unwrap_or(&line).to_string()))
+        }
+        // Other doc attributes, such as #[doc(hidden)] or #[doc = include_str!("...")],
+        // are left to the compiler, and are not part of the description
+        _ => Ok(None),
+    }
+}
+
 // looking for
 // #[{description} = "...")]
 pub fn try_parse_lit(attr: &Attribute, attr_name: &'static str) -> Result<Option<Lit>> {
@@ -62,9 +101,98 @@ pub fn try_parse_lit(attr: &Attribute, attr_name: &'static str) -> Result<Option
     }
 }
 
+// looking for
+// #[{attr_name}("...")]
+pub fn try_parse_str_arg(attr: &Attribute, attr_name: &'static str) -> Result<Option<LitStr>> {
+    if !attr.path.is_ident(attr_name) {
+        return Ok(None);
+    }
+
+    attr.parse_args::<LitStr>()
+        .map(Some)
+        .map_err(|_| Error::new_spanned(attr, format!("expected #[{}(\"...\")]", attr_name)))
+}
+
+pub fn try_parse_path_args(attr: &Attribute, attr_name: &'static str) -> Result<Option<Vec<Path>>> {
+    if !attr.path.is_ident(attr_name) {
+        return Ok(None);
+    }
+
+    attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_separated_nonempty)
+        .map(|paths| Some(paths.into_iter().collect()))
+        .map_err(|_| Error::new_spanned(attr, format!("expected #[{}(path, ...)]", attr_name)))
+}
+
+// looking for
+// #[{attr_name}(key = "value", ...)]
+pub fn try_parse_key_value_args(
+    attr: &Attribute,
+    attr_name: &'static str,
+) -> Result<Option<Vec<(String, LitStr)>>> {
+    if !attr.path.is_ident(attr_name) {
+        return Ok(None);
+    }
+
+    let expected = || {
+        Error::new_spanned(
+            attr,
+            format!("expected #[{}(key = \"value\", ...)]", attr_name),
+        )
+    };
+
+    let nested = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list.nested,
+        _ => return Err(expected()),
+    };
+
+    nested
+        .into_iter()
+        .map(|meta| match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(value),
+                ..
+            })) => match path.get_ident() {
+                Some(key) => Ok((key.to_string(), value)),
+                None => Err(expected()),
+            },
+            _ => Err(expected()),
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(Some)
+}
+
 pub fn try_parse_flag(attr: &Attribute, attr_name: &'static str) -> Result<Option<bool>> {
     match attr.path.is_ident(attr_name) {
         true => Ok(Some(true)),
         false => Ok(None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn should_parse_doc_comment_line() {
+        // Act
+        let line = try_parse_doc(&parse_quote!(#[doc = " a line of the doc comment"])).unwrap();
+
+        // Assert
+        assert_eq!(line, Some("a line of the doc comment".to_string()));
+    }
+
+    #[test]
+    fn should_ignore_doc_attributes_which_are_not_doc_comments() {
+        // Act
+        let hidden = try_parse_doc(&parse_quote!(#[doc(hidden)])).unwrap();
+        let alias = try_parse_doc(&parse_quote!(#[doc(alias = "other_name")])).unwrap();
+        let included = try_parse_doc(&parse_quote!(#[doc = include_str!("README.md")])).unwrap();
+
+        // Assert
+        assert_eq!(hidden, None);
+        assert_eq!(alias, None);
+        assert_eq!(included, None);
+    }
+}
@@ -0,0 +1,97 @@
+use proc_macro2::Span;
+use std::time::Duration;
+use syn::parse::Error;
+use syn::{
+    parse_quote, Attribute, Expr, Ident, Lit, Meta, MetaNameValue, NestedMeta, Path, Result,
+};
+
+use crate::parse;
+
+pub struct Retry {
+    times: u32,
+    delay: Duration,
+    backoff: Ident,
+}
+
+impl Retry {
+    // looking for
+    // #[retry(times = 3, delay = "2s", backoff = "exponential")]
+    pub fn try_parse(attr: &Attribute) -> Result<Option<Self>> {
+        if !attr.path.is_ident("retry") {
+            return Ok(None);
+        }
+
+        let expected = || {
+            Error::new_spanned(
+                attr,
+                "expected #[retry(times = 3, delay = \"2s\", backoff = \"fixed\" | \"exponential\")]",
+            )
+        };
+
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            _ => return Err(expected()),
+        };
+
+        let mut times = None;
+        let mut retry = Self {
+            times: 0,
+            delay: Duration::ZERO,
+            backoff: Ident::new("Fixed", Span::call_site()),
+        };
+
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Int(lit),
+                    ..
+                })) if path.is_ident("times") => {
+                    times = Some(lit.base10_parse::<u32>()?);
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) if path.is_ident("delay") => {
+                    retry.delay = parse::parse_duration_lit(&lit)?;
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) if path.is_ident("backoff") => {
+                    let backoff = match lit.value().as_str() {
+                        "fixed" => "Fixed",
+                        "exponential" => "Exponential",
+                        _ => {
+                            return Err(Error::new_spanned(
+                                &lit,
+                                "expected backoff = \"fixed\" or backoff = \"exponential\"",
+                            ))
+                        }
+                    };
+                    retry.backoff = Ident::new(backoff, lit.span());
+                }
+                _ => return Err(expected()),
+            }
+        }
+
+        retry.times = times.ok_or_else(expected)?;
+        Ok(Some(retry))
+    }
+
+    pub fn to_expr(&self, integra8_path: &Path) -> Expr {
+        let times = self.times;
+        let secs = self.delay.as_secs();
+        let subsec_nanos = self.delay.subsec_nanos();
+        let backoff = &self.backoff;
+        parse_quote!(
+            Some(#integra8_path ::components::RetryPolicy::new(
+                #times,
+                std::time::Duration::new(#secs, #subsec_nanos),
+                #integra8_path ::components::RetryBackoff:: #backoff,
+            ))
+        )
+    }
+}
@@ -0,0 +1,54 @@
+use quote::ToTokens;
+use syn::parse::{Error, ParseStream};
+use syn::{parse_quote, Attribute, Expr, LitStr, Path, Result, Token};
+
+pub struct SkipIf {
+    condition: Expr,
+    reason: String,
+}
+
+impl SkipIf {
+    // looking for
+    // #[skip_if(!parameters.app.payments_enabled)]
+    // #[skip_if(!parameters.app.payments_enabled, "payments are disabled")]
+    pub fn try_parse(attr: &Attribute) -> Result<Option<Self>> {
+        if !attr.path.is_ident("skip_if") {
+            return Ok(None);
+        }
+
+        attr.parse_args_with(|input: ParseStream| {
+            let condition: Expr = input.parse()?;
+
+            // Without a reason, the condition itself is reported as the reason
+            let reason = match input.parse::<Option<Token![,]>>()? {
+                Some(_) => input.parse::<LitStr>()?.value(),
+                None => condition.to_token_stream().to_string(),
+            };
+            Ok(Self { condition, reason })
+        })
+        .map(Some)
+        .map_err(|_| {
+            Error::new_spanned(
+                attr,
+                "expected #[skip_if(condition)] or #[skip_if(condition, \"reason\")]",
+            )
+        })
+    }
+
+    pub fn to_expr(&self, integra8_path: &Path) -> Expr {
+        let condition = &self.condition;
+        let reason = &self.reason;
+        parse_quote!(
+            Some(#integra8_path ::components::SkipCondition::new(
+                #reason,
+                |parameters: &dyn std::any::Any| -> bool {
+                    #[allow(unused_imports)]
+                    use #integra8_path ::components::TestParameters;
+                    #[allow(unused_variables)]
+                    let parameters = parameters.downcast_ref::<crate::Parameters>().unwrap();
+                    #condition
+                },
+            ))
+        )
+    }
+}
@@ -14,13 +14,19 @@ pub fn register_suite(input_tokens: TokenStream) -> TokenStream {
 
     let integra8_path = test_attr.take_integra8_path();
     let ignore_expr = test_attr.take_ignore();
+    let skip_if_expr = test_attr.take_skip_if(&integra8_path);
     let name_expr = test_attr.take_name();
     let description_expr = test_attr.take_description();
+    let doc_expr = test_attr.take_doc();
+    let tags_expr = test_attr.take_tags();
+    let metadata_expr = test_attr.take_metadata();
     let allow_fail_expr = test_attr.take_allow_fail();
     let test_warning_time_limit_expr = test_attr.take_test_warning_time_limit();
     let test_time_limit_expr = test_attr.take_test_time_limit();
     let setup_time_limit_expr = test_attr.take_setup_time_limit();
     let tear_down_time_limit_expr = test_attr.take_tear_down_time_limit();
+    let suite_warning_time_limit_expr = test_attr.take_suite_warning_time_limit();
+    let suite_time_limit_expr = test_attr.take_suite_time_limit();
     let suite_concurrency_mode_expr = test_attr.take_suite_concurrency_mode(&integra8_path);
     let test_concurrency_mode_expr = test_attr.take_test_concurrency_mode(&integra8_path);
 
@@ -46,13 +52,20 @@ pub fn register_suite(input_tokens: TokenStream) -> TokenStream {
                     #integra8_path ::decorations::SuiteAttributesDecoration {
                         name: #name_expr,
                         description: #description_expr,
+                        doc: #doc_expr,
+                        tags: #tags_expr,
+                        metadata: #metadata_expr,
                         location: #integra8_path ::src_loc!(),
                         ignore: #ignore_expr,
+                        skip_if: #skip_if_expr,
+                        filtered: None,
                         allow_suite_fail: #allow_fail_expr,
                         test_warning_time_limit: #test_warning_time_limit_expr,
                         test_time_limit: #test_time_limit_expr,
                         setup_time_limit: #setup_time_limit_expr,
                         tear_down_time_limit: #tear_down_time_limit_expr,
+                        suite_warning_time_limit: #suite_warning_time_limit_expr,
+                        suite_time_limit: #suite_time_limit_expr,
                         suite_concurrency_mode:  #suite_concurrency_mode_expr,
                         test_concurrency_mode:  #test_concurrency_mode_expr,
                     }
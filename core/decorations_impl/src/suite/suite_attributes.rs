@@ -2,20 +2,27 @@ use std::mem;
 use std::time::Duration;
 
 use syn::parse::Error;
-use syn::{parse_quote, Attribute, Expr, Lit, Path, Result};
+use syn::{parse_quote, Attribute, Expr, Lit, LitStr, Path, Result};
 
 use crate::parse;
+use crate::skip_if::SkipIf;
 
 pub struct SuiteAttributes {
     integra8_path: Option<Path>,
     name: Option<Lit>,
     description: Option<Lit>,
+    doc: Vec<String>,
+    tags: Vec<Lit>,
+    metadata: Vec<(String, LitStr)>,
     ignore: Option<bool>,
+    skip_if: Option<SkipIf>,
     allow_fail: Option<bool>,
     test_warning_time_limit: Option<Duration>,
     test_time_limit: Option<Duration>,
     setup_time_limit: Option<Duration>,
     tear_down_time_limit: Option<Duration>,
+    suite_warning_time_limit: Option<Duration>,
+    suite_time_limit: Option<Duration>,
     suite_parallel_enabled: Option<bool>,
     test_parallel_enabled: Option<bool>,
 }
@@ -26,12 +33,18 @@ impl SuiteAttributes {
             integra8_path: None,
             name: None,
             description: None,
+            doc: Vec::new(),
+            tags: Vec::new(),
+            metadata: Vec::new(),
             ignore: None,
+            skip_if: None,
             allow_fail: None,
             test_warning_time_limit: None,
             test_time_limit: None,
             setup_time_limit: None,
             tear_down_time_limit: None,
+            suite_warning_time_limit: None,
+            suite_time_limit: None,
             suite_parallel_enabled: None,
             test_parallel_enabled: None,
         };
@@ -55,6 +68,24 @@ impl SuiteAttributes {
                 continue;
             }
 
+            // /// the doc comment of this suite
+            if let Some(line) = parse::try_parse_doc(&attr)? {
+                builder.doc.push(line);
+                continue;
+            }
+
+            // #[tag = "smoke"]
+            if let Some(tag) = parse::try_parse_lit(&attr, "tag")? {
+                builder.tags.push(tag);
+                continue;
+            }
+
+            // #[meta(owner = "payments", severity = "p1")]
+            if let Some(metadata) = parse::try_parse_key_value_args(&attr, "meta")? {
+                builder.metadata.extend(metadata);
+                continue;
+            }
+
             // #[parallel]
             if let Some(flag) = parse::try_parse_flag(&attr, "parallel")? {
                 builder.suite_parallel_enabled = Some(flag);
@@ -79,6 +110,12 @@ impl SuiteAttributes {
                 continue;
             }
 
+            // #[skip_if(!parameters.app.payments_enabled, "payments are disabled")]
+            if let Some(skip_if) = SkipIf::try_parse(&attr)? {
+                builder.skip_if = Some(skip_if);
+                continue;
+            }
+
             // #[ignore]
             if let Some(flag) = parse::try_parse_flag(&attr, "ignore")? {
                 builder.ignore = Some(flag);
@@ -115,6 +152,18 @@ impl SuiteAttributes {
                 continue;
             }
 
+            // #[suite_warning_time_limit = "5m"]
+            if let Some(duration) = parse::try_parse_duration(&attr, "suite_warning_time_limit")? {
+                builder.suite_warning_time_limit = Some(duration);
+                continue;
+            }
+
+            // #[suite_time_limit = "10m"]
+            if let Some(duration) = parse::try_parse_duration(&attr, "suite_time_limit")? {
+                builder.suite_time_limit = Some(duration);
+                continue;
+            }
+
             return Err(Error::new_spanned(attr, "unexpected attribute"));
         }
 
@@ -141,12 +190,37 @@ impl SuiteAttributes {
             .unwrap_or_else(|| parse_quote!(None))
     }
 
+    pub fn take_doc(&mut self) -> Expr {
+        let doc = mem::take(&mut self.doc).join("\n");
+        match doc.trim() {
+            "" => parse_quote!(None),
+            doc => parse_quote!(Some(#doc)),
+        }
+    }
+
+    pub fn take_tags(&mut self) -> Expr {
+        let tags = mem::take(&mut self.tags);
+        parse_quote!(&[#(#tags),*])
+    }
+
+    pub fn take_metadata(&mut self) -> Expr {
+        let (keys, values): (Vec<String>, Vec<LitStr>) =
+            mem::take(&mut self.metadata).into_iter().unzip();
+        parse_quote!(&[#((#keys, #values)),*])
+    }
+
     pub fn take_ignore(&mut self) -> Expr {
         mem::take(&mut self.ignore)
             .map(|val| parse_quote!(Some(#val)))
             .unwrap_or_else(|| parse_quote!(None))
     }
 
+    pub fn take_skip_if(&mut self, integra8_path: &Path) -> Expr {
+        mem::take(&mut self.skip_if)
+            .map(|skip_if| skip_if.to_expr(integra8_path))
+            .unwrap_or_else(|| parse_quote!(None))
+    }
+
     pub fn take_allow_fail(&mut self) -> Expr {
         mem::take(&mut self.allow_fail)
             .map(|val| parse_quote!(Some(#val)))
@@ -205,6 +279,32 @@ impl SuiteAttributes {
         }
     }
 
+    pub fn take_suite_warning_time_limit(&mut self) -> Expr {
+        match mem::take(&mut self.suite_warning_time_limit) {
+            Some(duration) => {
+                let secs = duration.as_secs();
+                let subsec_nanos = duration.subsec_nanos();
+                parse_quote!(Some(std::time::Duration::new(#secs, #subsec_nanos)))
+            }
+            None => {
+                parse_quote!(None)
+            }
+        }
+    }
+
+    pub fn take_suite_time_limit(&mut self) -> Expr {
+        match mem::take(&mut self.suite_time_limit) {
+            Some(duration) => {
+                let secs = duration.as_secs();
+                let subsec_nanos = duration.subsec_nanos();
+                parse_quote!(Some(std::time::Duration::new(#secs, #subsec_nanos)))
+            }
+            None => {
+                parse_quote!(None)
+            }
+        }
+    }
+
     pub fn take_suite_concurrency_mode(&mut self, integra8_path: &Path) -> Expr {
         match mem::take(&mut self.suite_parallel_enabled) {
             Some(true) => {
@@ -0,0 +1,72 @@
+use std::time::Duration;
+use syn::parse::Error;
+use syn::{parse_quote, Attribute, Expr, Lit, Meta, MetaNameValue, NestedMeta, Path, Result};
+
+use crate::parse;
+
+pub struct Eventually {
+    timeout: Duration,
+    interval: Duration,
+}
+
+impl Eventually {
+    // looking for
+    // #[eventually(timeout = "2m", interval = "5s")]
+    pub fn try_parse(attr: &Attribute) -> Result<Option<Self>> {
+        if !attr.path.is_ident("eventually") {
+            return Ok(None);
+        }
+
+        let expected = || {
+            Error::new_spanned(
+                attr,
+                "expected #[eventually(timeout = \"2m\", interval = \"5s\")]",
+            )
+        };
+
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            _ => return Err(expected()),
+        };
+
+        let mut timeout = None;
+        let mut interval = Duration::from_secs(1);
+
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) if path.is_ident("timeout") => {
+                    timeout = Some(parse::parse_duration_lit(&lit)?);
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(lit),
+                    ..
+                })) if path.is_ident("interval") => {
+                    interval = parse::parse_duration_lit(&lit)?;
+                }
+                _ => return Err(expected()),
+            }
+        }
+
+        Ok(Some(Self {
+            timeout: timeout.ok_or_else(expected)?,
+            interval,
+        }))
+    }
+
+    pub fn to_expr(&self, integra8_path: &Path) -> Expr {
+        let (timeout_secs, timeout_nanos) = (self.timeout.as_secs(), self.timeout.subsec_nanos());
+        let (interval_secs, interval_nanos) =
+            (self.interval.as_secs(), self.interval.subsec_nanos());
+        parse_quote!(
+            Some(#integra8_path ::components::EventuallyPolicy::new(
+                std::time::Duration::new(#timeout_secs, #timeout_nanos),
+                std::time::Duration::new(#interval_secs, #interval_nanos),
+            ))
+        )
+    }
+}
@@ -1,3 +1,5 @@
+mod eventually;
 mod register_test;
 mod test_attributes;
+mod test_case;
 pub use register_test::register_test;
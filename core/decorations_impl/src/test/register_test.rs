@@ -1,9 +1,11 @@
 use crate::exec_fn::ExecFn;
 use crate::test::test_attributes::TestAttributes;
+use crate::test::test_case::TestCase;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, ItemFn};
+use syn::parse::Error;
+use syn::{parse_macro_input, parse_quote, Expr, ItemFn};
 
 pub fn register_test(input_tokens: TokenStream) -> TokenStream {
     let mut decorated_fn = parse_macro_input!(input_tokens as ItemFn);
@@ -15,25 +17,44 @@ pub fn register_test(input_tokens: TokenStream) -> TokenStream {
 
     // Attributes
     let integra8_path = test_attr.take_integra8_path();
+    let cases = test_attr.take_cases();
+    let data_source = test_attr.take_data_source();
+    let test_name = test_attr
+        .name_value()
+        .unwrap_or_else(|| decorated_fn.sig.ident.to_string());
     let ignore_expr = test_attr.take_ignore();
+    let skip_if_expr = test_attr.take_skip_if(&integra8_path);
     let name_expr = test_attr.take_name();
     let description_expr = test_attr.take_description();
+    let doc_expr = test_attr.take_doc();
+    let tags_expr = test_attr.take_tags();
+    let metadata_expr = test_attr.take_metadata();
     let allow_fail_expr = test_attr.take_allow_fail();
     let warn_time_limit_expr = test_attr.take_warn_time_limit();
     let time_limit_expr = test_attr.take_time_limit();
     let concurrency_mode_expr = test_attr.take_concurrency_mode(&integra8_path);
+    let retry_expr = test_attr.take_retry(&integra8_path);
+    let eventually_expr = test_attr.take_eventually(&integra8_path);
+    let depends_on_expr = test_attr.take_depends_on();
 
-    // Fn
-    let mut test_fn = ExecFn::from(decorated_fn, &integra8_path);
-    let test_method = test_fn.take_exec_fn();
-    let delegate_expr = test_fn.take_delegate_expr();
+    if let (Some(data_source), false) = (&data_source, cases.is_empty()) {
+        let err = Error::new_spanned(
+            data_source,
+            "#[data_source(...)] can not be combined with #[case(...)]",
+        );
+        return syn::Error::into_compile_error(err).into();
+    }
 
-    let test_name_ident = &test_method.sig.ident;
+    let data_source_expr: Expr = match &data_source {
+        Some(pattern) => parse_quote!(Some(#pattern)),
+        None => parse_quote!(None),
+    };
 
-    let tokens = quote! {
-        #test_method
+    // Fn
+    let mut test_fn = ExecFn::from(decorated_fn, &integra8_path);
 
-        pub mod #test_name_ident {
+    let test_def = |name_expr: &Expr, location_expr: &Expr, delegate_expr: &Expr| {
+        quote! {
             use crate::REGISTERED_COMPONENTS;
 
             #[#integra8_path ::linkme::distributed_slice(REGISTERED_COMPONENTS)]
@@ -46,12 +67,21 @@ pub fn register_test(input_tokens: TokenStream) -> TokenStream {
                         desc: #integra8_path ::decorations::TestAttributesDecoration {
                            name: #name_expr,
                            description: #description_expr,
-                           location: #integra8_path ::src_loc!(),
+                           doc: #doc_expr,
+                           tags: #tags_expr,
+                           metadata: #metadata_expr,
+                           location: #location_expr,
                            ignore: #ignore_expr,
+                           skip_if: #skip_if_expr,
+                           filtered: None,
+                           data_source: #data_source_expr,
                            allow_fail: #allow_fail_expr,
                            warning_time_limit: #warn_time_limit_expr,
                            time_limit: #time_limit_expr,
                            concurrency_mode: #concurrency_mode_expr,
+                           retry: #retry_expr,
+                           eventually: #eventually_expr,
+                           depends_on: #depends_on_expr,
                         },
                         test_fn: #delegate_expr,
                     }
@@ -60,5 +90,57 @@ pub fn register_test(input_tokens: TokenStream) -> TokenStream {
         }
     };
 
+    // Each test case is registered as its own test, within a module nested under the test's module
+    let case_idents = match TestCase::idents(&cases) {
+        Ok(case_idents) => case_idents,
+        Err(err) => return syn::Error::into_compile_error(err).into(),
+    };
+
+    let mut case_defs = Vec::new();
+    for case in cases.iter() {
+        let case_name = case.display_name(&test_name);
+        let case_name_expr: Expr = parse_quote!(Some(#case_name));
+        let case_delegate_expr = test_fn.case_delegate_expr(&integra8_path, &case.args);
+        let case_location_expr = case.location_expr(&integra8_path);
+        case_defs.push(test_def(
+            &case_name_expr,
+            &case_location_expr,
+            &case_delegate_expr,
+        ));
+    }
+
+    let delegate_expr = match &data_source {
+        Some(pattern) => test_fn.data_source_delegate_expr(&integra8_path, pattern),
+        None => test_fn.take_delegate_expr(),
+    };
+    let test_method = test_fn.take_exec_fn();
+
+    let test_name_ident = &test_method.sig.ident;
+
+    let tokens = match cases.is_empty() {
+        true => {
+            let location_expr = parse_quote!(#integra8_path ::src_loc!());
+            let test_def = test_def(&name_expr, &location_expr, &delegate_expr);
+            quote! {
+                #test_method
+
+                pub mod #test_name_ident {
+                    #test_def
+                }
+            }
+        }
+        false => quote! {
+            #test_method
+
+            pub mod #test_name_ident {
+                #(
+                    pub mod #case_idents {
+                        #case_defs
+                    }
+                )*
+            }
+        },
+    };
+
     TokenStream::from(tokens)
 }
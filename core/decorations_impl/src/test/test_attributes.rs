@@ -2,18 +2,31 @@ use std::mem;
 use std::time::Duration;
 
 use crate::parse;
+use crate::retry::Retry;
+use crate::skip_if::SkipIf;
+use crate::test::eventually::Eventually;
+use crate::test::test_case::TestCase;
 use syn::parse::Error;
-use syn::{parse_quote, Attribute, Expr, Lit, Path, Result};
+use syn::{parse_quote, Attribute, Expr, Lit, LitStr, Path, Result};
 
 pub struct TestAttributes {
     integra8_path: Option<Path>,
     name: Option<Lit>,
     description: Option<Lit>,
+    doc: Vec<String>,
+    tags: Vec<Lit>,
+    metadata: Vec<(String, LitStr)>,
+    cases: Vec<TestCase>,
+    data_source: Option<LitStr>,
     ignore: Option<bool>,
+    skip_if: Option<SkipIf>,
     allow_fail: Option<bool>,
     warn_time_limit: Option<Duration>,
     time_limit: Option<Duration>,
     parallel_enabled: Option<bool>,
+    retry: Option<Retry>,
+    eventually: Option<Eventually>,
+    depends_on: Vec<Path>,
 }
 
 impl TestAttributes {
@@ -22,11 +35,20 @@ impl TestAttributes {
             integra8_path: None,
             name: None,
             description: None,
+            doc: Vec::new(),
+            tags: Vec::new(),
+            metadata: Vec::new(),
+            cases: Vec::new(),
+            data_source: None,
             ignore: None,
+            skip_if: None,
             allow_fail: None,
             warn_time_limit: None,
             time_limit: None,
             parallel_enabled: None,
+            retry: None,
+            eventually: None,
+            depends_on: Vec::new(),
         };
 
         for attr in attrs.drain(..) {
@@ -48,6 +70,36 @@ impl TestAttributes {
                 continue;
             }
 
+            // /// the doc comment of this test
+            if let Some(line) = parse::try_parse_doc(&attr)? {
+                builder.doc.push(line);
+                continue;
+            }
+
+            // #[tag = "smoke"]
+            if let Some(tag) = parse::try_parse_lit(&attr, "tag")? {
+                builder.tags.push(tag);
+                continue;
+            }
+
+            // #[meta(owner = "payments", severity = "p1")]
+            if let Some(metadata) = parse::try_parse_key_value_args(&attr, "meta")? {
+                builder.metadata.extend(metadata);
+                continue;
+            }
+
+            // #[case(1, "two", name = "the name of this case")]
+            if let Some(case) = TestCase::try_parse(&attr)? {
+                builder.cases.push(case);
+                continue;
+            }
+
+            // #[data_source("cases/*.yaml")]
+            if let Some(pattern) = parse::try_parse_str_arg(&attr, "data_source")? {
+                builder.data_source = Some(pattern);
+                continue;
+            }
+
             // #[warning_time_limit = "1m")]
             if let Some(duration) = parse::try_parse_duration(&attr, "warning_time_limit")? {
                 builder.warn_time_limit = Some(duration);
@@ -60,6 +112,24 @@ impl TestAttributes {
                 continue;
             }
 
+            // #[retry(times = 3, delay = "2s", backoff = "exponential")]
+            if let Some(retry) = Retry::try_parse(&attr)? {
+                builder.retry = Some(retry);
+                continue;
+            }
+
+            // #[eventually(timeout = "2m", interval = "5s")]
+            if let Some(eventually) = Eventually::try_parse(&attr)? {
+                builder.eventually = Some(eventually);
+                continue;
+            }
+
+            // #[depends_on(create_order, update_order)]
+            if let Some(paths) = parse::try_parse_path_args(&attr, "depends_on")? {
+                builder.depends_on.extend(paths);
+                continue;
+            }
+
             // #[parallel]
             if let Some(flag) = parse::try_parse_flag(&attr, "parallel")? {
                 builder.parallel_enabled = Some(flag);
@@ -72,6 +142,12 @@ impl TestAttributes {
                 continue;
             }
 
+            // #[skip_if(!parameters.app.payments_enabled, "payments are disabled")]
+            if let Some(skip_if) = SkipIf::try_parse(&attr)? {
+                builder.skip_if = Some(skip_if);
+                continue;
+            }
+
             // #[ignore]
             if let Some(flag) = parse::try_parse_flag(&attr, "ignore")? {
                 builder.ignore = Some(flag);
@@ -90,6 +166,13 @@ impl TestAttributes {
         Ok(builder)
     }
 
+    pub fn name_value(&self) -> Option<String> {
+        match &self.name {
+            Some(Lit::Str(name)) => Some(name.value()),
+            _ => None,
+        }
+    }
+
     // Take values
 
     pub fn take_integra8_path(&mut self) -> Path {
@@ -108,12 +191,45 @@ impl TestAttributes {
             .unwrap_or_else(|| parse_quote!(None))
     }
 
+    pub fn take_doc(&mut self) -> Expr {
+        let doc = mem::take(&mut self.doc).join("\n");
+        match doc.trim() {
+            "" => parse_quote!(None),
+            doc => parse_quote!(Some(#doc)),
+        }
+    }
+
+    pub fn take_tags(&mut self) -> Expr {
+        let tags = mem::take(&mut self.tags);
+        parse_quote!(&[#(#tags),*])
+    }
+
+    pub fn take_metadata(&mut self) -> Expr {
+        let (keys, values): (Vec<String>, Vec<LitStr>) =
+            mem::take(&mut self.metadata).into_iter().unzip();
+        parse_quote!(&[#((#keys, #values)),*])
+    }
+
+    pub fn take_cases(&mut self) -> Vec<TestCase> {
+        mem::take(&mut self.cases)
+    }
+
+    pub fn take_data_source(&mut self) -> Option<LitStr> {
+        mem::take(&mut self.data_source)
+    }
+
     pub fn take_ignore(&mut self) -> Expr {
         mem::take(&mut self.ignore)
             .map(|val| parse_quote!(Some(#val)))
             .unwrap_or_else(|| parse_quote!(None))
     }
 
+    pub fn take_skip_if(&mut self, integra8_path: &Path) -> Expr {
+        mem::take(&mut self.skip_if)
+            .map(|skip_if| skip_if.to_expr(integra8_path))
+            .unwrap_or_else(|| parse_quote!(None))
+    }
+
     pub fn take_allow_fail(&mut self) -> Expr {
         mem::take(&mut self.allow_fail)
             .map(|val| parse_quote!(Some(#val)))
@@ -159,4 +275,27 @@ impl TestAttributes {
             }
         }
     }
+
+    pub fn take_retry(&mut self, integra8_path: &Path) -> Expr {
+        mem::take(&mut self.retry)
+            .map(|retry| retry.to_expr(integra8_path))
+            .unwrap_or_else(|| parse_quote!(None))
+    }
+
+    pub fn take_eventually(&mut self, integra8_path: &Path) -> Expr {
+        mem::take(&mut self.eventually)
+            .map(|eventually| eventually.to_expr(integra8_path))
+            .unwrap_or_else(|| parse_quote!(None))
+    }
+
+    pub fn take_depends_on(&mut self) -> Expr {
+        let depends_on = mem::take(&mut self.depends_on).into_iter().map(|path| {
+            path.segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::")
+        });
+        parse_quote!(&[#(#depends_on),*])
+    }
 }
@@ -0,0 +1,206 @@
+use proc_macro2::Span;
+use quote::{quote, quote_spanned};
+use syn::parse::{Error, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    parse_quote, Attribute, Expr, ExprAssign, ExprLit, Ident, Lit, LitStr, Path, Result, Token,
+};
+
+pub struct TestCase {
+    pub name: Option<LitStr>,
+    pub args: Vec<Expr>,
+    pub span: Span,
+}
+
+impl TestCase {
+    // looking for
+    // #[case(arg1, arg2, name = "...")]
+    pub fn try_parse(attr: &Attribute) -> Result<Option<Self>> {
+        if !attr.path.is_ident("case") {
+            return Ok(None);
+        }
+
+        let exprs = attr.parse_args_with(|input: ParseStream| {
+            Punctuated::<Expr, Token![,]>::parse_terminated(input)
+        })?;
+
+        let mut case = Self {
+            name: None,
+            args: Vec::new(),
+            span: attr.span(),
+        };
+
+        for expr in exprs {
+            match expr {
+                Expr::Assign(ExprAssign { left, right, .. }) if is_ident(&left, "name") => {
+                    match *right {
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(name),
+                            ..
+                        }) => case.name = Some(name),
+                        other => {
+                            return Err(Error::new_spanned(
+                                other,
+                                "expected #[case(..., name = \"...\")]",
+                            ))
+                        }
+                    }
+                }
+                arg => case.args.push(arg),
+            }
+        }
+
+        Ok(Some(case))
+    }
+
+    /// The identifier of the module the case is registered in, which becomes the last segment of its path.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position of the case, starting from 1. Used when the case has no name.
+    ///
+    pub fn ident(&self, index: usize) -> Ident {
+        let ident = match &self.name {
+            Some(name) => {
+                let ident: String = name
+                    .value()
+                    .chars()
+                    .map(|c| match c.is_ascii_alphanumeric() {
+                        true => c.to_ascii_lowercase(),
+                        false => '_',
+                    })
+                    .collect();
+
+                // Names which are not valid identifiers, such as those starting with a digit
+                // or matching a keyword like `type` or `self`, are prefixed to make them valid
+                match ident.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && syn::parse_str::<Ident>(&ident).is_ok()
+                {
+                    true => ident,
+                    false => format!("case_{}", ident),
+                }
+            }
+            None => format!("case_{}", index),
+        };
+        Ident::new(&ident, Span::call_site())
+    }
+
+    /// The identifiers of each case, in the order they are declared.
+    /// Cases which would be registered under the same identifier are rejected, as their paths would collide.
+    ///
+    /// # Arguments
+    ///
+    /// * `cases` - The cases of the test.
+    ///
+    pub fn idents(cases: &[TestCase]) -> Result<Vec<Ident>> {
+        let mut idents: Vec<Ident> = Vec::new();
+        for (i, case) in cases.iter().enumerate() {
+            let ident = case.ident(i + 1);
+            if idents.contains(&ident) {
+                return Err(Error::new(
+                    case.span,
+                    format!("a case named \"{}\" has already been declared", ident),
+                ));
+            }
+            idents.push(ident);
+        }
+        Ok(idents)
+    }
+
+    /// The source location of the case, being the location of its `#[case(...)]` attribute.
+    /// This ensures cases are run in the order they are declared.
+    pub fn location_expr(&self, integra8_path: &Path) -> Expr {
+        let location = quote_spanned! {self.span=>
+            {
+                let mut location = #integra8_path ::src_loc!();
+                location.line = line!();
+                location.column = column!();
+                location
+            }
+        };
+        parse_quote!(#location)
+    }
+
+    /// The name the case is reported with, being the test's name followed by the case name,
+    /// or the case arguments when the case has no name.
+    ///
+    /// # Arguments
+    ///
+    /// * `test_name` - The `#[name = "..."]` of the test, or the name of the test function
+    ///
+    pub fn display_name(&self, test_name: &str) -> String {
+        match &self.name {
+            Some(name) => format!("{} ({})", test_name, name.value()),
+            None => {
+                let args = &self.args;
+                format!("{} ({})", test_name, quote!(#(#args),*))
+            }
+        }
+    }
+}
+
+fn is_ident(expr: &Expr, ident: &str) -> bool {
+    match expr {
+        Expr::Path(expr_path) => expr_path.path.is_ident(ident),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cases(attrs: Vec<Attribute>) -> Vec<TestCase> {
+        attrs
+            .iter()
+            .map(|attr| TestCase::try_parse(attr).unwrap().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn should_name_unnamed_cases_by_position() {
+        // Act
+        let idents = TestCase::idents(&cases(vec![
+            parse_quote!(#[case(1)]),
+            parse_quote!(#[case(2, name = "two")]),
+            parse_quote!(#[case(3)]),
+        ]))
+        .unwrap();
+
+        // Assert
+        let idents = idents.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        assert_eq!(idents, vec!["case_1", "two", "case_3"]);
+    }
+
+    #[test]
+    fn should_prefix_case_names_which_are_keywords() {
+        // Act
+        let idents = TestCase::idents(&cases(vec![
+            parse_quote!(#[case(1, name = "type")]),
+            parse_quote!(#[case(2, name = "Self")]),
+            parse_quote!(#[case(3, name = "1st")]),
+        ]))
+        .unwrap();
+
+        // Assert
+        let idents = idents.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        assert_eq!(idents, vec!["case_type", "case_self", "case_1st"]);
+    }
+
+    #[test]
+    fn should_reject_named_case_colliding_with_unnamed_case() {
+        // Act
+        let result = TestCase::idents(&cases(vec![
+            parse_quote!(#[case(1)]),
+            parse_quote!(#[case(2, name = "case 1")]),
+        ]));
+
+        // Assert
+        let err = result.err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "a case named \"case_1\" has already been declared"
+        );
+    }
+}
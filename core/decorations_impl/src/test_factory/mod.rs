@@ -0,0 +1,2 @@
+mod register_test_factory;
+pub use register_test_factory::register_test_factory;
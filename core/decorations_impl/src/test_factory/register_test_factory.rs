@@ -0,0 +1,53 @@
+use crate::parse;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Expr, ItemFn, Path};
+
+pub fn register_test_factory(input_tokens: TokenStream) -> TokenStream {
+    let mut decorated_fn = parse_macro_input!(input_tokens as ItemFn);
+
+    // Attributes
+    let mut integra8_path: Path = parse_quote!(::integra8);
+    let mut attrs = Vec::new();
+    for attr in decorated_fn.attrs.drain(..) {
+        // #[integra8(crate = path::to::integra8)]
+        match parse::try_parse_integra8_path(&attr) {
+            Ok(Some(path)) => integra8_path = path,
+            Ok(None) => attrs.push(attr),
+            Err(err) => return syn::Error::into_compile_error(err).into(),
+        }
+    }
+    decorated_fn.attrs = attrs;
+
+    // Fn
+    let factory_name_ident = &decorated_fn.sig.ident;
+    let factory_fn_expr: Expr = match decorated_fn.sig.inputs.is_empty() {
+        true => parse_quote!(|_| super::#factory_name_ident()),
+        false => parse_quote!(super::#factory_name_ident),
+    };
+
+    let tokens = quote! {
+        #decorated_fn
+
+        pub(crate) mod #factory_name_ident {
+
+            use crate::REGISTERED_COMPONENTS;
+
+            #[#integra8_path ::linkme::distributed_slice(REGISTERED_COMPONENTS)]
+            #[linkme(crate = #integra8_path ::linkme)]
+            static REGISTERER_COMPONENTS: fn() -> #integra8_path ::decorations::ComponentDecoration<crate::Parameters> = factory_def;
+
+            pub(crate) fn factory_def() -> #integra8_path ::decorations::ComponentDecoration<crate::Parameters> {
+                #integra8_path ::decorations::ComponentDecoration::TestFactory(
+                    #integra8_path ::decorations::TestFactoryDecoration {
+                        location: #integra8_path ::src_loc!(),
+                        factory_fn: #factory_fn_expr,
+                    }
+                )
+            }
+        }
+    };
+
+    TokenStream::from(tokens)
+}
@@ -64,6 +64,44 @@ where
     async_std::task::spawn(future)
 }
 
+/// Runs a blocking function on the runtime's blocking thread pool, so it does not hold up an async worker thread.
+/// A panic within the function is resumed on the calling task.
+#[cfg(feature = "tokio-runtime")]
+pub async fn spawn_blocking<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(output) => output,
+        Err(err) => match err.try_into_panic() {
+            Ok(panic) => std::panic::resume_unwind(panic),
+            Err(err) => panic!("{}", err),
+        },
+    }
+}
+
+/// Runs a blocking function on the runtime's blocking thread pool, so it does not hold up an async worker thread.
+/// A panic within the function is resumed on the calling task.
+#[cfg(feature = "async-std-runtime")]
+pub async fn spawn_blocking<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    async_std::task::spawn_blocking(f).await
+}
+
+#[cfg(feature = "tokio-runtime")]
+pub async fn abort<T>(handle: tokio::task::JoinHandle<T>) {
+    handle.abort()
+}
+
+#[cfg(feature = "async-std-runtime")]
+pub async fn abort<T>(handle: async_std::task::JoinHandle<T>) {
+    handle.cancel().await;
+}
+
 #[cfg(feature = "tokio-runtime")]
 pub fn sleep(duration: std::time::Duration) -> tokio::time::Sleep {
     tokio::time::sleep(duration)
@@ -50,10 +50,10 @@ impl TimingAcceptanceCriteria {
         }
     }
 
-    pub fn for_suite(_attributes: &SuiteAttributes) -> Self {
+    pub fn for_suite(attributes: &SuiteAttributes) -> Self {
         Self {
-            warning_time_limit: None,
-            time_limit: None,
+            warning_time_limit: attributes.suite_warning_time_limit,
+            time_limit: attributes.suite_time_limit,
         }
     }
 }
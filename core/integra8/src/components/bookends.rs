@@ -3,7 +3,7 @@ use std::time::Duration;
 
 use crate::components::{
     ComponentDescription, ComponentId, ComponentLocation, ComponentType, ConcurrencyMode, Delegate,
-    SuiteAttributes,
+    RetryPolicy, SkipCondition, SuiteAttributes,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -11,6 +11,14 @@ pub struct BookEndAttributes {
     /// Indicates that bookend should not be run.
     pub ignore: bool,
 
+    /// The condition under which this bookend is skipped, evaluated against the parameters before it is run.
+    /// Not passed to child processes, as the condition is evaluated by the parent process.
+    #[serde(skip)]
+    pub skip_if: Option<SkipCondition>,
+
+    /// Indicates that bookend was excluded from this run by the command line test selection.
+    pub filtered: bool,
+
     /// Describes the maximum duration a bookend can take before it is forcibly aborted
     pub time_limit: Duration,
 
@@ -18,49 +26,75 @@ pub struct BookEndAttributes {
     /// `ConcurrencyMode::Parallel` will allow this bookend for be run at the same time as other bookends within this suite
     /// `ConcurrencyMode::Sequential` will ensure that this bookend wont run at the same time as any other bookend from this suite
     pub concurrency_mode: ConcurrencyMode,
+
+    /// Describes how this bookend is re-run if it fails, or none if it is not retried.
+    pub retry: Option<RetryPolicy>,
 }
 
 impl BookEndAttributes {
     pub fn new_setup(
         parent_desc: &SuiteAttributes,
         ignore: Option<bool>,
+        skip_if: Option<SkipCondition>,
+        filtered: Option<bool>,
         time_limit: Option<Duration>,
         concurrency_mode: Option<ConcurrencyMode>,
+        retry: Option<RetryPolicy>,
     ) -> Self {
         Self {
             ignore: ignore.unwrap_or_else(|| parent_desc.ignore),
+            skip_if,
+            filtered: filtered.unwrap_or(parent_desc.filtered),
             time_limit: time_limit.map_or_else(|| parent_desc.setup_time_limit, |val| val),
 
             concurrency_mode: concurrency_mode
                 // Default Serial unless explicitly stated otherwise
                 .map_or_else(|| ConcurrencyMode::Sequential, |val| val),
+            retry,
         }
     }
 
     pub fn new_tear_down(
         parent_desc: &SuiteAttributes,
         ignore: Option<bool>,
+        skip_if: Option<SkipCondition>,
+        filtered: Option<bool>,
         time_limit: Option<Duration>,
         concurrency_mode: Option<ConcurrencyMode>,
+        retry: Option<RetryPolicy>,
     ) -> Self {
         Self {
             ignore: ignore.unwrap_or_else(|| parent_desc.ignore),
+            skip_if,
+            filtered: filtered.unwrap_or(parent_desc.filtered),
             time_limit: time_limit.map_or_else(|| parent_desc.tear_down_time_limit, |val| val),
 
             concurrency_mode: concurrency_mode
                 // Default Serial unless explicitly stated otherwise
                 .map_or_else(|| ConcurrencyMode::Sequential, |val| val),
+            retry,
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct BookEnd<TParameters> {
     pub attributes: BookEndAttributes,
     pub description: ComponentDescription,
     pub bookend_fn: Delegate<TParameters>,
 }
 
+// Implemented by hand, as derive would require `TParameters` to be `Clone`
+impl<TParameters> Clone for BookEnd<TParameters> {
+    fn clone(&self) -> Self {
+        Self {
+            attributes: self.attributes.clone(),
+            description: self.description.clone(),
+            bookend_fn: self.bookend_fn.clone(),
+        }
+    }
+}
+
 impl<TParameters> BookEnd<TParameters> {
     pub fn new_setup(
         parent_suite_description: &ComponentDescription,
@@ -68,10 +102,16 @@ impl<TParameters> BookEnd<TParameters> {
         id: ComponentId,
         name: Option<&'static str>,
         description: Option<&'static str>,
+        doc: Option<&'static str>,
+        tags: &'static [&'static str],
+        metadata: &'static [(&'static str, &'static str)],
         location: ComponentLocation,
         ignore: Option<bool>,
+        skip_if: Option<SkipCondition>,
+        filtered: Option<bool>,
         time_limit: Option<Duration>,
         concurrency_mode: Option<ConcurrencyMode>,
+        retry: Option<RetryPolicy>,
         setup_fn: Delegate<TParameters>,
     ) -> Self {
         Self {
@@ -82,13 +122,19 @@ impl<TParameters> BookEnd<TParameters> {
                 location,
                 parent_suite_description.location().clone(),
                 description,
+                parent_suite_description.tags().inherit(tags),
+                parent_suite_description.metadata().inherit(metadata),
                 ComponentType::Setup,
-            ),
+            )
+            .with_doc(doc),
             attributes: BookEndAttributes::new_setup(
                 parent_suite_attributes,
                 ignore,
+                skip_if,
+                filtered,
                 time_limit,
                 concurrency_mode,
+                retry,
             ),
             bookend_fn: setup_fn,
         }
@@ -100,10 +146,16 @@ impl<TParameters> BookEnd<TParameters> {
         id: ComponentId,
         name: Option<&'static str>,
         description: Option<&'static str>,
+        doc: Option<&'static str>,
+        tags: &'static [&'static str],
+        metadata: &'static [(&'static str, &'static str)],
         location: ComponentLocation,
         ignore: Option<bool>,
+        skip_if: Option<SkipCondition>,
+        filtered: Option<bool>,
         time_limit: Option<Duration>,
         concurrency_mode: Option<ConcurrencyMode>,
+        retry: Option<RetryPolicy>,
         setup_fn: Delegate<TParameters>,
     ) -> Self {
         Self {
@@ -114,13 +166,19 @@ impl<TParameters> BookEnd<TParameters> {
                 location,
                 parent_suite_description.location().clone(),
                 description,
+                parent_suite_description.tags().inherit(tags),
+                parent_suite_description.metadata().inherit(metadata),
                 ComponentType::TearDown,
-            ),
+            )
+            .with_doc(doc),
             attributes: BookEndAttributes::new_tear_down(
                 parent_suite_attributes,
                 ignore,
+                skip_if,
+                filtered,
                 time_limit,
                 concurrency_mode,
+                retry,
             ),
             bookend_fn: setup_fn,
         }
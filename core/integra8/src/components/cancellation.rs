@@ -0,0 +1,118 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Signals a component that it should stop, giving it the chance to release any resources it holds.
+///
+/// A component's token is cancelled once it exceeds its time limit, or the suite or run it belongs to exceeds its time limit.
+/// The component is then given a grace period to return, after which it is aborted.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationState>,
+}
+
+#[derive(Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token, waking any component awaiting `cancelled()`.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        for waker in self.inner.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns a future which completes once the token is cancelled.
+    /// Intended to be raced against the component's own work, for example with `tokio::select!`.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// A future which completes once its `CancellationToken` is cancelled.
+pub struct Cancelled {
+    token: CancellationToken,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+
+        let mut wakers = self.token.inner.wakers.lock().unwrap();
+
+        // The token may have been cancelled before the lock was taken
+        if self.token.is_cancelled() {
+            return Poll::Ready(());
+        }
+
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_complete_once_cancelled() {
+        let token = CancellationToken::new();
+        let cancelled = token.cancelled();
+
+        // Act
+        let handle = {
+            let token = token.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                token.cancel();
+            })
+        };
+        futures::executor::block_on(cancelled);
+
+        // Assert
+        handle.join().unwrap();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn should_complete_immediately_if_already_cancelled() {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        // Act
+        futures::executor::block_on(token.cancelled());
+
+        // Assert
+        assert!(token.clone().is_cancelled());
+    }
+}
@@ -1,6 +1,7 @@
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::io::{BufRead, Cursor, Read, Seek, SeekFrom, Write};
 use std::mem;
@@ -10,14 +11,43 @@ use std::sync::RwLock;
 use std::time::Duration;
 
 use crate::components::{
-    BookEnd, BookEndAttributes, Component, ComponentDescription, ComponentId, ComponentLocation,
-    ComponentPath, ComponentType, ConcurrencyMode, Delegate, Test, TestAttributes,
+    BookEnd, BookEndAttributes, CancellationToken, Component, ComponentDescription, ComponentId,
+    ComponentLocation, ComponentMetadata, ComponentPath, ComponentTags, ComponentType,
+    ConcurrencyMode, Delegate, Shard, SkipRequest, SuiteState, TagExpression, Test, TestAttributes,
 };
 
 pub struct ExecutionContext<TParameters> {
     pub parameters: Arc<TParameters>,
     pub description: ComponentDescription,
     pub artifacts: Arc<ExecutionArtifacts>,
+    pub state: SuiteState,
+
+    /// Cancelled when the component should stop, such as when it exceeds its time limit.
+    pub cancellation: CancellationToken,
+}
+
+impl<TParameters> ExecutionContext<TParameters> {
+    /// Stops the component and reports it as skipped with the given reason, rather than passed or failed.
+    /// If the component is a setup, the rest of its suite is also skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `reason` - The reason the component was skipped.
+    ///
+    pub fn skip(&self, reason: impl Into<String>) -> ! {
+        SkipRequest::unwind(reason.into())
+    }
+
+    /// Raises a non-fatal warning, which is reported as a warning rather than a pass if the component
+    /// otherwise passes. The message is included in the component's artifacts.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message describing the warning.
+    ///
+    pub fn warn(&self, message: impl Into<String>) {
+        self.artifacts.include_warning(message);
+    }
 }
 
 pub enum ExecutionArtifact {
@@ -32,12 +62,14 @@ pub struct ExecutionArtifacts {
     // Use index map to ensure items alway print in the same order
     // when outputting results
     map: RwLock<IndexMap<String, ExecutionArtifact>>,
+    warnings: RwLock<Vec<String>>,
 }
 
 impl ExecutionArtifacts {
     pub fn new() -> Self {
         Self {
             map: RwLock::new(IndexMap::new()),
+            warnings: RwLock::new(Vec::new()),
         }
     }
 
@@ -59,6 +91,24 @@ impl ExecutionArtifacts {
         }
     }
 
+    /// Includes the formatted error, followed by each error in its `source()` chain.
+    pub fn include_error(&self, name: impl Into<String>, error: &(dyn Error + 'static)) -> &Self {
+        self.include_text(name, format_error(error))
+    }
+
+    /// Includes a warning raised by the component.
+    /// Warnings are included together as a single text artifact named `warning`, one per line.
+    pub fn include_warning(&self, message: impl Into<String>) -> &Self {
+        let mut warnings = self.warnings.write().unwrap();
+        warnings.push(message.into());
+        self.include_text("warning", warnings.join("\n"))
+    }
+
+    /// Returns the warnings raised by the component since the artifacts were last drained.
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.read().unwrap().clone()
+    }
+
     pub fn include_text(&self, name: impl Into<String>, string: impl Into<String>) -> &Self {
         self.include(name, ExecutionArtifact::Text(string.into()));
         self
@@ -106,10 +156,36 @@ impl ExecutionArtifacts {
     pub fn drain(&self) -> IndexMap<String, ExecutionArtifact> {
         let mut drain_map = IndexMap::new();
         mem::swap(&mut *self.map.write().unwrap(), &mut drain_map);
+        self.warnings.write().unwrap().clear();
         drain_map
     }
 }
 
+/// Formats an error along with its `source()` chain, for example
+///
+/// ```text
+/// failed to load config
+///
+/// Caused by:
+///     0: failed to read config.toml
+///     1: No such file or directory (os error 2)
+/// ```
+pub fn format_error(error: &(dyn Error + 'static)) -> String {
+    let mut formatted = error.to_string();
+    let mut source = error.source();
+    if source.is_some() {
+        formatted.push_str("\n\nCaused by:");
+    }
+
+    let mut i = 0;
+    while let Some(cause) = source {
+        formatted.push_str(&format!("\n    {}: {}", i, cause));
+        source = cause.source();
+        i += 1;
+    }
+    formatted
+}
+
 pub trait BufferSource {
     fn read_all(&mut self) -> std::io::Result<Vec<u8>>;
 }
@@ -197,6 +273,8 @@ pub enum ExecutionStrategy {
     GreenThread,
     ChildProcess,
     CurrentThread,
+    /// Components are run in a child process of a remote worker, dispatched by this process as the coordinator.
+    Remote,
 }
 
 pub trait TestParameters {
@@ -211,6 +289,10 @@ pub trait TestParameters {
             return ExecutionStrategy::CurrentThread;
         }
 
+        if self.coordinator_address().is_some() {
+            return ExecutionStrategy::Remote;
+        }
+
         if self.use_child_processes() {
             return ExecutionStrategy::ChildProcess;
         }
@@ -228,10 +310,80 @@ pub trait TestParameters {
     fn test_time_limit_duration(&self) -> Duration;
     fn test_warning_time_limit_duration(&self) -> Duration;
 
+    /// The maximum duration of the whole run, or none if the run has no limit.
+    fn run_time_limit_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    /// The duration a component is given to stop once it has been cancelled, before it is aborted.
+    fn cancellation_grace_period_duration(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    /// The number of failed components after which the run is aborted, or none if the run is never aborted.
+    fn max_failures(&self) -> Option<usize> {
+        None
+    }
+
+    /// When true, only the tests which failed or timed out the last time they were run are selected.
+    fn rerun_failed(&self) -> bool {
+        false
+    }
+
+    /// The number of times the schedule is run, or none if it is only run once (unless repeated until failure).
+    fn repeat(&self) -> Option<usize> {
+        None
+    }
+
+    /// When true, the schedule is run until a run fails, up to the `repeat` count if one is given.
+    fn repeat_until_failure(&self) -> bool {
+        false
+    }
+
+    /// The seed used to randomize the order of sequential tests and child suites, or none if they run in the order they appear.
+    fn shuffle_seed(&self) -> Option<u64> {
+        None
+    }
+
+    /// The shard of the run to select components for, or none if all components are selected.
+    fn shard(&self) -> Option<Shard> {
+        None
+    }
+
+    /// The address to listen for workers on, or none if components are not run by remote workers.
+    fn coordinator_address(&self) -> Option<&'_ str> {
+        None
+    }
+
+    /// The address of the coordinator to run components for, or none if this process is not a worker.
+    fn worker_address(&self) -> Option<&'_ str> {
+        None
+    }
+
+    /// When true, the components selected by the command line are listed instead of being run.
+    fn list_components(&self) -> bool {
+        false
+    }
+
     fn max_concurrency(&self) -> usize;
     fn root_namespace(&self) -> &'static str;
     fn use_child_processes(&self) -> bool;
 
+    /// The paths or globs of the components to run, or empty if all components are run.
+    fn filter_patterns(&self) -> &'_ [String] {
+        &[]
+    }
+
+    /// The paths or globs of the components to skip.
+    fn skip_patterns(&self) -> &'_ [String] {
+        &[]
+    }
+
+    /// The tag expression tests must satisfy to run, or none if tests are not selected by their tags.
+    fn tag_expression(&self) -> Option<&'_ TagExpression> {
+        None
+    }
+
     fn console_output_style(&self) -> &'_ str;
     fn console_output_detail_level(&self) -> &'_ str;
     fn console_output_encoding(&self) -> &'_ str;
@@ -244,15 +396,85 @@ pub struct ChildProcessComponentMetaArgs {
     pub parent_location: ComponentLocation,
     pub id: ComponentId,
     pub parent_id: ComponentId,
+    pub tags: ComponentTags,
+    pub metadata: ComponentMetadata,
+    /// The serialized suite state which can be read by the component.
+    pub suite_state: BTreeMap<String, String>,
+    /// The file to write any suite state inserted by the component to, so it can be read by the parent process.
+    pub suite_state_output: PathBuf,
+    /// The file to write the reason to, if the component skips itself, so it can be read by the parent process.
+    pub skip_reason_output: PathBuf,
+    /// The file to write how the component was stopped to, if it is cancelled, so it can be read by the parent process.
+    pub cancellation_output: PathBuf,
 }
 
 impl ChildProcessComponentMetaArgs {
-    pub fn from_description(description: ComponentDescription) -> Self {
-        Self {
+    /// Creates the meta args for running a component in a child process
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the component.
+    ///
+    /// * `suite_state` - The suite state which can be read by the component.
+    ///
+    /// * `suite_state_output` - The file to write any suite state inserted by the component to.
+    ///
+    /// * `skip_reason_output` - The file to write the reason to, if the component skips itself.
+    ///
+    /// * `cancellation_output` - The file to write how the component was stopped to, if it is cancelled.
+    ///
+    /// Returns an error if the suite state can not be serialized.
+    pub fn from_description(
+        description: ComponentDescription,
+        suite_state: &SuiteState,
+        suite_state_output: PathBuf,
+        skip_reason_output: PathBuf,
+        cancellation_output: PathBuf,
+    ) -> Result<Self, String> {
+        Ok(Self {
             path: description.location().path.clone(),
             parent_location: description.parent_location().clone(),
             id: description.id().clone(),
             parent_id: description.parent_id().clone(),
+            tags: description.tags().clone(),
+            metadata: description.metadata().clone(),
+            suite_state: suite_state.to_serialized()?,
+            suite_state_output,
+            skip_reason_output,
+            cancellation_output,
+        })
+    }
+}
+
+/// Describes a before or after each hook, which is run in the same child process as the test it wraps
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChildProcessHookArgs {
+    pub description: ComponentDescription,
+    pub attributes: BookEndAttributes,
+}
+
+impl ChildProcessHookArgs {
+    pub fn from_hook<TParameters>(hook: BookEnd<TParameters>) -> Self {
+        Self {
+            description: hook.description,
+            attributes: hook.attributes,
+        }
+    }
+
+    fn into_hook<TParameters>(
+        self,
+        hook_fns: &[(ComponentPath, Delegate<TParameters>)],
+    ) -> BookEnd<TParameters> {
+        let hook_fn = hook_fns
+            .iter()
+            .find(|(path, _)| path == self.description.path())
+            .map(|(_, hook_fn)| hook_fn.clone())
+            .unwrap_or_else(|| panic!("{} was not found", self.description.path()));
+
+        BookEnd {
+            description: self.description,
+            attributes: self.attributes,
+            bookend_fn: hook_fn,
         }
     }
 }
@@ -262,6 +484,8 @@ pub enum ChildProcessComponentArgs {
     Test {
         meta: ChildProcessComponentMetaArgs,
         attributes: TestAttributes,
+        before_each: Vec<ChildProcessHookArgs>,
+        after_each: Vec<ChildProcessHookArgs>,
     },
     Setup {
         meta: ChildProcessComponentMetaArgs,
@@ -287,20 +511,74 @@ impl ChildProcessComponentArgs {
         }
     }
 
+    /// Returns true if the component at the given path is run by the child process,
+    /// either as the target component or as a hook wrapped around it.
+    pub fn runs_component(&self, path: &ComponentPath) -> bool {
+        if &self.meta().path == path {
+            return true;
+        }
+
+        match self {
+            Self::Test {
+                before_each,
+                after_each,
+                ..
+            } => before_each
+                .iter()
+                .chain(after_each.iter())
+                .any(|hook| hook.description.path() == path),
+            _ => false,
+        }
+    }
+
     pub fn to_string(&self) -> Result<String, Box<dyn Error>> {
         let as_string = serde_json::to_string(&self)?;
         Ok(as_string)
     }
 
+    /// Replaces the files the child process writes its suite state, skip reason and cancellation to.
+    /// Used when the component is run by a remote worker, where the original files may not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `suite_state_output` - The file to write any suite state inserted by the component to.
+    ///
+    /// * `skip_reason_output` - The file to write the reason to, if the component skips itself.
+    ///
+    /// * `cancellation_output` - The file to write how the component was stopped to, if it is cancelled.
+    ///
+    pub fn with_outputs(
+        mut self,
+        suite_state_output: PathBuf,
+        skip_reason_output: PathBuf,
+        cancellation_output: PathBuf,
+    ) -> Self {
+        let meta = match &mut self {
+            Self::Test { meta, .. } => meta,
+            Self::Setup { meta, .. } => meta,
+            Self::TearDown { meta, .. } => meta,
+        };
+        meta.suite_state_output = suite_state_output;
+        meta.skip_reason_output = skip_reason_output;
+        meta.cancellation_output = cancellation_output;
+        self
+    }
+
     pub fn into_component<TParameters>(
         self,
         name: Option<&'static str>,
         description: Option<&'static str>,
         location: ComponentLocation,
         component_fn: Delegate<TParameters>,
+        hook_fns: &[(ComponentPath, Delegate<TParameters>)],
     ) -> Component<TParameters> {
         match self {
-            Self::Test { meta, attributes } => Component::Test(Test {
+            Self::Test {
+                meta,
+                attributes,
+                before_each,
+                after_each,
+            } => Component::Test(Test {
                 description: ComponentDescription::new(
                     name,
                     meta.id,
@@ -308,10 +586,20 @@ impl ChildProcessComponentArgs {
                     location,
                     meta.parent_location,
                     description,
+                    meta.tags,
+                    meta.metadata,
                     ComponentType::Test,
                 ),
                 attributes: attributes,
                 test_fn: component_fn,
+                before_each: before_each
+                    .into_iter()
+                    .map(|hook| hook.into_hook(hook_fns))
+                    .collect(),
+                after_each: after_each
+                    .into_iter()
+                    .map(|hook| hook.into_hook(hook_fns))
+                    .collect(),
             }),
             Self::Setup { meta, attributes } => Component::Setup(BookEnd {
                 description: ComponentDescription::new(
@@ -321,6 +609,8 @@ impl ChildProcessComponentArgs {
                     location,
                     meta.parent_location,
                     description,
+                    meta.tags,
+                    meta.metadata,
                     ComponentType::Setup,
                 ),
                 attributes: attributes,
@@ -334,6 +624,8 @@ impl ChildProcessComponentArgs {
                     location,
                     meta.parent_location,
                     description,
+                    meta.tags,
+                    meta.metadata,
                     ComponentType::TearDown,
                 ),
                 attributes: attributes,
@@ -342,3 +634,95 @@ impl ChildProcessComponentArgs {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Only implements the methods of the parameters which existed before the test selection
+    // and run control parameters, as a hand written implementation would
+    struct MinimalParameters;
+
+    impl TestParameters for MinimalParameters {
+        fn test_concurrency(&self) -> ConcurrencyMode {
+            ConcurrencyMode::Parallel
+        }
+
+        fn suite_concurrency(&self) -> ConcurrencyMode {
+            ConcurrencyMode::Sequential
+        }
+
+        fn child_process_target(&self) -> Option<&'_ ChildProcessComponentArgs> {
+            None
+        }
+
+        fn setup_time_limit_duration(&self) -> Duration {
+            Duration::from_secs(20)
+        }
+
+        fn tear_down_time_limit_duration(&self) -> Duration {
+            Duration::from_secs(20)
+        }
+
+        fn test_time_limit_duration(&self) -> Duration {
+            Duration::from_secs(30)
+        }
+
+        fn test_warning_time_limit_duration(&self) -> Duration {
+            Duration::from_secs(10)
+        }
+
+        fn max_concurrency(&self) -> usize {
+            1
+        }
+
+        fn root_namespace(&self) -> &'static str {
+            "minimal"
+        }
+
+        fn use_child_processes(&self) -> bool {
+            false
+        }
+
+        fn console_output_style(&self) -> &'_ str {
+            "Text"
+        }
+
+        fn console_output_detail_level(&self) -> &'_ str {
+            "Error"
+        }
+
+        fn console_output_encoding(&self) -> &'_ str {
+            "Utf8"
+        }
+
+        fn console_output_ansi_mode(&self) -> &'_ str {
+            "Auto"
+        }
+    }
+
+    #[test]
+    fn should_default_optional_parameters_to_off() {
+        // Act
+        let parameters = MinimalParameters;
+
+        // Assert
+        assert!(parameters.filter_patterns().is_empty());
+        assert!(parameters.skip_patterns().is_empty());
+        assert!(parameters.tag_expression().is_none());
+        assert_eq!(parameters.run_time_limit_duration(), None);
+        assert_eq!(parameters.max_failures(), None);
+        assert!(!parameters.rerun_failed());
+        assert_eq!(parameters.repeat(), None);
+        assert!(!parameters.repeat_until_failure());
+        assert_eq!(parameters.shuffle_seed(), None);
+        assert!(parameters.shard().is_none());
+        assert_eq!(parameters.coordinator_address(), None);
+        assert_eq!(parameters.worker_address(), None);
+        assert!(!parameters.list_components());
+        assert!(matches!(
+            parameters.execution_strategy(),
+            ExecutionStrategy::GreenThread
+        ));
+    }
+}
@@ -1,18 +1,125 @@
 use futures::executor::block_on;
+use std::error::Error;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::Arc;
 
 use crate::components::ExecutionContext;
 
-#[derive(Clone)]
+/// The error returned by a component which did not succeed.
+pub type DelegateError = Box<dyn Error + Send + Sync + 'static>;
+
+/// The outcome of running a component. An `Err` results in the component being rejected.
+pub type DelegateResult = Result<(), DelegateError>;
+
+pub type DelegateFuture = Pin<Box<dyn Future<Output = DelegateResult> + Send + 'static>>;
+
+pub type SyncClosure<TParameters> =
+    Arc<dyn Fn(ExecutionContext<TParameters>) -> DelegateResult + Send + Sync>;
+
+pub type AsyncClosure<TParameters> =
+    Arc<dyn Fn(ExecutionContext<TParameters>) -> DelegateFuture + Send + Sync>;
+
+/// Converts the value returned by a test, setup or tear down function into a `DelegateResult`.
+///
+/// Implemented for `()`, and for `Result<(), E>` where `E` can be converted into a boxed error
+/// (which includes all `std::error::Error` types, `String`, `&str` and `anyhow::Error`).
+/// Functions decorated as components are converted with `into_delegate_result!` instead,
+/// which also accepts errors only implementing `Display` or `Debug`.
+pub trait IntoDelegateResult {
+    fn into_delegate_result(self) -> DelegateResult;
+}
+
+impl IntoDelegateResult for () {
+    fn into_delegate_result(self) -> DelegateResult {
+        Ok(())
+    }
+}
+
+impl<E: Into<DelegateError>> IntoDelegateResult for Result<(), E> {
+    fn into_delegate_result(self) -> DelegateResult {
+        self.map_err(Into::into)
+    }
+}
+
+/// Chooses how `into_delegate_result!` converts the value returned by a component function,
+/// by the traits its error implements (autoref specialization).
+/// Errors which can be boxed keep their `source()` chain, otherwise they are formatted with `Display`, or failing that `Debug`.
+#[doc(hidden)]
+pub mod kind {
+    use super::*;
+
+    pub struct IntoDelegateResultKind;
+    pub struct DisplayErrorKind;
+    pub struct DebugErrorKind;
+
+    pub trait IntoDelegateResultTag {
+        fn delegate_result_kind(&self) -> IntoDelegateResultKind {
+            IntoDelegateResultKind
+        }
+    }
+
+    impl<R: IntoDelegateResult> IntoDelegateResultTag for &&R {}
+
+    pub trait DisplayErrorTag {
+        fn delegate_result_kind(&self) -> DisplayErrorKind {
+            DisplayErrorKind
+        }
+    }
+
+    impl<E: fmt::Display> DisplayErrorTag for &Result<(), E> {}
+
+    pub trait DebugErrorTag {
+        fn delegate_result_kind(&self) -> DebugErrorKind {
+            DebugErrorKind
+        }
+    }
+
+    impl<E: fmt::Debug> DebugErrorTag for Result<(), E> {}
+
+    impl IntoDelegateResultKind {
+        pub fn into_result<R: IntoDelegateResult>(self, returned: R) -> DelegateResult {
+            returned.into_delegate_result()
+        }
+    }
+
+    impl DisplayErrorKind {
+        pub fn into_result<E: fmt::Display>(self, returned: Result<(), E>) -> DelegateResult {
+            returned.map_err(|error| error.to_string().into())
+        }
+    }
+
+    impl DebugErrorKind {
+        pub fn into_result<E: fmt::Debug>(self, returned: Result<(), E>) -> DelegateResult {
+            returned.map_err(|error| format!("{:?}", error).into())
+        }
+    }
+}
+
 pub enum Delegate<TParameters> {
-    SyncWithoutContext(fn()),
-    SyncWithContext(fn(ExecutionContext<TParameters>)),
-    AsyncWithoutContext(fn() -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>),
-    AsyncWithContext(
-        fn(ExecutionContext<TParameters>) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
-    ),
+    SyncWithoutContext(fn() -> DelegateResult),
+    SyncWithContext(fn(ExecutionContext<TParameters>) -> DelegateResult),
+    AsyncWithoutContext(fn() -> DelegateFuture),
+    AsyncWithContext(fn(ExecutionContext<TParameters>) -> DelegateFuture),
+    /// A closure, which may capture state determined at runtime. Used by dynamic tests
+    SyncClosure(SyncClosure<TParameters>),
+    /// An async closure, which may capture state determined at runtime. Used by dynamic tests
+    AsyncClosure(AsyncClosure<TParameters>),
+}
+
+// Implemented by hand, as derive would require `TParameters` to be `Clone`
+impl<TParameters> Clone for Delegate<TParameters> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::SyncWithoutContext(del) => Self::SyncWithoutContext(*del),
+            Self::SyncWithContext(del) => Self::SyncWithContext(*del),
+            Self::AsyncWithoutContext(del) => Self::AsyncWithoutContext(*del),
+            Self::AsyncWithContext(del) => Self::AsyncWithContext(*del),
+            Self::SyncClosure(del) => Self::SyncClosure(del.clone()),
+            Self::AsyncClosure(del) => Self::AsyncClosure(del.clone()),
+        }
+    }
 }
 
 impl<TParameters> fmt::Debug for Delegate<TParameters> {
@@ -28,48 +135,91 @@ impl<TParameters> fmt::Debug for Delegate<TParameters> {
                 .field("del", del)
                 .finish(),
             Self::AsyncWithContext(_) => f.debug_struct("AsyncWithContext").finish(),
+            Self::SyncClosure(_) => f.debug_struct("SyncClosure").finish(),
+            Self::AsyncClosure(_) => f.debug_struct("AsyncClosure").finish(),
         }
     }
 }
 
 impl<TParameters> Delegate<TParameters> {
-    pub fn sync_without_context(del: fn()) -> Self {
+    pub fn sync_without_context(del: fn() -> DelegateResult) -> Self {
         Self::SyncWithoutContext(del)
     }
 
-    pub fn sync_with_context(del: fn(ExecutionContext<TParameters>)) -> Self {
+    pub fn sync_with_context(del: fn(ExecutionContext<TParameters>) -> DelegateResult) -> Self {
         Self::SyncWithContext(del)
     }
 
-    pub fn async_without_context(
-        del: fn() -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
-    ) -> Self {
+    pub fn async_without_context(del: fn() -> DelegateFuture) -> Self {
         Self::AsyncWithoutContext(del)
     }
 
-    pub fn async_with_context(
-        del: fn(
-            ExecutionContext<TParameters>,
-        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
-    ) -> Self {
+    pub fn async_with_context(del: fn(ExecutionContext<TParameters>) -> DelegateFuture) -> Self {
         Self::AsyncWithContext(del)
     }
 
+    /// Creates a delegate from a closure, which may capture state determined at runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `del` - The closure to invoke. It may return `()`, or a `Result<(), E>`.
+    ///
+    pub fn sync_closure<F, R>(del: F) -> Self
+    where
+        TParameters: 'static,
+        F: Fn(ExecutionContext<TParameters>) -> R + Send + Sync + 'static,
+        R: IntoDelegateResult,
+    {
+        Self::SyncClosure(Arc::new(move |ctx| del(ctx).into_delegate_result()))
+    }
+
+    /// Creates a delegate from a closure returning a future, which may capture state determined at runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `del` - The closure to invoke. Its future may output `()`, or a `Result<(), E>`.
+    ///
+    pub fn async_closure<F, Fut, R>(del: F) -> Self
+    where
+        TParameters: 'static,
+        F: Fn(ExecutionContext<TParameters>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        R: IntoDelegateResult,
+    {
+        Self::AsyncClosure(Arc::new(move |ctx| {
+            let fut = del(ctx);
+            Box::pin(async move { fut.await.into_delegate_result() })
+        }))
+    }
+
     pub fn requires_parameters(&self) -> bool {
         match self {
             Self::SyncWithoutContext(_) | Self::AsyncWithoutContext(_) => false,
-            Self::SyncWithContext(_) | Self::AsyncWithContext(_) => true,
+            Self::SyncWithContext(_)
+            | Self::AsyncWithContext(_)
+            | Self::SyncClosure(_)
+            | Self::AsyncClosure(_) => true,
         }
     }
 
-    pub async fn run_async_without_parameters(&self) {
+    /// Returns true if the delegate blocks the thread it is run on, rather than returning a future.
+    pub fn is_sync(&self) -> bool {
+        match self {
+            Self::SyncWithoutContext(_) | Self::SyncWithContext(_) | Self::SyncClosure(_) => true,
+            Self::AsyncWithoutContext(_) | Self::AsyncWithContext(_) | Self::AsyncClosure(_) => {
+                false
+            }
+        }
+    }
+
+    pub async fn run_async_without_parameters(&self) -> DelegateResult {
         match self {
             Self::SyncWithoutContext(del) => (del)(),
             Self::AsyncWithoutContext(del) => (del)().await,
             _ => panic!("Parameter are required to invoke the delegate"),
         }
     }
-    pub fn run_sync_without_parameters(&self) {
+    pub fn run_sync_without_parameters(&self) -> DelegateResult {
         match self {
             Self::SyncWithoutContext(del) => (del)(),
             Self::AsyncWithoutContext(del) => block_on((del)()),
@@ -77,21 +227,135 @@ impl<TParameters> Delegate<TParameters> {
         }
     }
 
-    pub fn run_sync(&self, ctx: ExecutionContext<TParameters>) {
+    pub fn run_sync(&self, ctx: ExecutionContext<TParameters>) -> DelegateResult {
         match self {
             Self::SyncWithoutContext(del) => (del)(),
             Self::SyncWithContext(del) => (del)(ctx),
             Self::AsyncWithoutContext(del) => block_on((del)()),
             Self::AsyncWithContext(del) => block_on((del)(ctx)),
+            Self::SyncClosure(del) => (del)(ctx),
+            Self::AsyncClosure(del) => block_on((del)(ctx)),
         }
     }
 
-    pub async fn run_async(&self, ctx: ExecutionContext<TParameters>) {
+    pub async fn run_async(&self, ctx: ExecutionContext<TParameters>) -> DelegateResult {
         match self {
             Self::SyncWithoutContext(del) => (del)(),
             Self::SyncWithContext(del) => (del)(ctx),
             Self::AsyncWithoutContext(del) => (del)().await,
             Self::AsyncWithContext(del) => (del)(ctx).await,
+            Self::SyncClosure(del) => (del)(ctx),
+            Self::AsyncClosure(del) => (del)(ctx).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::context::format_error;
+
+    #[derive(Debug)]
+    struct OuterError(std::io::Error);
+
+    impl fmt::Display for OuterError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "failed to load config")
+        }
+    }
+
+    impl Error for OuterError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    /// An error which only implements `Debug`
+    #[derive(Debug)]
+    enum DebugOnlyError {
+        Unavailable,
+    }
+
+    /// An error which only implements `Display`
+    struct DisplayOnlyError;
+
+    impl fmt::Display for DisplayOnlyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "service unavailable")
         }
     }
+
+    #[test]
+    fn should_accept_unit_and_ok() {
+        assert!(().into_delegate_result().is_ok());
+        assert!(Ok::<(), String>(()).into_delegate_result().is_ok());
+    }
+
+    #[test]
+    fn should_reject_err() {
+        let delegate = Delegate::<()>::sync_without_context(|| Err("boom".into()));
+
+        // Act
+        let result = delegate.run_sync_without_parameters();
+
+        // Assert
+        assert_eq!(result.unwrap_err().to_string(), "boom");
+    }
+
+    #[test]
+    fn should_format_error_with_source_chain() {
+        let error = Err::<(), _>(OuterError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "config.toml not found",
+        )))
+        .into_delegate_result()
+        .unwrap_err();
+
+        // Assert
+        assert_eq!(
+            format_error(error.as_ref()),
+            "failed to load config\n\nCaused by:\n    0: config.toml not found"
+        );
+    }
+
+    #[test]
+    fn should_accept_unit_and_ok_returned_by_component_function() {
+        assert!(crate::into_delegate_result!(()).is_ok());
+        assert!(crate::into_delegate_result!(Ok::<(), DebugOnlyError>(())).is_ok());
+    }
+
+    #[test]
+    fn should_format_error_which_only_implements_debug() {
+        // Act
+        let error =
+            crate::into_delegate_result!(Err::<(), _>(DebugOnlyError::Unavailable)).unwrap_err();
+
+        // Assert
+        assert_eq!(error.to_string(), "Unavailable");
+    }
+
+    #[test]
+    fn should_format_error_which_only_implements_display() {
+        // Act
+        let error = crate::into_delegate_result!(Err::<(), _>(DisplayOnlyError)).unwrap_err();
+
+        // Assert
+        assert_eq!(error.to_string(), "service unavailable");
+    }
+
+    #[test]
+    fn should_keep_source_chain_of_error_returned_by_component_function() {
+        // Act
+        let error = crate::into_delegate_result!(Err::<(), _>(OuterError(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "config.toml not found",
+        ))))
+        .unwrap_err();
+
+        // Assert
+        assert_eq!(
+            format_error(error.as_ref()),
+            "failed to load config\n\nCaused by:\n    0: config.toml not found"
+        );
+    }
 }
@@ -0,0 +1,492 @@
+use crate::components::{ComponentPath, ConcurrencyMode, Suite, TestParameters};
+
+/// Where a test is placed within one of the suites it belongs to.
+#[derive(Clone, Debug, PartialEq)]
+enum Placement {
+    /// The test belongs to the suite itself.
+    Test,
+
+    /// The test belongs to the child suite at the given index, or one of its nested suites.
+    Suite {
+        index: usize,
+        concurrency_mode: ConcurrencyMode,
+    },
+}
+
+struct DependentTest<'a> {
+    path: &'a ComponentPath,
+    placement: Vec<Placement>,
+    depends_on: &'a [ComponentPath],
+}
+
+impl<TParameters: TestParameters> Suite<TParameters> {
+    /// Resolves the paths declared by `#[depends_on(...)]` within this suite and its nested suites,
+    /// to the full paths of the tests they refer to.
+    ///
+    /// Paths starting with `crate::` are resolved from the root of the test application,
+    /// and paths starting with `super::` from the parent of the test's suite.
+    /// Other paths are relative to the test's suite, or failing that, are the full path of a test.
+    /// Paths which do not refer to any test are resolved relative to the test's suite,
+    /// and are reported by `validate_dependencies`.
+    pub fn resolve_dependencies(&mut self) {
+        let mut tests = Vec::new();
+        collect_test_paths(self, &mut tests);
+        resolve_suite_dependencies(self, &tests);
+    }
+
+    /// Validates the dependencies between the tests of this suite and its nested suites,
+    /// expected to be called once their paths are resolved.
+    ///
+    /// Returns an error describing the first dependency which can not be met, being one which
+    /// is not a test, one which forms a cycle, or one which the schedule does not run before the test that depends on it.
+    /// Within a suite, tests are run before the suites nested within it, and parallel suites are run before serial suites.
+    pub fn validate_dependencies(&self) -> Result<(), String> {
+        let mut tests = Vec::new();
+        collect_dependent_tests(self, Vec::new(), &mut tests);
+
+        let mut dependencies = Vec::new();
+        for test in &tests {
+            let mut test_dependencies = Vec::new();
+            for dependency in test.depends_on {
+                let matches = tests
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, other)| other.path.is_within(dependency))
+                    .map(|(idx, _)| idx)
+                    .collect::<Vec<usize>>();
+
+                if matches.is_empty() {
+                    return Err(format!(
+                        "{} depends on {}, which is not a test",
+                        test.path, dependency
+                    ));
+                }
+                test_dependencies.extend(matches);
+            }
+            dependencies.push(test_dependencies);
+        }
+
+        if let Some(cycle) = find_cycle(&dependencies) {
+            return Err(format!(
+                "The dependencies of {} form a cycle: {}",
+                tests[cycle[0]].path,
+                cycle
+                    .iter()
+                    .map(|idx| tests[*idx].path.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ));
+        }
+
+        for (test, test_dependencies) in tests.iter().zip(&dependencies) {
+            for dependency in test_dependencies {
+                validate_order(&tests[*dependency], test)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn collect_test_paths<TParameters>(suite: &Suite<TParameters>, paths: &mut Vec<ComponentPath>) {
+    paths.extend(
+        suite
+            .tests
+            .iter()
+            .map(|test| test.description.path().clone()),
+    );
+    for child in &suite.suites {
+        collect_test_paths(child, paths);
+    }
+}
+
+fn resolve_suite_dependencies<TParameters>(
+    suite: &mut Suite<TParameters>,
+    tests: &[ComponentPath],
+) {
+    let suite_path = suite.description.path().clone();
+    for test in suite.tests.iter_mut() {
+        for dependency in test.attributes.depends_on.iter_mut() {
+            *dependency = resolve_path(dependency.as_str(), &suite_path, tests);
+        }
+    }
+
+    for child in suite.suites.iter_mut() {
+        resolve_suite_dependencies(child, tests);
+    }
+}
+
+/// Returns the full path of the declared dependency of a test.
+///
+/// # Arguments
+///
+/// * `declared` - The path as declared by `#[depends_on(...)]`.
+///
+/// * `suite_path` - The path of the suite the test belongs to.
+///
+/// * `tests` - The paths of every test in the test application.
+///
+fn resolve_path(
+    declared: &str,
+    suite_path: &ComponentPath,
+    tests: &[ComponentPath],
+) -> ComponentPath {
+    let mut segments = suite_path.as_str().split("::").collect::<Vec<&str>>();
+    let mut remainder = declared;
+
+    if let Some(path) = declared.strip_prefix("crate::") {
+        segments.truncate(1);
+        remainder = path;
+    }
+
+    while let Some(path) = remainder.strip_prefix("super::") {
+        if segments.len() > 1 {
+            segments.pop();
+        }
+        remainder = path;
+    }
+
+    segments.push(remainder);
+    let relative = ComponentPath::from_string(segments.join("::"));
+
+    let is_explicit = remainder.len() != declared.len();
+    let is_test = |path: &ComponentPath| tests.iter().any(|test| test.is_within(path));
+    let absolute = ComponentPath::from_string(declared.to_string());
+
+    match is_explicit || is_test(&relative) || !is_test(&absolute) {
+        true => relative,
+        false => absolute,
+    }
+}
+
+fn collect_dependent_tests<'a, TParameters>(
+    suite: &'a Suite<TParameters>,
+    placement: Vec<Placement>,
+    tests: &mut Vec<DependentTest<'a>>,
+) {
+    for test in &suite.tests {
+        let mut test_placement = placement.clone();
+        test_placement.push(Placement::Test);
+        tests.push(DependentTest {
+            path: test.description.path(),
+            placement: test_placement,
+            depends_on: &test.attributes.depends_on,
+        });
+    }
+
+    for (index, child) in suite.suites.iter().enumerate() {
+        let mut child_placement = placement.clone();
+        child_placement.push(Placement::Suite {
+            index,
+            concurrency_mode: child.attributes.suite_concurrency_mode.clone(),
+        });
+        collect_dependent_tests(child, child_placement, tests);
+    }
+}
+
+/// Returns an error if the schedule does not run the dependency before the test which depends on it.
+/// Tests of the same suite are ordered by their dependencies, so are always valid.
+fn validate_order(dependency: &DependentTest, test: &DependentTest) -> Result<(), String> {
+    // Find the suite both tests belong to, and where each of them are placed within it
+    let placements = dependency
+        .placement
+        .iter()
+        .zip(&test.placement)
+        .find(|(a, b)| a != b || **a == Placement::Test);
+
+    match placements {
+        None | Some((Placement::Test, _)) => Ok(()),
+        Some((Placement::Suite { .. }, Placement::Test)) => Err(format!(
+            "{} depends on {}, which is not run before it, as tests are run before the suites nested alongside them",
+            test.path, dependency.path
+        )),
+        Some((
+            Placement::Suite {
+                index: dependency_index,
+                concurrency_mode: dependency_mode,
+            },
+            Placement::Suite {
+                index: test_index,
+                concurrency_mode: test_mode,
+            },
+        )) => match (dependency_mode, test_mode) {
+            (ConcurrencyMode::Parallel, ConcurrencyMode::Sequential) => Ok(()),
+            (ConcurrencyMode::Sequential, ConcurrencyMode::Sequential)
+                if dependency_index < test_index =>
+            {
+                Ok(())
+            }
+            (ConcurrencyMode::Parallel, ConcurrencyMode::Parallel) => Err(format!(
+                "{} depends on {}, which may run at the same time, as both belong to parallel suites",
+                test.path, dependency.path
+            )),
+            _ => Err(format!(
+                "{} depends on {}, which is not run before it, as its suite is run after the suite of the test",
+                test.path, dependency.path
+            )),
+        },
+    }
+}
+
+/// Returns the first cycle found within the given graph, starting and ending with the same node.
+///
+/// # Arguments
+///
+/// * `dependencies` - The indexes of the nodes each node depends on.
+///
+fn find_cycle(dependencies: &[Vec<usize>]) -> Option<Vec<usize>> {
+    fn visit(
+        idx: usize,
+        dependencies: &[Vec<usize>],
+        visited: &mut Vec<bool>,
+        stack: &mut Vec<usize>,
+    ) -> Option<Vec<usize>> {
+        if let Some(start) = stack.iter().position(|x| *x == idx) {
+            let mut cycle = stack[start..].to_vec();
+            cycle.push(idx);
+            return Some(cycle);
+        }
+
+        if visited[idx] {
+            return None;
+        }
+
+        visited[idx] = true;
+        stack.push(idx);
+        for dependency in &dependencies[idx] {
+            if let Some(cycle) = visit(*dependency, dependencies, visited, stack) {
+                return Some(cycle);
+            }
+        }
+        stack.pop();
+        None
+    }
+
+    let mut visited = vec![false; dependencies.len()];
+    (0..dependencies.len()).find_map(|idx| visit(idx, dependencies, &mut visited, &mut Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decorations::test_rigging::Parameters;
+    use crate::decorations::{ComponentDecoration, ComponentGroup};
+
+    mod mock_app {
+        pub use integra8_decorations_impl::*;
+
+        #[suite]
+        #[integra8(crate = crate)]
+        pub mod orders {
+            pub use integra8_decorations_impl::*;
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            pub fn create_order() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[depends_on(create_order)]
+            pub fn cancel_order() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[depends_on(super::refunds::refund_order)]
+            pub fn close_order() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[depends_on(delete_order)]
+            pub fn unknown_order() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[depends_on(cycle_b)]
+            pub fn cycle_a() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[depends_on(cycle_a)]
+            pub fn cycle_b() {}
+        }
+
+        #[suite]
+        #[integra8(crate = crate)]
+        pub mod refunds {
+            pub use integra8_decorations_impl::*;
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[depends_on(super::orders::create_order)]
+            pub fn refund_order() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[depends_on(crate::components::dependencies::tests::mock_app::orders::cancel_order)]
+            pub fn refund_cancelled_order() {}
+        }
+
+        #[suite]
+        #[integra8(crate = crate)]
+        #[parallel]
+        pub mod parallel_a {
+            pub use integra8_decorations_impl::*;
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            pub fn test_a() {}
+        }
+
+        #[suite]
+        #[integra8(crate = crate)]
+        #[parallel]
+        pub mod parallel_b {
+            pub use integra8_decorations_impl::*;
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[depends_on(super::parallel_a::test_a)]
+            pub fn test_b() {}
+        }
+    }
+
+    fn root_suite(decorations: Vec<ComponentDecoration<Parameters>>) -> Suite<Parameters> {
+        ComponentGroup::into_root_component(decorations, &Parameters::default())
+    }
+
+    #[test]
+    fn should_resolve_dependencies_relative_to_suite() {
+        // Act
+        let root = root_suite(vec![
+            mock_app::orders::__suite_def(),
+            mock_app::orders::create_order::test_def(),
+            mock_app::orders::cancel_order::test_def(),
+            mock_app::refunds::__suite_def(),
+            mock_app::refunds::refund_order::test_def(),
+            mock_app::refunds::refund_cancelled_order::test_def(),
+        ]);
+
+        // Assert
+        assert_eq!(
+            root.suites[0].tests[1].attributes.depends_on,
+            vec![ComponentPath::from(
+                "integra8::components::dependencies::tests::mock_app::orders::create_order"
+            )]
+        );
+        assert_eq!(
+            root.suites[1].tests[0].attributes.depends_on,
+            vec![ComponentPath::from(
+                "integra8::components::dependencies::tests::mock_app::orders::create_order"
+            )]
+        );
+        assert_eq!(
+            root.suites[1].tests[1].attributes.depends_on,
+            vec![ComponentPath::from(
+                "integra8::components::dependencies::tests::mock_app::orders::cancel_order"
+            )]
+        );
+        assert_eq!(root.validate_dependencies(), Ok(()));
+    }
+
+    #[test]
+    fn should_reject_dependency_on_unknown_test() {
+        // Act
+        let root = root_suite(vec![
+            mock_app::orders::__suite_def(),
+            mock_app::orders::unknown_order::test_def(),
+        ]);
+
+        // Assert
+        assert_eq!(
+            root.validate_dependencies(),
+            Err("integra8::components::dependencies::tests::mock_app::orders::unknown_order depends on \
+                integra8::components::dependencies::tests::mock_app::orders::delete_order, which is not a test"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn should_reject_dependencies_which_form_a_cycle() {
+        // Act
+        let root = root_suite(vec![
+            mock_app::orders::__suite_def(),
+            mock_app::orders::cycle_a::test_def(),
+            mock_app::orders::cycle_b::test_def(),
+        ]);
+
+        // Assert
+        assert_eq!(
+            root.validate_dependencies(),
+            Err("The dependencies of integra8::components::dependencies::tests::mock_app::orders::cycle_a form a cycle: \
+                integra8::components::dependencies::tests::mock_app::orders::cycle_a -> \
+                integra8::components::dependencies::tests::mock_app::orders::cycle_b -> \
+                integra8::components::dependencies::tests::mock_app::orders::cycle_a"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn should_reject_dependency_on_suite_run_after() {
+        // Act
+        let root = root_suite(vec![
+            mock_app::orders::__suite_def(),
+            mock_app::orders::create_order::test_def(),
+            mock_app::orders::close_order::test_def(),
+            mock_app::refunds::__suite_def(),
+            mock_app::refunds::refund_order::test_def(),
+        ]);
+
+        // Assert
+        assert_eq!(
+            root.validate_dependencies(),
+            Err("integra8::components::dependencies::tests::mock_app::orders::close_order depends on \
+                integra8::components::dependencies::tests::mock_app::refunds::refund_order, which is not run before it, \
+                as its suite is run after the suite of the test"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn should_reject_dependency_between_parallel_suites() {
+        // Act
+        let root = root_suite(vec![
+            mock_app::parallel_a::__suite_def(),
+            mock_app::parallel_a::test_a::test_def(),
+            mock_app::parallel_b::__suite_def(),
+            mock_app::parallel_b::test_b::test_def(),
+        ]);
+
+        // Assert
+        assert_eq!(
+            root.validate_dependencies(),
+            Err("integra8::components::dependencies::tests::mock_app::parallel_b::test_b depends on \
+                integra8::components::dependencies::tests::mock_app::parallel_a::test_a, which may run at the same time, \
+                as both belong to parallel suites"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn should_find_no_cycle_in_acyclic_graph() {
+        let dependencies = vec![vec![], vec![0], vec![0, 1]];
+
+        // Assert
+        assert_eq!(find_cycle(&dependencies), None);
+    }
+
+    #[test]
+    fn should_find_cycle() {
+        let dependencies = vec![vec![], vec![2], vec![0, 1]];
+
+        // Assert
+        assert_eq!(find_cycle(&dependencies), Some(vec![1, 2, 1]));
+    }
+
+    #[test]
+    fn should_find_test_which_depends_on_its_self() {
+        let dependencies = vec![vec![0]];
+
+        // Assert
+        assert_eq!(find_cycle(&dependencies), Some(vec![0, 0]));
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Describes how a test is polled until it passes, as declared by `#[eventually(...)]`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EventuallyPolicy {
+    /// The duration after which the test stops being polled, and its last failure is reported.
+    pub timeout: Duration,
+
+    /// The duration to wait between each poll.
+    pub interval: Duration,
+}
+
+impl EventuallyPolicy {
+    pub fn new(timeout: Duration, interval: Duration) -> Self {
+        Self { timeout, interval }
+    }
+
+    /// Returns true if there is time for another poll, after the test has been polled for the given duration.
+    ///
+    /// # Arguments
+    ///
+    /// * `elapsed` - The time since the test was first polled.
+    ///
+    pub fn can_poll_again(&self, elapsed: Duration) -> bool {
+        elapsed.saturating_add(self.interval) < self.timeout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_poll_again_until_timeout() {
+        let policy = EventuallyPolicy::new(Duration::from_secs(10), Duration::from_secs(2));
+
+        // Assert
+        assert!(policy.can_poll_again(Duration::from_secs(0)));
+        assert!(policy.can_poll_again(Duration::from_secs(7)));
+        assert!(!policy.can_poll_again(Duration::from_secs(8)));
+    }
+}
@@ -0,0 +1,120 @@
+#[cfg(feature = "enable_serde")]
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::components::{BookEnd, ComponentDescription, ConcurrencyMode, Suite, Test};
+
+/// Describes a component found in the test application, and the components within it,
+/// as they would be run, without running any of them.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
+pub struct ComponentListing {
+    pub description: ComponentDescription,
+    pub attributes: ListedAttributes,
+
+    /// The setups, tests, nested suites and tear downs of a suite, in the order they are run.
+    #[cfg_attr(
+        feature = "enable_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub components: Vec<ComponentListing>,
+}
+
+/// The attributes of a component, once resolved from its decorations, its suites and the command line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
+pub struct ListedAttributes {
+    pub ignore: bool,
+    pub allow_fail: bool,
+    pub concurrency_mode: ConcurrencyMode,
+
+    /// The duration after which the component is aborted, or none if it has no time limit.
+    pub time_limit: Option<Duration>,
+
+    /// The duration after which the component is flagged as exceeding its expected duration, or none if it has no warning.
+    pub warning_time_limit: Option<Duration>,
+}
+
+impl ComponentListing {
+    /// Lists a suite, along with each of its components which are selected by the command line.
+    /// Components excluded by the test selection filters are not listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `suite` - The suite to list.
+    ///
+    pub fn from_suite<TParameters>(suite: &Suite<TParameters>) -> Self {
+        let setups = suite
+            .setups
+            .iter()
+            .filter(|setup| !setup.attributes.filtered)
+            .map(Self::from_bookend);
+
+        let tests = suite
+            .tests
+            .iter()
+            .filter(|test| !test.attributes.filtered)
+            .map(Self::from_test);
+
+        let suites = suite
+            .suites
+            .iter()
+            .filter(|suite| !suite.attributes.filtered)
+            .map(Self::from_suite);
+
+        let tear_downs = suite
+            .tear_downs
+            .iter()
+            .filter(|tear_down| !tear_down.attributes.filtered)
+            .map(Self::from_bookend);
+
+        Self {
+            description: suite.description.clone(),
+            attributes: ListedAttributes {
+                ignore: suite.attributes.ignore,
+                allow_fail: suite.attributes.allow_suite_fail,
+                concurrency_mode: suite.attributes.suite_concurrency_mode.clone(),
+                time_limit: suite.attributes.suite_time_limit,
+                warning_time_limit: suite.attributes.suite_warning_time_limit,
+            },
+            components: setups
+                .chain(tests)
+                .chain(suites)
+                .chain(tear_downs)
+                .collect(),
+        }
+    }
+
+    fn from_test<TParameters>(test: &Test<TParameters>) -> Self {
+        Self {
+            description: test.description.clone(),
+            attributes: ListedAttributes {
+                ignore: test.attributes.ignore,
+                allow_fail: test.attributes.allow_fail,
+                concurrency_mode: test.attributes.concurrency_mode.clone(),
+                time_limit: Some(test.attributes.time_limit),
+                warning_time_limit: Some(test.attributes.warning_time_limit),
+            },
+            components: Vec::new(),
+        }
+    }
+
+    fn from_bookend<TParameters>(bookend: &BookEnd<TParameters>) -> Self {
+        Self {
+            description: bookend.description.clone(),
+            attributes: ListedAttributes {
+                ignore: bookend.attributes.ignore,
+                allow_fail: false,
+                concurrency_mode: bookend.attributes.concurrency_mode.clone(),
+                time_limit: Some(bookend.attributes.time_limit),
+                warning_time_limit: None,
+            },
+            components: Vec::new(),
+        }
+    }
+
+    /// Returns this component and every component within it, depth first.
+    pub fn all(&self) -> Box<dyn Iterator<Item = &'_ ComponentListing> + '_> {
+        Box::new(std::iter::once(self).chain(self.components.iter().flat_map(|c| c.all())))
+    }
+}
@@ -9,3 +9,19 @@ macro_rules! src_loc {
         }
     };
 }
+
+/// Converts the value returned by a component function into a `DelegateResult`.
+/// Used by the decorations, as unlike `IntoDelegateResult` the error returned
+/// only needs to implement `Display` or `Debug`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! into_delegate_result {
+    ($returned:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::components::delegates::kind::{
+            DebugErrorTag as _, DisplayErrorTag as _, IntoDelegateResultTag as _,
+        };
+        let returned = $returned;
+        (&&&returned).delegate_result_kind().into_result(returned)
+    }};
+}
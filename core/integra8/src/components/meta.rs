@@ -6,6 +6,8 @@ use std::convert::AsRef;
 use std::ffi::OsStr;
 use std::fmt::{self, Display, Formatter};
 
+use crate::components::{ComponentMetadata, ComponentTags};
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ComponentPath(Cow<'static, str>);
 
@@ -14,9 +16,38 @@ impl ComponentPath {
         Self(Cow::from(path))
     }
 
+    pub fn from_string(path: String) -> Self {
+        Self(Cow::from(path))
+    }
+
     pub fn as_str<'a>(&'a self) -> &'a str {
         &self.0
     }
+
+    /// Returns true if this path is the given path, or is nested beneath it.
+    /// For example, each case of a test is nested beneath the test's path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to test against.
+    ///
+    pub fn is_within(&self, path: &ComponentPath) -> bool {
+        self.as_str()
+            .strip_prefix(path.as_str())
+            .is_some_and(|remainder| remainder.is_empty() || remainder.starts_with("::"))
+    }
+
+    /// Returns a hash of this path which, unlike `Hash`, is the same across builds and platforms.
+    pub fn stable_hash(&self) -> u64 {
+        fnv1a(self.as_str().as_bytes())
+    }
+}
+
+/// FNV-1a, used where a hash must be the same across builds
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
 }
 
 impl Ord for ComponentPath {
@@ -143,6 +174,28 @@ pub struct ComponentDescription {
     )]
     description: Option<Cow<'static, str>>,
 
+    /// The doc comments the component was declared with
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "enable_serde",
+        serde(skip_serializing_if = "Option::is_none")
+    )]
+    doc: Option<Cow<'static, str>>,
+
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "enable_serde",
+        serde(skip_serializing_if = "ComponentTags::is_empty")
+    )]
+    tags: ComponentTags,
+
+    #[serde(default)]
+    #[cfg_attr(
+        feature = "enable_serde",
+        serde(skip_serializing_if = "ComponentMetadata::is_empty")
+    )]
+    metadata: ComponentMetadata,
+
     id: ComponentId,
 
     parent_id: ComponentId,
@@ -162,6 +215,8 @@ impl ComponentDescription {
         location: ComponentLocation,
         parent_location: ComponentLocation,
         description: Option<&'static str>,
+        tags: ComponentTags,
+        metadata: ComponentMetadata,
         component_type: ComponentType,
     ) -> Self {
         Self {
@@ -172,9 +227,18 @@ impl ComponentDescription {
             component_type,
             name: name.map(Cow::from),
             description: description.map(Cow::from),
+            doc: None,
+            tags,
+            metadata,
         }
     }
 
+    /// Returns the same description, along with the doc comments the component was declared with.
+    pub fn with_doc(mut self, doc: Option<&'static str>) -> Self {
+        self.doc = doc.map(Cow::from);
+        self
+    }
+
     pub fn is_root(&self) -> bool {
         self.location.path == self.parent_location.path
     }
@@ -224,6 +288,18 @@ impl ComponentDescription {
         self.description.as_ref().map(|x| x.as_ref())
     }
 
+    pub fn doc(&self) -> Option<&'_ str> {
+        self.doc.as_deref()
+    }
+
+    pub fn tags(&self) -> &'_ ComponentTags {
+        &self.tags
+    }
+
+    pub fn metadata(&self) -> &'_ ComponentMetadata {
+        &self.metadata
+    }
+
     pub fn component_type(&self) -> &'_ ComponentType {
         &self.component_type
     }
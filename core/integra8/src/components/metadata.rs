@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// Key value pairs describing a component, such as the team which owns it or its severity.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ComponentMetadata(BTreeMap<Cow<'static, str>, Cow<'static, str>>);
+
+impl ComponentMetadata {
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
+
+    pub fn from(metadata: &'static [(&'static str, &'static str)]) -> Self {
+        Self::new().inherit(metadata)
+    }
+
+    /// Returns the metadata of a child component, being the metadata of its parent
+    /// along with the child's own metadata, which takes precedence over its parent's.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - The metadata the child component was decorated with.
+    ///
+    pub fn inherit(&self, metadata: &'static [(&'static str, &'static str)]) -> Self {
+        let mut inherited = self.0.clone();
+        for (key, value) in metadata {
+            inherited.insert(Cow::from(*key), Cow::from(*value));
+        }
+        Self(inherited)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&'_ str> {
+        self.0.get(key).map(|value| value.as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'_ str, &'_ str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+    }
+}
+
+impl Display for ComponentMetadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let pairs: Vec<String> = self
+            .iter()
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_inherit_parent_metadata() {
+        let metadata = ComponentMetadata::from(&[("owner", "payments"), ("severity", "p2")])
+            .inherit(&[("severity", "p1"), ("ticket", "PAY-123")]);
+
+        // Assert
+        assert_eq!(metadata.get("owner"), Some("payments"));
+        assert_eq!(metadata.get("severity"), Some("p1"));
+        assert_eq!(metadata.get("ticket"), Some("PAY-123"));
+        assert_eq!(
+            metadata.to_string(),
+            "owner = payments, severity = p1, ticket = PAY-123"
+        );
+    }
+}
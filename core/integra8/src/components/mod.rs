@@ -1,7 +1,7 @@
 pub mod context;
 pub use context::{
-    ChildProcessComponentArgs, ChildProcessComponentMetaArgs, ExecutionArtifact,
-    ExecutionArtifacts, ExecutionContext, ExecutionStrategy, TestParameters,
+    ChildProcessComponentArgs, ChildProcessComponentMetaArgs, ChildProcessHookArgs,
+    ExecutionArtifact, ExecutionArtifacts, ExecutionContext, ExecutionStrategy, TestParameters,
 };
 
 pub mod delegates;
@@ -16,17 +16,48 @@ pub use bookends::{BookEnd, BookEndAttributes};
 mod suite;
 pub use suite::{Suite, SuiteAttributes};
 
+mod dependencies;
+
 mod acceptance_criteria;
 pub use acceptance_criteria::{AcceptanceCriteria, TimingAcceptanceCriteria};
 
 pub mod macros;
 
+mod tags;
+pub use tags::{ComponentTags, TagExpression};
+
+mod metadata;
+pub use metadata::ComponentMetadata;
+
+mod retry;
+pub use retry::{RetryBackoff, RetryPolicy};
+
+mod eventually;
+pub use eventually::EventuallyPolicy;
+
+mod cancellation;
+pub use cancellation::{CancellationToken, Cancelled};
+
+mod skip;
+pub(crate) use skip::SkipRequest;
+pub use skip::SkipCondition;
+
+mod state;
+pub use state::SuiteState;
+
 mod meta;
+pub(crate) use meta::fnv1a;
 pub use meta::{
     ComponentDescription, ComponentGeneratorId, ComponentId, ComponentLocation, ComponentPath,
     ComponentType, ConcurrencyMode,
 };
 
+mod shard;
+pub use shard::{Shard, ShardMode};
+
+mod listing;
+pub use listing::{ComponentListing, ListedAttributes};
+
 #[derive(Clone, Debug)]
 pub enum Component<TParameters> {
     Suite(Suite<TParameters>),
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How the delay between retries grows with each attempt.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RetryBackoff {
+    /// Wait the same delay before each retry
+    Fixed,
+
+    /// Double the delay after each retry
+    Exponential,
+}
+
+/// Describes how a failed component is re-run, as declared by `#[retry(...)]`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// The maximum number of times the component is re-run after its first attempt fails.
+    pub times: u32,
+
+    /// The delay before the first retry.
+    pub delay: Duration,
+
+    /// How the delay grows for each subsequent retry.
+    pub backoff: RetryBackoff,
+}
+
+impl RetryPolicy {
+    pub fn new(times: u32, delay: Duration, backoff: RetryBackoff) -> Self {
+        Self {
+            times,
+            delay,
+            backoff,
+        }
+    }
+
+    /// Returns the duration to wait before the given retry.
+    ///
+    /// # Arguments
+    ///
+    /// * `retry` - The number of the retry, starting from 1.
+    ///
+    pub fn delay_before(&self, retry: u32) -> Duration {
+        match self.backoff {
+            RetryBackoff::Fixed => self.delay,
+            RetryBackoff::Exponential => self
+                .delay
+                .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_use_same_delay_for_fixed_backoff() {
+        let policy = RetryPolicy::new(3, Duration::from_secs(2), RetryBackoff::Fixed);
+
+        // Assert
+        assert_eq!(policy.delay_before(1), Duration::from_secs(2));
+        assert_eq!(policy.delay_before(3), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn should_double_delay_for_exponential_backoff() {
+        let policy = RetryPolicy::new(3, Duration::from_secs(2), RetryBackoff::Exponential);
+
+        // Assert
+        assert_eq!(policy.delay_before(1), Duration::from_secs(2));
+        assert_eq!(policy.delay_before(2), Duration::from_secs(4));
+        assert_eq!(policy.delay_before(3), Duration::from_secs(8));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::components::ComponentPath;
+
+/// Determines which components are split between shards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShardMode {
+    /// Each top level suite is run by a single shard, along with all of its tests and nested suites.
+    Suite,
+
+    /// Each test is run by a single shard, regardless of the suite it belongs to.
+    Test,
+}
+
+impl std::str::FromStr for ShardMode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Suite" => Ok(ShardMode::Suite),
+            "Test" => Ok(ShardMode::Test),
+            _ => Err(format!(
+                "{} was not a valid shard mode. Valid values are either \"Suite\" or \"Test\".",
+                s
+            )),
+        }
+    }
+}
+
+/// One of several shards a run is split between, given as `{index}/{count}` where the index starts from 1.
+///
+/// Components are assigned to a shard by a stable hash of their path,
+/// so every shard agrees on which shard runs each component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shard {
+    index: usize,
+    count: usize,
+    mode: ShardMode,
+}
+
+impl Shard {
+    /// # Arguments
+    ///
+    /// * `index` - The index of this shard, from 1 to `count`.
+    ///
+    /// * `count` - The total number of shards.
+    ///
+    /// * `mode` - Determines which components are split between shards.
+    ///
+    pub fn new(index: usize, count: usize, mode: ShardMode) -> Result<Self, String> {
+        if count == 0 || index == 0 || index > count {
+            return Err(format!(
+                "{}/{} was not a valid shard. The index must be from 1 to the number of shards.",
+                index, count
+            ));
+        }
+        Ok(Self { index, count, mode })
+    }
+
+    /// Returns the same shard, splitting components by the given mode.
+    pub fn by(mut self, mode: ShardMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mode(&self) -> ShardMode {
+        self.mode
+    }
+
+    /// Returns true if the component at the given path is assigned to this shard.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the top level suite or the test, depending on the shard mode.
+    ///
+    pub fn includes(&self, path: &ComponentPath) -> bool {
+        (path.stable_hash() % self.count as u64) as usize == self.index - 1
+    }
+}
+
+impl std::str::FromStr for Shard {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "{} was not a valid shard. Expected \"{{index}}/{{count}}\", for example \"1/4\".",
+                s
+            )
+        };
+
+        let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+        let index = index.trim().parse().map_err(|_| invalid())?;
+        let count = count.trim().parse().map_err(|_| invalid())?;
+        Shard::new(index, count, ShardMode::Suite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn should_parse_shard() {
+        // Act
+        let shard = Shard::from_str("2/4").unwrap();
+
+        // Assert
+        assert_eq!((shard.index(), shard.count()), (2, 4));
+        assert_eq!(shard.mode(), ShardMode::Suite);
+    }
+
+    #[test]
+    fn should_not_parse_shard_out_of_range() {
+        // Assert
+        assert!(Shard::from_str("0/4").is_err());
+        assert!(Shard::from_str("5/4").is_err());
+        assert!(Shard::from_str("1/0").is_err());
+        assert!(Shard::from_str("1").is_err());
+    }
+
+    #[test]
+    fn should_assign_each_path_to_exactly_one_shard() {
+        let shards = (1..=3)
+            .map(|i| Shard::new(i, 3, ShardMode::Test).unwrap())
+            .collect::<Vec<_>>();
+
+        for path in ["app::a", "app::b", "app::c", "app::suite::d"] {
+            // Act
+            let path = ComponentPath::from(path);
+            let assigned = shards.iter().filter(|s| s.includes(&path)).count();
+
+            // Assert
+            assert_eq!(assigned, 1);
+        }
+    }
+}
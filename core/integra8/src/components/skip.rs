@@ -0,0 +1,85 @@
+use std::any::Any;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
+
+/// A condition evaluated against the parameters before a component is run, as declared by `#[skip_if(...)]`.
+/// Components which meet their condition are skipped, along with their child components.
+#[derive(Clone, Copy)]
+pub struct SkipCondition {
+    reason: &'static str,
+    condition: fn(&dyn Any) -> bool,
+}
+
+impl SkipCondition {
+    /// Creates a new skip condition
+    ///
+    /// # Arguments
+    ///
+    /// * `reason` - The reason reported when the component is skipped.
+    ///
+    /// * `condition` - Returns true if the component should be skipped, given the parameters.
+    ///
+    pub fn new(reason: &'static str, condition: fn(&dyn Any) -> bool) -> Self {
+        Self { reason, condition }
+    }
+
+    pub fn reason(&self) -> &'static str {
+        self.reason
+    }
+
+    /// Returns the reason the component is skipped, or none if the condition is not met.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The parameter type as defined by the test author.
+    ///
+    pub fn evaluate<TParameters: 'static>(&self, parameters: &TParameters) -> Option<String> {
+        match (self.condition)(parameters) {
+            true => Some(self.reason.to_string()),
+            false => None,
+        }
+    }
+}
+
+// Implemented by hand, as function pointers can not be meaningfully compared
+impl PartialEq for SkipCondition {
+    fn eq(&self, other: &Self) -> bool {
+        self.reason == other.reason
+    }
+}
+
+impl Eq for SkipCondition {}
+
+impl Hash for SkipCondition {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.reason.hash(state);
+    }
+}
+
+impl Debug for SkipCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SkipCondition")
+            .field("reason", &self.reason)
+            .finish()
+    }
+}
+
+/// The payload a component unwinds with when it skips itself by calling `ExecutionContext::skip`.
+/// The unwind is caught by the executor, which reports the component as skipped rather than failed.
+pub(crate) struct SkipRequest {
+    reason: String,
+}
+
+impl SkipRequest {
+    pub fn unwind(reason: String) -> ! {
+        // `resume_unwind` is used over `panic_any`, as it does not invoke the panic hook
+        std::panic::resume_unwind(Box::new(SkipRequest { reason }))
+    }
+
+    /// Returns the reason given to `ExecutionContext::skip`, or none if the panic was not a request to skip.
+    pub fn from_panic(payload: &(dyn Any + Send)) -> Option<&'_ str> {
+        payload
+            .downcast_ref::<SkipRequest>()
+            .map(|request| request.reason.as_str())
+    }
+}
@@ -0,0 +1,275 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::any::{type_name, Any};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+/// The values returned by the setups of a suite, which are shared with the tests, setups and
+/// tear downs of the suite and its child suites.
+///
+/// Values are keyed by their type, so a suite holds at most one value of each type.
+/// Values are only serialized once they are passed to a component which is run in a child process.
+#[derive(Clone, Default)]
+pub struct SuiteState {
+    values: Arc<RwLock<BTreeMap<String, SuiteStateValue>>>,
+    parent: Option<Box<SuiteState>>,
+}
+
+/// Serializes a value of the type it was inserted as
+type SerializeFn = fn(&(dyn Any + Send + Sync)) -> Result<String, String>;
+
+struct SuiteStateValue {
+    // Values received from another process are only deserialized once they are first requested
+    value: Option<Arc<dyn Any + Send + Sync>>,
+    serialized: Option<String>,
+    serialize: Option<SerializeFn>,
+}
+
+impl SuiteStateValue {
+    fn serialized(&mut self) -> Result<String, String> {
+        if let (None, Some(value), Some(serialize)) =
+            (&self.serialized, &self.value, self.serialize)
+        {
+            self.serialized = Some(serialize(value.as_ref())?);
+        }
+        Ok(self.serialized.clone().unwrap_or_default())
+    }
+}
+
+fn serialize<T: Serialize + 'static>(value: &(dyn Any + Send + Sync)) -> Result<String, String> {
+    serde_json::to_string(value.downcast_ref::<T>().unwrap()).map_err(|err| {
+        format!(
+            "Failed to serialize suite state of type {}: {}",
+            type_name::<T>(),
+            err
+        )
+    })
+}
+
+impl SuiteState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the state of a child suite, which can read the values of its parent,
+    /// but only inserts into its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The state of the parent suite.
+    ///
+    pub fn child_of(parent: &SuiteState) -> Self {
+        Self {
+            values: Arc::new(RwLock::new(BTreeMap::new())),
+            parent: Some(Box::new(parent.clone())),
+        }
+    }
+
+    /// Creates a state from values serialized by another process.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The serialized values, keyed by their type name.
+    ///
+    pub fn from_serialized(values: BTreeMap<String, String>) -> Self {
+        let state = Self::new();
+        state.extend_serialized(values);
+        state
+    }
+
+    /// Inserts a value, replacing any value of the same type previously inserted into this state.
+    /// As values are keyed by their type, setups which provide values of a common type, such as `String`,
+    /// should wrap them in a type of their own to avoid replacing each other's values.
+    ///
+    /// The value is serialized if it is passed to a component run in a child process,
+    /// which fails if it can not be serialized.
+    pub fn insert<T: Serialize + Send + Sync + 'static>(&self, value: T) -> Result<(), String> {
+        self.values.write().unwrap().insert(
+            type_name::<T>().to_string(),
+            SuiteStateValue {
+                value: Some(Arc::new(value)),
+                serialized: None,
+                serialize: Some(serialize::<T>),
+            },
+        );
+        Ok(())
+    }
+
+    /// Returns the value of the given type, looking in this state first and then its parents.
+    pub fn get<T: DeserializeOwned + Send + Sync + 'static>(&self) -> Result<Arc<T>, String> {
+        match self.try_get::<T>()? {
+            Some(value) => Ok(value),
+            None => Err(format!(
+                "No setup has provided suite state of type {}",
+                type_name::<T>()
+            )),
+        }
+    }
+
+    fn try_get<T: DeserializeOwned + Send + Sync + 'static>(
+        &self,
+    ) -> Result<Option<Arc<T>>, String> {
+        let key = type_name::<T>();
+        let mut values = self.values.write().unwrap();
+
+        let entry = match values.get_mut(key) {
+            Some(entry) => entry,
+            None => {
+                drop(values);
+                return match &self.parent {
+                    Some(parent) => parent.try_get::<T>(),
+                    None => Ok(None),
+                };
+            }
+        };
+
+        let value = match &entry.value {
+            Some(value) => value.clone(),
+            None => {
+                let serialized = entry.serialized.as_deref().unwrap_or_default();
+                let value: T = serde_json::from_str(serialized).map_err(|err| {
+                    format!("Failed to deserialize suite state of type {}: {}", key, err)
+                })?;
+                let value: Arc<dyn Any + Send + Sync> = Arc::new(value);
+                entry.value = Some(value.clone());
+                value
+            }
+        };
+
+        value
+            .downcast::<T>()
+            .map(Some)
+            .map_err(|_| format!("Suite state of type {} has an unexpected type", key))
+    }
+
+    /// Inserts values serialized by another process, replacing any values of the same type.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The serialized values, keyed by their type name.
+    ///
+    pub fn extend_serialized(&self, values: BTreeMap<String, String>) {
+        self.values
+            .write()
+            .unwrap()
+            .extend(values.into_iter().map(|(key, serialized)| {
+                (
+                    key,
+                    SuiteStateValue {
+                        value: None,
+                        serialized: Some(serialized),
+                        serialize: None,
+                    },
+                )
+            }));
+    }
+
+    /// Returns all the values which can be read from this state serialized,
+    /// with the values of this state taking precedence over the values of its parents.
+    /// Returns an error if any of the values can not be serialized.
+    pub fn to_serialized(&self) -> Result<BTreeMap<String, String>, String> {
+        let mut serialized = match &self.parent {
+            Some(parent) => parent.to_serialized()?,
+            None => BTreeMap::new(),
+        };
+        serialized.extend(self.own_serialized()?);
+        Ok(serialized)
+    }
+
+    /// Returns only the values inserted into this state serialized, excluding the values of its parents.
+    /// Returns an error if any of the values can not be serialized.
+    pub fn own_serialized(&self) -> Result<BTreeMap<String, String>, String> {
+        self.values
+            .write()
+            .unwrap()
+            .iter_mut()
+            .map(|(key, value)| Ok((key.clone(), value.serialized()?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_get_value_inserted_into_parent() {
+        let parent = SuiteState::new();
+        let child = SuiteState::child_of(&parent);
+
+        // Act
+        parent.insert(String::from("token")).unwrap();
+
+        // Assert
+        assert_eq!(*child.get::<String>().unwrap(), "token");
+    }
+
+    #[test]
+    fn should_prefer_value_inserted_into_child() {
+        let parent = SuiteState::new();
+        let child = SuiteState::child_of(&parent);
+
+        // Act
+        parent.insert(1_u32).unwrap();
+        child.insert(2_u32).unwrap();
+
+        // Assert
+        assert_eq!(*child.get::<u32>().unwrap(), 2);
+        assert_eq!(*parent.get::<u32>().unwrap(), 1);
+    }
+
+    #[test]
+    fn should_not_get_value_inserted_into_child() {
+        let parent = SuiteState::new();
+        let child = SuiteState::child_of(&parent);
+
+        // Act
+        child.insert(1_u32).unwrap();
+
+        // Assert
+        assert_eq!(
+            parent.get::<u32>().unwrap_err(),
+            "No setup has provided suite state of type u32"
+        );
+    }
+
+    #[test]
+    fn should_get_values_serialized_by_another_process() {
+        let parent = SuiteState::new();
+        parent.insert(String::from("token")).unwrap();
+        let child = SuiteState::child_of(&parent);
+        child.insert(vec![1_u32, 2]).unwrap();
+
+        // Act
+        let state = SuiteState::from_serialized(child.to_serialized().unwrap());
+
+        // Assert
+        assert_eq!(*state.get::<String>().unwrap(), "token");
+        assert_eq!(*state.get::<Vec<u32>>().unwrap(), vec![1, 2]);
+    }
+
+    struct Unserializable;
+
+    impl Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("not supported"))
+        }
+    }
+
+    #[test]
+    fn should_only_serialize_values_passed_to_another_process() {
+        let state = SuiteState::new();
+
+        // Act
+        state.insert(Unserializable).unwrap();
+
+        // Assert
+        assert_eq!(
+            state.to_serialized().unwrap_err(),
+            format!(
+                "Failed to serialize suite state of type {}: not supported",
+                type_name::<Unserializable>()
+            )
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use std::time::Duration;
 
 use crate::components::{
-    BookEnd, ComponentDescription, ComponentId, ComponentLocation, ComponentType, ConcurrencyMode,
-    Test, TestParameters,
+    BookEnd, ComponentDescription, ComponentId, ComponentLocation, ComponentMetadata,
+    ComponentTags, ComponentType, ConcurrencyMode, SkipCondition, Test, TestParameters,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -10,6 +10,12 @@ pub struct SuiteAttributes {
     /// Indicates that this entire suite should not be run.
     pub ignore: bool,
 
+    /// The condition under which this entire suite is skipped, evaluated against the parameters before it is run.
+    pub skip_if: Option<SkipCondition>,
+
+    /// Indicates that this entire suite was excluded from this run by the command line test selection.
+    pub filtered: bool,
+
     /// Indicates that this suite should be run, but failures should be ignored and do not cascade.
     pub allow_suite_fail: bool,
 
@@ -31,6 +37,14 @@ pub struct SuiteAttributes {
     /// Tear downs which are a part of this suite, that do not advertize a time limit will inherit this value
     pub tear_down_time_limit: Duration,
 
+    /// The duration after which this suite is flagged as exceeding its expected duration.
+    /// This is measured from when the suite starts, until its last component completes.
+    pub suite_warning_time_limit: Option<Duration>,
+
+    /// The maximum duration this suite can take before its remaining components are aborted,
+    /// measured from when the suite starts. Tear downs are still run once the limit is exceeded.
+    pub suite_time_limit: Option<Duration>,
+
     /// The concurrency model used when executing this suite of tests.
     /// `ConcurrencyMode::Parallel` will allow this suite to be run at the same time as other suites.
     /// `ConcurrencyMode::Sequential` will ensure this suite is only run on its own
@@ -47,17 +61,25 @@ impl SuiteAttributes {
         parent_desc: Option<&SuiteAttributes>,
         parameters: &TParameters,
         ignore: Option<bool>,
+        skip_if: Option<SkipCondition>,
+        filtered: Option<bool>,
         allow_suite_fail: Option<bool>,
         test_warning_time_limit: Option<Duration>,
         test_time_limit: Option<Duration>,
         setup_time_limit: Option<Duration>,
         tear_down_time_limit: Option<Duration>,
+        suite_warning_time_limit: Option<Duration>,
+        suite_time_limit: Option<Duration>,
         suite_concurrency_mode: Option<ConcurrencyMode>,
         test_concurrency_mode: Option<ConcurrencyMode>,
     ) -> Self {
         Self {
             ignore: ignore.unwrap_or_else(|| parent_desc.map_or(false, |p| p.ignore)),
 
+            skip_if,
+
+            filtered: filtered.unwrap_or_else(|| parent_desc.is_some_and(|p| p.filtered)),
+
             allow_suite_fail: allow_suite_fail
                 .unwrap_or_else(|| parent_desc.map_or(false, |p| p.allow_suite_fail)),
 
@@ -101,6 +123,14 @@ impl SuiteAttributes {
                 |val| val.clone(),
             ),
 
+            // Not inherited, as the time limit of a parent suite already applies to its children.
+            // The root suite is instead limited by the run time limit
+            suite_warning_time_limit,
+            suite_time_limit: match parent_desc {
+                Some(_) => suite_time_limit,
+                None => suite_time_limit.or_else(|| parameters.run_time_limit_duration()),
+            },
+
             suite_concurrency_mode: suite_concurrency_mode.map_or_else(
                 || {
                     parent_desc.map_or_else(
@@ -144,6 +174,12 @@ pub struct Suite<TParameters> {
     pub tests: Vec<Test<TParameters>>,
     pub setups: Vec<BookEnd<TParameters>>,
     pub tear_downs: Vec<BookEnd<TParameters>>,
+
+    /// Hooks run before each test within this suite, including the tests of its nested suites.
+    pub before_each: Vec<BookEnd<TParameters>>,
+
+    /// Hooks run after each test within this suite, including the tests of its nested suites.
+    pub after_each: Vec<BookEnd<TParameters>>,
     pub suites: Vec<Suite<TParameters>>,
 }
 
@@ -154,20 +190,41 @@ impl<TParameters: TestParameters> Suite<TParameters> {
         id: ComponentId,
         name: Option<&'static str>,
         description: Option<&'static str>,
+        doc: Option<&'static str>,
+        tags: &'static [&'static str],
+        metadata: &'static [(&'static str, &'static str)],
         ignore: Option<bool>,
+        skip_if: Option<SkipCondition>,
+        filtered: Option<bool>,
         location: ComponentLocation,
         allow_suite_fail: Option<bool>,
         test_warning_time_limit: Option<Duration>,
         test_time_limit: Option<Duration>,
         setup_time_limit: Option<Duration>,
         tear_down_time_limit: Option<Duration>,
+        suite_warning_time_limit: Option<Duration>,
+        suite_time_limit: Option<Duration>,
         suite_concurrency_mode: Option<ConcurrencyMode>,
         test_concurrency_mode: Option<ConcurrencyMode>,
     ) -> Suite<TParameters> {
-        let (parent_location, parent_id) = parent
-            .map(|p| (p.1.location().clone(), p.1.id().clone()))
+        let (parent_location, parent_id, parent_tags, parent_metadata) = parent
+            .map(|p| {
+                (
+                    p.1.location().clone(),
+                    p.1.id().clone(),
+                    p.1.tags().clone(),
+                    p.1.metadata().clone(),
+                )
+            })
             // root nodes have themselves as their parent and an id of zero
-            .unwrap_or_else(|| (location.clone(), id.clone()));
+            .unwrap_or_else(|| {
+                (
+                    location.clone(),
+                    id.clone(),
+                    ComponentTags::new(),
+                    ComponentMetadata::new(),
+                )
+            });
 
         Suite {
             description: ComponentDescription::new(
@@ -177,23 +234,32 @@ impl<TParameters: TestParameters> Suite<TParameters> {
                 location,
                 parent_location,
                 description,
+                parent_tags.inherit(tags),
+                parent_metadata.inherit(metadata),
                 ComponentType::Suite,
-            ),
+            )
+            .with_doc(doc),
             attributes: SuiteAttributes::new(
                 parent.map(|p| p.0),
                 parameters,
                 ignore,
+                skip_if,
+                filtered,
                 allow_suite_fail,
                 test_warning_time_limit,
                 test_time_limit,
                 setup_time_limit,
                 tear_down_time_limit,
+                suite_warning_time_limit,
+                suite_time_limit,
                 suite_concurrency_mode,
                 test_concurrency_mode,
             ),
             tests: Vec::new(),
             setups: Vec::new(),
             tear_downs: Vec::new(),
+            before_each: Vec::new(),
+            after_each: Vec::new(),
             suites: Vec::new(),
         }
     }
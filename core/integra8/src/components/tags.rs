@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ComponentTags(Vec<Cow<'static, str>>);
+
+impl ComponentTags {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn from(tags: &'static [&'static str]) -> Self {
+        Self::new().inherit(tags)
+    }
+
+    /// Returns the tags of a child component, being the tags of its parent along with the child's own tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - The tags the child component was decorated with.
+    ///
+    pub fn inherit(&self, tags: &'static [&'static str]) -> Self {
+        let mut inherited = self.0.clone();
+        for tag in tags {
+            if !inherited.iter().any(|t| t == tag) {
+                inherited.push(Cow::from(*tag));
+            }
+        }
+        Self(inherited)
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.0.iter().any(|t| t == tag)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'_ str> {
+        self.0.iter().map(|t| t.as_ref())
+    }
+}
+
+impl Display for ComponentTags {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.iter().collect::<Vec<&str>>().join(", "))
+    }
+}
+
+/// A boolean expression used to select components by their tags.
+///
+/// Supports `&` (and), `|` (or), `!` (not) and parentheses, for example `smoke & !(slow | flaky)`.
+/// `&` has a higher precedence than `|`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TagExpression {
+    Tag(String),
+    Not(Box<TagExpression>),
+    And(Box<TagExpression>, Box<TagExpression>),
+    Or(Box<TagExpression>, Box<TagExpression>),
+}
+
+impl TagExpression {
+    pub fn is_match(&self, tags: &ComponentTags) -> bool {
+        match self {
+            Self::Tag(tag) => tags.contains(tag),
+            Self::Not(expr) => !expr.is_match(tags),
+            Self::And(lhs, rhs) => lhs.is_match(tags) && rhs.is_match(tags),
+            Self::Or(lhs, rhs) => lhs.is_match(tags) || rhs.is_match(tags),
+        }
+    }
+}
+
+impl std::str::FromStr for TagExpression {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = TagExpressionParser {
+            chars: s.chars().peekable(),
+        };
+
+        let expr = parser.parse_or()?;
+        match parser.next_token() {
+            None => Ok(expr),
+            Some(c) => Err(format!(
+                "{} was not a valid tag expression. Unexpected character '{}'.",
+                s, c
+            )),
+        }
+    }
+}
+
+impl Display for TagExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tag(tag) => write!(f, "{}", tag),
+            Self::Not(expr) => write!(f, "!{}", expr),
+            Self::And(lhs, rhs) => write!(f, "({} & {})", lhs, rhs),
+            Self::Or(lhs, rhs) => write!(f, "({} | {})", lhs, rhs),
+        }
+    }
+}
+
+struct TagExpressionParser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> TagExpressionParser<'a> {
+    // or := and ('|' and)*
+    fn parse_or(&mut self) -> Result<TagExpression, String> {
+        let mut expr = self.parse_and()?;
+        while self.peek_token() == Some('|') {
+            self.chars.next();
+            expr = TagExpression::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    // and := not ('&' not)*
+    fn parse_and(&mut self) -> Result<TagExpression, String> {
+        let mut expr = self.parse_not()?;
+        while self.peek_token() == Some('&') {
+            self.chars.next();
+            expr = TagExpression::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    // not := '!' not | '(' or ')' | tag
+    fn parse_not(&mut self) -> Result<TagExpression, String> {
+        match self.peek_token() {
+            Some('!') => {
+                self.chars.next();
+                Ok(TagExpression::Not(Box::new(self.parse_not()?)))
+            }
+            Some('(') => {
+                self.chars.next();
+                let expr = self.parse_or()?;
+                match self.next_token() {
+                    Some(')') => Ok(expr),
+                    _ => Err("expected ')' in tag expression".to_string()),
+                }
+            }
+            Some(c) if is_tag_char(c) => {
+                let mut tag = String::new();
+                while let Some(c) = self.chars.peek().filter(|c| is_tag_char(**c)) {
+                    tag.push(*c);
+                    self.chars.next();
+                }
+                Ok(TagExpression::Tag(tag))
+            }
+            Some(c) => Err(format!("expected a tag, but found '{}'", c)),
+            None => Err("expected a tag, but found the end of the tag expression".to_string()),
+        }
+    }
+
+    fn peek_token(&mut self) -> Option<char> {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.chars.next();
+        }
+        self.chars.peek().cloned()
+    }
+
+    fn next_token(&mut self) -> Option<char> {
+        self.peek_token();
+        self.chars.next()
+    }
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '.' || c == ':'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn is_match(expr: &str, tags: &'static [&'static str]) -> bool {
+        TagExpression::from_str(expr)
+            .unwrap()
+            .is_match(&ComponentTags::from(tags))
+    }
+
+    #[test]
+    fn should_match_single_tag() {
+        assert!(is_match("smoke", &["smoke", "slow"]));
+        assert!(!is_match("smoke", &["slow"]));
+        assert!(!is_match("smoke", &[]));
+    }
+
+    #[test]
+    fn should_match_expression() {
+        assert!(is_match("smoke & !slow", &["smoke"]));
+        assert!(!is_match("smoke & !slow", &["smoke", "slow"]));
+        assert!(is_match("smoke | nightly", &["nightly"]));
+        assert!(is_match("!(slow | flaky)", &["smoke"]));
+        assert!(!is_match("!(slow | flaky)", &["flaky"]));
+    }
+
+    #[test]
+    fn should_give_and_precedence_over_or() {
+        let expr = TagExpression::from_str("a | b & c").unwrap();
+
+        // Assert
+        assert_eq!(expr.to_string(), "(a | (b & c))");
+    }
+
+    #[test]
+    fn should_reject_invalid_expression() {
+        assert!(TagExpression::from_str("").is_err());
+        assert!(TagExpression::from_str("smoke &").is_err());
+        assert!(TagExpression::from_str("(smoke").is_err());
+        assert!(TagExpression::from_str("smoke slow").is_err());
+    }
+
+    #[test]
+    fn should_inherit_parent_tags() {
+        let tags = ComponentTags::from(&["smoke"]).inherit(&["slow", "smoke"]);
+
+        // Assert
+        assert_eq!(tags.iter().collect::<Vec<&str>>(), vec!["smoke", "slow"]);
+    }
+}
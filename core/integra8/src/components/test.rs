@@ -2,8 +2,9 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::components::{
-    ComponentDescription, ComponentId, ComponentLocation, ComponentType, ConcurrencyMode, Delegate,
-    SuiteAttributes, TestParameters,
+    BookEnd, ComponentDescription, ComponentId, ComponentLocation, ComponentPath, ComponentType,
+    ConcurrencyMode, Delegate, EventuallyPolicy, RetryPolicy, SkipCondition, SuiteAttributes,
+    TestParameters,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -14,6 +15,14 @@ pub struct TestAttributes {
     /// Indicates that test should not be run.
     pub ignore: bool,
 
+    /// The condition under which this test is skipped, evaluated against the parameters before it is run.
+    /// Not passed to child processes, as the condition is evaluated by the parent process.
+    #[serde(skip)]
+    pub skip_if: Option<SkipCondition>,
+
+    /// Indicates that test was excluded from this run by the command line test selection.
+    pub filtered: bool,
+
     /// The the duration after which a test is flagged as exceeded is expected duration.
     /// This can be used to give early warnings before a test exceeds some critical threshold.
     /// For example, a HTTP request time out.
@@ -26,6 +35,16 @@ pub struct TestAttributes {
     /// `ConcurrencyMode::Parallel` will allow this test for be run at the same time as other tests within this tests suite
     /// `ConcurrencyMode::Sequential` will ensure that this test wont run at the same time as any other test from this suite
     pub concurrency_mode: ConcurrencyMode,
+
+    /// Describes how this test is re-run if it fails, or none if it is not retried.
+    pub retry: Option<RetryPolicy>,
+
+    /// Describes how this test is polled until it passes, or none if it is only run once.
+    pub eventually: Option<EventuallyPolicy>,
+
+    /// The paths of the tests which must complete before this test is run.
+    /// If any of these tests fail, this test is not run.
+    pub depends_on: Vec<ComponentPath>,
 }
 
 impl TestAttributes {
@@ -33,10 +52,15 @@ impl TestAttributes {
         parent_desc: &SuiteAttributes,
         parameters: &TParameters,
         ignore: Option<bool>,
+        skip_if: Option<SkipCondition>,
+        filtered: Option<bool>,
         allow_fail: Option<bool>,
         warning_time_limit: Option<Duration>,
         time_limit: Option<Duration>,
         concurrency_mode: Option<ConcurrencyMode>,
+        retry: Option<RetryPolicy>,
+        eventually: Option<EventuallyPolicy>,
+        depends_on: Vec<ComponentPath>,
     ) -> Self {
         Self {
             // If we are running as a child process, we need the test
@@ -47,6 +71,8 @@ impl TestAttributes {
                 false => allow_fail.unwrap_or(false),
             },
             ignore: ignore.unwrap_or_else(|| parent_desc.ignore),
+            skip_if,
+            filtered: filtered.unwrap_or(parent_desc.filtered),
 
             warning_time_limit: warning_time_limit
                 .map_or_else(|| parent_desc.test_warning_time_limit, |val| val),
@@ -55,15 +81,39 @@ impl TestAttributes {
 
             concurrency_mode: concurrency_mode
                 .map_or_else(|| parent_desc.test_concurrency_mode.clone(), |val| val),
+            retry,
+            eventually,
+            depends_on,
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct Test<TParameters> {
     pub attributes: TestAttributes,
     pub description: ComponentDescription,
     pub test_fn: Delegate<TParameters>,
+
+    /// Hooks run before this test, from the outer most suite to the suite the test belongs to.
+    /// These are wrapped around the test when it is scheduled.
+    pub before_each: Vec<BookEnd<TParameters>>,
+
+    /// Hooks run after this test, even if it fails or times out,
+    /// from the suite the test belongs to, to the outer most suite.
+    pub after_each: Vec<BookEnd<TParameters>>,
+}
+
+// Implemented by hand, as derive would require `TParameters` to be `Clone`
+impl<TParameters> Clone for Test<TParameters> {
+    fn clone(&self) -> Self {
+        Self {
+            attributes: self.attributes.clone(),
+            description: self.description.clone(),
+            test_fn: self.test_fn.clone(),
+            before_each: self.before_each.clone(),
+            after_each: self.after_each.clone(),
+        }
+    }
 }
 
 impl<TParameters: TestParameters> Test<TParameters> {
@@ -74,12 +124,20 @@ impl<TParameters: TestParameters> Test<TParameters> {
         id: ComponentId,
         name: Option<&'static str>,
         description: Option<&'static str>,
+        doc: Option<&'static str>,
+        tags: &'static [&'static str],
+        metadata: &'static [(&'static str, &'static str)],
         location: ComponentLocation,
         ignore: Option<bool>,
+        skip_if: Option<SkipCondition>,
+        filtered: Option<bool>,
         allow_fail: Option<bool>,
         warning_time_limit: Option<Duration>,
         time_limit: Option<Duration>,
         concurrency_mode: Option<ConcurrencyMode>,
+        retry: Option<RetryPolicy>,
+        eventually: Option<EventuallyPolicy>,
+        depends_on: &'static [&'static str],
         test_fn: Delegate<TParameters>,
     ) -> Self {
         Self {
@@ -90,18 +148,30 @@ impl<TParameters: TestParameters> Test<TParameters> {
                 location,
                 parent_description.location().clone(),
                 description,
+                parent_description.tags().inherit(tags),
+                parent_description.metadata().inherit(metadata),
                 ComponentType::Test,
-            ),
+            )
+            .with_doc(doc),
             attributes: TestAttributes::new(
                 parent_attributes,
                 parameters,
                 ignore,
+                skip_if,
+                filtered,
                 allow_fail,
                 warning_time_limit,
                 time_limit,
                 concurrency_mode,
+                retry,
+                eventually,
+                // Dependencies are resolved to the paths of the tests they refer to,
+                // once the component hierarchy is built
+                depends_on.iter().map(|path| ComponentPath::from(path)).collect(),
             ),
             test_fn: test_fn,
+            before_each: Vec::new(),
+            after_each: Vec::new(),
         }
     }
 }
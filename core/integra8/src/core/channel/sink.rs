@@ -67,6 +67,7 @@ impl ResultsSink {
 pub struct ResultsOutputWriterSink {
     state: RunSummary,
     output_writer: OutputFormatterAggregator,
+    write_run_complete: bool,
 }
 
 impl ResultsOutputWriterSink {
@@ -74,8 +75,21 @@ impl ResultsOutputWriterSink {
         Self {
             state: RunSummary::new(),
             output_writer: OutputFormatterAggregator::new(output_writer),
+            write_run_complete: true,
         }
     }
+
+    /// Skips writing the summary once the run completes, so the summary of several runs can be written instead
+    pub fn skip_run_complete(mut self) -> Self {
+        self.write_run_complete = false;
+        self
+    }
+
+    /// Records the seed the schedule was shuffled with in the summary of the run
+    pub fn shuffled_with(mut self, shuffle_seed: Option<u64>) -> Self {
+        self.state.with_shuffle_seed(shuffle_seed);
+        self
+    }
 }
 
 impl ResultsOutputWriterSink {
@@ -88,7 +102,10 @@ impl ResultsOutputWriterSink {
     }
 
     pub fn on_run_complete(&mut self) -> Result<(), Box<dyn Error>> {
-        self.output_writer.write_run_complete(&self.state)
+        match self.write_run_complete {
+            true => self.output_writer.write_run_complete(&self.state),
+            false => Ok(()),
+        }
     }
 
     // component
@@ -168,6 +185,13 @@ impl OutputFormatter for OutputFormatterAggregator {
         Ok(())
     }
 
+    fn write_run_notice(&mut self, notice: &str) -> Result<(), Box<dyn Error>> {
+        for o in &mut self.output_writers {
+            o.write_run_notice(notice)?;
+        }
+        Ok(())
+    }
+
     // Component
 
     fn write_component_start(&mut self, desc: &ComponentDescription) -> Result<(), Box<dyn Error>> {
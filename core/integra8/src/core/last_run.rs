@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::components::{fnv1a, ComponentPath, ComponentType};
+use crate::results::report::ComponentRunReport;
+use crate::results::{ComponentResult, FailureReason};
+
+/// The result of a test the last time it was run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LastRunResult {
+    Passed,
+    Failed,
+    TimedOut,
+}
+
+/// The results of the tests of a test binary, as of the last time each test was run.
+/// Used by `--framework:rerun-failed` to select only the tests which failed.
+///
+/// Results are persisted to a file in the system's temp directory, named after the binary's path,
+/// so each binary has its own results.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LastRunState {
+    tests: BTreeMap<ComponentPath, LastRunResult>,
+}
+
+impl LastRunState {
+    /// Returns the path of the file which the results of the current binary are persisted to.
+    pub fn path() -> PathBuf {
+        let exe = std::env::current_exe().unwrap_or_default();
+        let name = exe
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        std::env::temp_dir().join("integra8").join(format!(
+            "{}-{:016x}.json",
+            name,
+            fnv1a(exe.to_string_lossy().as_bytes())
+        ))
+    }
+
+    /// Loads the results of the current binary, or no results if the binary has not been run before.
+    pub fn load() -> Self {
+        Self::load_from(&Self::path())
+    }
+
+    /// Loads the results persisted to the given file, or no results if the file does not exist or can not be read.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to load.
+    ///
+    pub fn load_from(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persists the results of the current binary
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(&Self::path())
+    }
+
+    /// Persists the results to the given file, creating its directory if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to write.
+    ///
+    pub fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Records the results of the tests which were run.
+    /// Tests which did not run keep the result of the last time they were run.
+    ///
+    /// # Arguments
+    ///
+    /// * `reports` - The reports of the components of the run.
+    ///
+    pub fn record<'a>(&mut self, reports: impl IntoIterator<Item = &'a ComponentRunReport>) {
+        for report in reports {
+            if report.description.component_type() != &ComponentType::Test {
+                continue;
+            }
+
+            let result = match &report.result {
+                ComponentResult::Pass(_) | ComponentResult::Warning(_) => LastRunResult::Passed,
+                ComponentResult::Fail(FailureReason::Overtime) => LastRunResult::TimedOut,
+                ComponentResult::Fail(_) => LastRunResult::Failed,
+                ComponentResult::DidNotRun(_) => continue,
+            };
+            self.tests.insert(report.description.path().clone(), result);
+        }
+    }
+
+    /// Returns the paths of the tests which failed or timed out the last time they were run.
+    pub fn failed_paths(&self) -> Vec<ComponentPath> {
+        self.tests
+            .iter()
+            .filter(|(_, result)| **result != LastRunResult::Passed)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{
+        AcceptanceCriteria, ComponentDescription, ComponentId, ComponentMetadata, ComponentTags,
+        TimingAcceptanceCriteria,
+    };
+    use crate::results::report::ComponentReportBuilder;
+
+    fn report(path: &'static str, result: ComponentResult) -> ComponentRunReport {
+        let mut location = crate::src_loc!();
+        location.path = ComponentPath::from(path);
+
+        let mut builder = ComponentReportBuilder::new(
+            ComponentDescription::new(
+                None,
+                ComponentId::from(1),
+                ComponentId::from(0),
+                location,
+                crate::src_loc!(),
+                None,
+                ComponentTags::new(),
+                ComponentMetadata::new(),
+                ComponentType::Test,
+            ),
+            AcceptanceCriteria {
+                allowed_fail: false,
+                timing: TimingAcceptanceCriteria {
+                    warning_time_limit: None,
+                    time_limit: None,
+                },
+            },
+        );
+        builder.with_result(result);
+        builder.build()
+    }
+
+    #[test]
+    fn should_return_failed_and_timed_out_tests() {
+        let mut state = LastRunState::default();
+
+        // Act
+        state.record(&[
+            report("app::passes", ComponentResult::passed()),
+            report("app::fails", ComponentResult::rejected()),
+            report("app::times_out", ComponentResult::timed_out()),
+        ]);
+
+        // Assert
+        assert_eq!(
+            state.failed_paths(),
+            vec![
+                ComponentPath::from("app::fails"),
+                ComponentPath::from("app::times_out")
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_last_result_of_tests_which_did_not_run() {
+        let mut state = LastRunState::default();
+        state.record(&[
+            report("app::fixed", ComponentResult::rejected()),
+            report("app::broken", ComponentResult::rejected()),
+        ]);
+
+        // Act
+        state.record(&[
+            report("app::fixed", ComponentResult::passed()),
+            report("app::broken", ComponentResult::filtered()),
+        ]);
+
+        // Assert
+        assert_eq!(
+            state.failed_paths(),
+            vec![ComponentPath::from("app::broken")]
+        );
+    }
+
+    #[test]
+    fn should_load_saved_results() {
+        let path = std::env::temp_dir()
+            .join("integra8")
+            .join(format!("last_run_test-{}.json", std::process::id()));
+        let mut state = LastRunState::default();
+        state.record(&[report("app::fails", ComponentResult::rejected())]);
+
+        // Act
+        state.save_to(&path).unwrap();
+        let loaded = LastRunState::load_from(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        // Assert
+        assert_eq!(loaded, state);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod channel;
+pub mod last_run;
 
 use futures::join;
+use std::future::Future;
 use std::panic::UnwindSafe;
 
 use crate::strategy::{
@@ -9,14 +11,16 @@ use crate::strategy::{
     DefaultTestApplicationLocator, TestApplicationLocator,
 };
 
-use crate::results::summary::ComponentTypeCountSummary;
+use crate::results::summary::{ComponentTypeCountSummary, FlakinessSummary, RunSummary};
 use crate::results::ComponentResult;
 
-use crate::components::TestParameters;
+use crate::components::{Component, ComponentListing, TestParameters};
 use crate::decorations::ComponentDecoration;
 use crate::formatters::FormatterParameters;
 
 use crate::core::channel::{ResultsChannel, ResultsOutputWriterSink, RunProgressChannelNotify};
+use crate::core::last_run::LastRunState;
+use crate::runner::distributed::{run_worker, Coordinator};
 use crate::runner::DefaultResolveRunnerStrategy;
 use crate::scheduling::state_machine::TaskStream;
 use crate::scheduling::ScheduledComponent;
@@ -56,12 +60,19 @@ pub async fn run<
     auto_detect_components: Vec<ComponentDecoration<TParameters>>,
     parameters: TParameters,
 ) -> ComponentResult {
-    match parameters.child_process_target() {
-        Some(_) => {
-            run_child_process::<TParameters, Locator>(auto_detect_components, parameters).await
-        }
-        None => run_root_process::<TParameters, Locator>(auto_detect_components, parameters).await,
+    if parameters.child_process_target().is_some() {
+        return run_child_process::<TParameters, Locator>(auto_detect_components, parameters).await;
+    }
+
+    if let Some(address) = parameters.worker_address() {
+        return run_worker(&parameters, address).await;
+    }
+
+    if parameters.list_components() {
+        return list_components::<TParameters, Locator>(auto_detect_components, parameters);
     }
+
+    run_root_process::<TParameters, Locator>(auto_detect_components, parameters).await
 }
 
 pub async fn run_child_process<
@@ -86,6 +97,36 @@ pub async fn run_child_process<
         .result
 }
 
+/// Writes the components selected by the command line, without running any of them.
+pub fn list_components<
+    TParameters: TestParameters + Clone + Sync + Send + UnwindSafe + 'static + std::fmt::Debug,
+    Locator: TestApplicationLocator<TParameters> + Sync + Send + 'static,
+>(
+    auto_detect_components: Vec<ComponentDecoration<TParameters>>,
+    parameters: TParameters,
+) -> ComponentResult {
+    // 1: Resolve all decorations
+    let decorations = Locator::resolve_decorations_strategy(&parameters)
+        .resolve_decorations(&parameters, auto_detect_components);
+
+    // 2: Build component hierarchy
+    let root_component = Locator::resolve_component_hierarchy_strategy(&parameters)
+        .resolve_component_hierarchy(&parameters, decorations);
+
+    // 3: List the components, as they would be run
+    let root_suite = match root_component {
+        Component::Suite(suite) => suite,
+        _ => panic!("The root component of the test application must be a suite"),
+    };
+
+    Locator::resolve_formatter_strategy(&parameters)
+        .resolve_formatter(&parameters)
+        .write_component_list(&ComponentListing::from_suite(&root_suite))
+        .unwrap();
+
+    ComponentResult::passed()
+}
+
 pub async fn run_root_process<
     TParameters: TestParameters + Clone + Sync + Send + UnwindSafe + 'static + std::fmt::Debug,
     Locator: TestApplicationLocator<TParameters> + Sync + Send + 'static,
@@ -97,36 +138,207 @@ pub async fn run_root_process<
     let decorations = Locator::resolve_decorations_strategy(&parameters)
         .resolve_decorations(&parameters, auto_detect_components);
 
-    // 2: Count all the component types found in the decorations
-    let component_summary =
-        decorations
-            .iter()
-            .fold(ComponentTypeCountSummary::new(), |mut count, c| {
-                count.increment(&c.component_type());
-                count
-            });
+    // Let the user know why nothing is run, when rerunning the tests which failed last time and none did
+    let mut last_run = LastRunState::load();
+    if parameters.rerun_failed() && last_run.failed_paths().is_empty() {
+        Locator::resolve_formatter_strategy(&parameters)
+            .resolve_formatter(&parameters)
+            .write_run_notice(
+                "No failed tests were recorded by the last run, so there is nothing to rerun",
+            )
+            .unwrap();
+    }
 
-    // 3: Build component hierarchy
+    // 2: Check the dependencies between tests can be met, before any of them are run
+    let root_component = Locator::resolve_component_hierarchy_strategy(&parameters)
+        .resolve_component_hierarchy(&parameters, decorations.clone());
+    if let Component::Suite(root_suite) = &root_component {
+        if let Err(e) = root_suite.validate_dependencies() {
+            eprintln!("{}", e);
+            return ComponentResult::rejected();
+        }
+    }
+
+    // 3: Count all the component types found in the decorations.
+    // Before and after each hooks are run as part of each test, so are not counted
+    let component_summary = decorations
+        .iter()
+        .filter(|c| !c.is_each_hook())
+        .fold(ComponentTypeCountSummary::new(), |mut count, c| {
+            count.increment(&c.component_type());
+            count
+        });
+
+    // 4: Listen for workers, if components are run by remote workers
+    let coordinator = match parameters.coordinator_address() {
+        Some(address) => match Coordinator::start(address) {
+            Ok(coordinator) => Some(coordinator),
+            Err(e) => {
+                eprintln!("Failed to listen for workers on {}, {}", address, e);
+                return ComponentResult::rejected();
+            }
+        },
+        None => None,
+    };
+    Coordinator::set_current(coordinator.clone());
+
+    // 5: Run the schedule, repeating it if requested.
+    // Each run is given its own copy of the decorations, as they are consumed by the run
+    let repeat_until_failure = parameters.repeat_until_failure();
+    let max_runs = match (parameters.repeat(), repeat_until_failure) {
+        (Some(repeat), _) => repeat,
+        (None, true) => usize::MAX,
+        (None, false) => 1,
+    };
+    let is_repeated = max_runs > 1;
+
+    let runs = run_repeatedly(
+        max_runs,
+        repeat_until_failure,
+        || {
+            run_schedule::<TParameters, Locator>(
+                parameters.clone(),
+                decorations.clone(),
+                component_summary.clone(),
+                is_repeated,
+            )
+        },
+        |run_summary| last_run.record(run_summary.all_tests()),
+    )
+    .await;
+
+    // Disconnect the workers, so they exit once the run completes
+    if let Some(coordinator) = coordinator {
+        coordinator.shutdown();
+    }
+
+    // 6: Persist the results of the tests, so the tests which failed can be rerun
+    if let Err(e) = last_run.save() {
+        eprintln!(
+            "Failed to save the results of the run to {:?}, {}",
+            LastRunState::path(),
+            e
+        );
+    }
+
+    // 7: Write the summary of the last run, along with the results of each component across all the runs
+    let mut run_summary = runs.last_run;
+    if is_repeated {
+        run_summary.with_flakiness(runs.flakiness);
+        Locator::resolve_formatter_strategy(&parameters)
+            .resolve_formatter(&parameters)
+            .write_run_complete(&run_summary)
+            .unwrap();
+    }
+
+    runs.result
+}
+
+/// The results of running the schedule one or more times
+struct RepeatedRuns {
+    /// The summary of the last run
+    last_run: RunSummary,
+
+    /// The results of each component across all the runs
+    flakiness: FlakinessSummary,
+
+    /// The result of the first run which failed, or of the last run if none of them failed
+    result: ComponentResult,
+}
+
+/// Runs the schedule until it has been run `max_runs` times, or until a run fails if `repeat_until_failure` is set.
+///
+/// # Arguments
+///
+/// * `max_runs` - The most times the schedule is run.
+///
+/// * `repeat_until_failure` - When true, no more runs are made once a run fails.
+///
+/// * `run` - Runs the schedule once, and returns the summary of the run.
+///
+/// * `on_run_complete` - Called with the summary of each run, once it completes.
+///
+async fn run_repeatedly<Run, RunFuture, OnRunComplete>(
+    max_runs: usize,
+    repeat_until_failure: bool,
+    mut run: Run,
+    mut on_run_complete: OnRunComplete,
+) -> RepeatedRuns
+where
+    Run: FnMut() -> RunFuture,
+    RunFuture: Future<Output = RunSummary>,
+    OnRunComplete: FnMut(&RunSummary),
+{
+    let mut flakiness = FlakinessSummary::new();
+    let mut first_failure = None;
+    loop {
+        let run_summary = run().await;
+        on_run_complete(&run_summary);
+        flakiness.record(run_summary.all());
+
+        let run_result = run_summary.run_result();
+        let failed = run_result.has_failed();
+        if failed && first_failure.is_none() {
+            first_failure = Some(run_result);
+        }
+
+        if flakiness.runs() >= max_runs || (failed && repeat_until_failure) {
+            return RepeatedRuns {
+                // A repeated run fails if any of its runs failed
+                result: first_failure.unwrap_or_else(|| run_summary.run_result()),
+                last_run: run_summary,
+                flakiness,
+            };
+        }
+    }
+}
+
+/// Runs the schedule resolved from the given decorations, and returns the summary of the run.
+///
+/// # Arguments
+///
+/// * `parameters` - The parameter type as defined by the test author.
+///
+/// * `decorations` - The resolved component decorations of the run.
+///
+/// * `component_summary` - Count summary of all the components within the decorations.
+///
+/// * `is_repeated` - When true, the summary is not written once the run completes,
+///   as the summary of all the runs is written instead.
+///
+async fn run_schedule<
+    TParameters: TestParameters + Clone + Sync + Send + UnwindSafe + 'static + std::fmt::Debug,
+    Locator: TestApplicationLocator<TParameters> + Sync + Send + 'static,
+>(
+    parameters: TParameters,
+    decorations: Vec<ComponentDecoration<TParameters>>,
+    component_summary: ComponentTypeCountSummary,
+    is_repeated: bool,
+) -> RunSummary {
+    // 1: Build component hierarchy
     let root_component = Locator::resolve_component_hierarchy_strategy(&parameters)
         .resolve_component_hierarchy(&parameters, decorations);
 
-    // 4: Calculate component schedule
+    // 2: Calculate component schedule
     let schedule = Locator::resolve_component_schedule_strategy(&parameters)
         .resolve_schedule(&parameters, root_component);
 
-    // 5: Calculate the max needed concurrency based on the schedule, or limit to the
+    // 3: Calculate the max needed concurrency based on the schedule, or limit to the
     // max max concurrency parameter if smaller
     let max_concurrency = schedule.max_concurrency_or_limit(parameters.max_concurrency());
 
-    // 6: Setup results channels for publishing tests results
-    let (sender, receiver) = ResultsChannel::new(
-        ResultsOutputWriterSink::new(
-            Locator::resolve_formatter_strategy(&parameters).resolve_formatter(&parameters),
-        ),
-        max_concurrency,
-    );
+    // 4: Setup results channels for publishing tests results
+    let sink = ResultsOutputWriterSink::new(
+        Locator::resolve_formatter_strategy(&parameters).resolve_formatter(&parameters),
+    )
+    .shuffled_with(parameters.shuffle_seed());
+    let sink = match is_repeated {
+        true => sink.skip_run_complete(),
+        false => sink,
+    };
+    let (sender, receiver) = ResultsChannel::new(sink, max_concurrency);
 
-    // 7: Run Tests using schedule
+    // 5: Run Tests using schedule
     let runner_task = crate::async_runtime::spawn(async move {
         let mut runner = Locator::resolve_runner_strategy(&parameters);
         runner
@@ -139,8 +351,126 @@ pub async fn run_root_process<
             .await;
     });
 
-    // 8: Wait for results
+    // 6: Wait for results
     let receiver_task = receiver.start_listening();
     let (_, run_summary) = join!(runner_task, receiver_task);
-    run_summary.run_result()
+    run_summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{
+        AcceptanceCriteria, ComponentDescription, ComponentId, ComponentMetadata, ComponentPath,
+        ComponentTags, ComponentType, TimingAcceptanceCriteria,
+    };
+    use crate::decorations::test_rigging::block_on;
+    use crate::results::report::ComponentReportBuilder;
+
+    fn run_summary(result: ComponentResult) -> RunSummary {
+        let mut location = crate::src_loc!();
+        location.path = ComponentPath::from("app");
+
+        let mut builder = ComponentReportBuilder::new(
+            ComponentDescription::new(
+                None,
+                ComponentId::from(0),
+                ComponentId::from(0),
+                location.clone(),
+                location,
+                None,
+                ComponentTags::new(),
+                ComponentMetadata::new(),
+                ComponentType::Suite,
+            ),
+            AcceptanceCriteria {
+                allowed_fail: false,
+                timing: TimingAcceptanceCriteria {
+                    warning_time_limit: None,
+                    time_limit: None,
+                },
+            },
+        );
+        builder.with_result(result);
+
+        let mut summary = RunSummary::new();
+        summary.push_report(builder.build());
+        summary
+    }
+
+    fn run_with_results(
+        max_runs: usize,
+        repeat_until_failure: bool,
+        results: Vec<ComponentResult>,
+    ) -> (RepeatedRuns, usize) {
+        let mut results = results.into_iter();
+        let mut completed_runs = 0;
+        let runs = block_on(run_repeatedly(
+            max_runs,
+            repeat_until_failure,
+            || {
+                let result = results.next().expect("More runs were made than expected");
+                async move { run_summary(result) }
+            },
+            |_| completed_runs += 1,
+        ));
+        (runs, completed_runs)
+    }
+
+    #[test]
+    fn should_stop_once_max_runs_have_been_made() {
+        // Act
+        let (runs, completed_runs) = run_with_results(
+            3,
+            false,
+            vec![
+                ComponentResult::passed(),
+                ComponentResult::passed(),
+                ComponentResult::passed(),
+            ],
+        );
+
+        // Assert
+        assert_eq!(completed_runs, 3);
+        assert_eq!(runs.flakiness.runs(), 3);
+        assert!(runs.result.has_passed());
+    }
+
+    #[test]
+    fn should_stop_on_first_failure_when_repeating_until_failure() {
+        // Act
+        let (runs, completed_runs) = run_with_results(
+            usize::MAX,
+            true,
+            vec![
+                ComponentResult::passed(),
+                ComponentResult::passed(),
+                ComponentResult::rejected(),
+            ],
+        );
+
+        // Assert
+        assert_eq!(completed_runs, 3);
+        assert_eq!(runs.flakiness.runs(), 3);
+        assert!(runs.result.has_failed());
+    }
+
+    #[test]
+    fn should_fail_when_any_run_failed() {
+        // Act
+        let (runs, completed_runs) = run_with_results(
+            3,
+            false,
+            vec![
+                ComponentResult::passed(),
+                ComponentResult::rejected(),
+                ComponentResult::passed(),
+            ],
+        );
+
+        // Assert
+        assert_eq!(completed_runs, 3);
+        assert!(runs.last_run.run_result().has_passed());
+        assert!(runs.result.has_failed());
+    }
 }
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use crate::components::{
     BookEnd, ComponentDescription, ComponentId, ComponentLocation, ConcurrencyMode, Delegate,
-    SuiteAttributes, TestParameters,
+    RetryPolicy, SkipCondition, SuiteAttributes, TestParameters,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -13,12 +13,26 @@ pub struct BookEndAttributesDecoration {
     // A description of the bookend which can be displayed by the output formatter if it supports it
     pub description: Option<&'static str>,
 
+    /// The doc comments of the bookend, if it has any
+    pub doc: Option<&'static str>,
+
+    /// Tags used to select this bookend from the command line.
+    pub tags: &'static [&'static str],
+    pub metadata: &'static [(&'static str, &'static str)],
+
     /// The source code location of this bookend
     pub location: ComponentLocation,
 
     /// Indicates that bookend should not be run.
     pub ignore: Option<bool>,
 
+    /// Indicates that bookend should not be run, if the condition is met by the parameters.
+    pub skip_if: Option<SkipCondition>,
+
+    /// Indicates that bookend was excluded by the test selection filters.
+    /// This is determined by `ResolveDecorationStrategy` and not by the decoration itself.
+    pub filtered: Option<bool>,
+
     /// Describes the maximum duration a bookend can take before it is forcibly aborted
     pub time_limit: Option<Duration>,
 
@@ -26,6 +40,9 @@ pub struct BookEndAttributesDecoration {
     /// `ConcurrencyMode::Parallel` will allow this bookend for be run at the same time as other bookends within this suite
     /// `ConcurrencyMode::Sequential` will ensure that this bookend wont run at the same time as any other bookend from this suite
     pub concurrency_mode: Option<ConcurrencyMode>,
+
+    /// Describes how this bookend is re-run if it fails.
+    pub retry: Option<RetryPolicy>,
 }
 
 #[derive(Clone, Debug)]
@@ -47,10 +64,16 @@ impl<TParameters: TestParameters> BookEndDecoration<TParameters> {
             id,
             self.desc.name,
             self.desc.description,
+            self.desc.doc,
+            self.desc.tags,
+            self.desc.metadata,
             self.desc.location,
             self.desc.ignore,
+            self.desc.skip_if,
+            self.desc.filtered,
             self.desc.time_limit,
             self.desc.concurrency_mode,
+            self.desc.retry,
             self.bookend_fn,
         )
     }
@@ -67,10 +90,16 @@ impl<TParameters: TestParameters> BookEndDecoration<TParameters> {
             id,
             self.desc.name,
             self.desc.description,
+            self.desc.doc,
+            self.desc.tags,
+            self.desc.metadata,
             self.desc.location,
             self.desc.ignore,
+            self.desc.skip_if,
+            self.desc.filtered,
             self.desc.time_limit,
             self.desc.concurrency_mode,
+            self.desc.retry,
             self.bookend_fn,
         )
     }
@@ -0,0 +1,594 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::de::DeserializeOwned;
+
+use crate::components::{ComponentPath, ExecutionContext};
+use crate::decorations::dynamic::path_segment;
+use crate::decorations::filter::glob_match;
+use crate::decorations::{TestAttributesDecoration, TestDecoration};
+
+/// The files of each data source, keyed by their pattern, so they are only read once per process
+/// rather than once for each record.
+static LOADED: Mutex<BTreeMap<&'static str, Arc<Vec<DataFile>>>> = Mutex::new(BTreeMap::new());
+
+/// A set of data files, from which a test is run once for each record.
+///
+/// Files are located using a path relative to the current working directory, where the file name
+/// may contain a glob, for example `cases/*.yaml`. Files with a `.csv`, `.json`, `.yaml` or `.yml`
+/// extension are supported.
+///
+/// Json and Yaml files contain either a list of records, or a map of records keyed by their name.
+/// Records in a list are identified by their `name` field if they have one, otherwise by their index.
+/// Csv files contain a record per row. Rows are identified by their `name` column if there is one,
+/// otherwise by their index.
+#[derive(Clone, Debug)]
+pub struct DataSource {
+    pattern: &'static str,
+}
+
+impl DataSource {
+    pub fn new(pattern: &'static str) -> Self {
+        Self { pattern }
+    }
+
+    /// Returns all the files matching the data source pattern, in alphabetical order.
+    pub fn files(&self) -> Result<Vec<PathBuf>, String> {
+        let pattern = Path::new(self.pattern);
+        let file_pattern = match pattern.file_name() {
+            Some(file_pattern) => file_pattern.to_string_lossy(),
+            None => return Err(format!("{} is not a valid data source", self.pattern)),
+        };
+
+        let dir = match pattern.parent() {
+            Some(dir) if dir != Path::new("") => dir,
+            _ => Path::new("."),
+        };
+
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| format!("Unable to read data source {}, {}", self.pattern, e))?;
+
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter(|path| {
+                path.file_name().is_some_and(|name| {
+                    glob_match(file_pattern.as_bytes(), name.to_string_lossy().as_bytes())
+                })
+            })
+            .collect();
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Reads and parses each file matching the data source pattern.
+    /// Files are only read the first time the data source is loaded, and shared from then on.
+    fn load(&self) -> Result<Arc<Vec<DataFile>>, String> {
+        // The lock is held while the files are read, so tests sharing a data source don't each read it
+        let mut loaded = LOADED.lock().unwrap();
+        if let Some(files) = loaded.get(self.pattern) {
+            return Ok(files.clone());
+        }
+
+        let files = Arc::new(
+            self.files()?
+                .into_iter()
+                .map(|file| DataFile {
+                    segment: file_segment(&file),
+                    records: read_records(&file),
+                    file,
+                })
+                .collect::<Vec<_>>(),
+        );
+        loaded.insert(self.pattern, files.clone());
+        Ok(files)
+    }
+
+    /// Expands a test into a test per record found within the data source.
+    /// Each test is given a path made up of the test's path, followed by the file and record it is run with,
+    /// for example `my_app::my_test::cases.yaml#first`.
+    ///
+    /// Files which can not be read are expanded into a single test, which will fail with the read error when run.
+    ///
+    /// # Arguments
+    ///
+    /// * `test` - The test decorated with the data source.
+    ///
+    pub fn expand<TParameters>(
+        &self,
+        test: TestDecoration<TParameters>,
+    ) -> Vec<TestDecoration<TParameters>> {
+        let files = match self.load() {
+            Ok(files) if !files.is_empty() => files,
+            // Leave the test as is, it will fail with the same error when run
+            _ => return vec![test],
+        };
+
+        let mut expanded = vec![];
+        for data_file in files.iter() {
+            let segment = &data_file.segment;
+            match &data_file.records {
+                Ok(records) => {
+                    for record in records {
+                        let path = format!("{}#{}", segment, record.key);
+                        let label = format!("{}: {}", segment, record.key);
+                        expanded.push(record_test(&test, path, label));
+                    }
+                }
+                Err(_) => {
+                    expanded.push(record_test(&test, segment.clone(), segment.clone()));
+                }
+            }
+        }
+        expanded
+    }
+
+    /// Loads the record a test was expanded from, using the test's path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the test, as assigned by `DataSource::expand`.
+    ///
+    pub fn load_record<T: DeserializeOwned>(&self, path: &ComponentPath) -> Result<T, String> {
+        let files = self.load()?;
+        if files.is_empty() {
+            return Err(format!("No files matched the data source {}", self.pattern));
+        }
+
+        let path = path.as_str();
+        for data_file in files.iter() {
+            let segment = &data_file.segment;
+            let file = &data_file.file;
+
+            // The file could not be read when the test was expanded
+            if path.ends_with(&format!("::{}", segment)) {
+                return Err(data_file
+                    .records
+                    .as_ref()
+                    .err()
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        format!("{} has changed since it was loaded", file.display())
+                    }));
+            }
+
+            if let Some((_, key)) = path.rsplit_once(&format!("::{}#", segment)) {
+                let record = data_file
+                    .records
+                    .as_ref()
+                    .map_err(|e| e.clone())?
+                    .iter()
+                    .find(|r| r.key == key)
+                    .ok_or_else(|| format!("Record {} was not found in {}", key, file.display()))?;
+
+                return record.value.deserialize().map_err(|e| {
+                    format!(
+                        "Unable to deserialize record {} from {}, {}",
+                        key,
+                        file.display(),
+                        e
+                    )
+                });
+            }
+        }
+
+        Err(format!(
+            "No record matching {} was found in {}",
+            path, self.pattern
+        ))
+    }
+
+    /// Loads the record a test was expanded from.
+    ///
+    /// # Panics
+    ///
+    /// When the record could not be loaded. The error is included in the test's artifacts.
+    ///
+    pub fn record<T: DeserializeOwned, TParameters>(
+        &self,
+        ctx: &ExecutionContext<TParameters>,
+    ) -> T {
+        match self.load_record(ctx.description.path()) {
+            Ok(record) => record,
+            Err(err) => {
+                ctx.artifacts.include_text("data_source", err.clone());
+                panic!("{}", err)
+            }
+        }
+    }
+}
+
+fn record_test<TParameters>(
+    test: &TestDecoration<TParameters>,
+    path: String,
+    label: String,
+) -> TestDecoration<TParameters> {
+    let mut location = test.desc.location.clone();
+    location.path = ComponentPath::from_string(format!("{}::{}", location.path, path));
+
+    TestDecoration {
+        desc: TestAttributesDecoration {
+            // Names are expected to be static, however
+            // records are only known once the data source is loaded
+            name: Some(Box::leak(
+                format!("{} ({})", test_name(test), label).into_boxed_str(),
+            )),
+            description: test.desc.description,
+            doc: test.desc.doc,
+            tags: test.desc.tags,
+            metadata: test.desc.metadata,
+            location,
+            allow_fail: test.desc.allow_fail,
+            ignore: test.desc.ignore,
+            skip_if: test.desc.skip_if,
+            filtered: test.desc.filtered,
+            data_source: test.desc.data_source,
+            warning_time_limit: test.desc.warning_time_limit,
+            time_limit: test.desc.time_limit,
+            concurrency_mode: test.desc.concurrency_mode.clone(),
+            retry: test.desc.retry.clone(),
+            eventually: test.desc.eventually.clone(),
+            depends_on: test.desc.depends_on,
+        },
+        test_fn: test.test_fn.clone(),
+    }
+}
+
+/// Returns the `#[name = "..."]` of the test, or the name of the test function if it has none.
+fn test_name<TParameters>(test: &TestDecoration<TParameters>) -> &'_ str {
+    match test.desc.name {
+        Some(name) => name,
+        None => test
+            .desc
+            .location
+            .path
+            .as_str()
+            .rsplit("::")
+            .next()
+            .unwrap_or_default(),
+    }
+}
+
+/// Returns the path segment used to identify a file, being its file name with any characters
+/// which are not allowed in a path segment replaced.
+fn file_segment(file: &Path) -> String {
+    let name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path_segment(&name)
+}
+
+struct DataFile {
+    file: PathBuf,
+    segment: String,
+    records: Result<Vec<DataRecord>, String>,
+}
+
+struct DataRecord {
+    key: String,
+    value: DataRecordValue,
+}
+
+enum DataRecordValue {
+    Value(serde_json::Value),
+    Csv(csv::StringRecord, csv::StringRecord),
+}
+
+impl DataRecordValue {
+    fn deserialize<T: DeserializeOwned>(&self) -> Result<T, String> {
+        match self {
+            Self::Value(value) => T::deserialize(value).map_err(|e| e.to_string()),
+            Self::Csv(headers, record) => record
+                .deserialize(Some(headers))
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+fn read_records(file: &Path) -> Result<Vec<DataRecord>, String> {
+    let extension = file
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    let open = || {
+        File::open(file)
+            .map(BufReader::new)
+            .map_err(|e| format!("Unable to open {}, {}", file.display(), e))
+    };
+
+    let parse_error = |e: String| format!("Unable to parse {}, {}", file.display(), e);
+
+    let records = match extension.as_str() {
+        "json" => {
+            let value: serde_json::Value =
+                serde_json::from_reader(open()?).map_err(|e| parse_error(e.to_string()))?;
+            value_records(value).map_err(parse_error)?
+        }
+        "yaml" | "yml" => {
+            let value: serde_json::Value =
+                serde_yaml::from_reader(open()?).map_err(|e| parse_error(e.to_string()))?;
+            value_records(value).map_err(parse_error)?
+        }
+        "csv" => csv_records(open()?).map_err(parse_error)?,
+        _ => {
+            return Err(format!(
+                "{} is not a supported data source, expected a .csv, .json, .yaml or .yml file",
+                file.display()
+            ))
+        }
+    };
+
+    Ok(unique_keys(records))
+}
+
+fn value_records(value: serde_json::Value) -> Result<Vec<DataRecord>, String> {
+    match value {
+        serde_json::Value::Array(values) => Ok(values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| DataRecord {
+                key: match value.get("name").and_then(|name| name.as_str()) {
+                    Some(name) => path_segment(name),
+                    None => i.to_string(),
+                },
+                value: DataRecordValue::Value(value),
+            })
+            .collect()),
+        serde_json::Value::Object(values) => Ok(values
+            .into_iter()
+            .map(|(name, value)| DataRecord {
+                key: path_segment(&name),
+                value: DataRecordValue::Value(value),
+            })
+            .collect()),
+        _ => Err("expected a list or a map of records".to_string()),
+    }
+}
+
+fn csv_records(reader: BufReader<File>) -> Result<Vec<DataRecord>, String> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let name_column = headers.iter().position(|h| h == "name");
+
+    reader
+        .records()
+        .enumerate()
+        .map(|(i, record)| {
+            let record = record.map_err(|e| e.to_string())?;
+            Ok(DataRecord {
+                key: match name_column.and_then(|c| record.get(c)) {
+                    Some(name) => path_segment(name),
+                    None => i.to_string(),
+                },
+                value: DataRecordValue::Csv(headers.clone(), record),
+            })
+        })
+        .collect()
+}
+
+/// Records with the same name are identified by their index instead
+fn unique_keys(mut records: Vec<DataRecord>) -> Vec<DataRecord> {
+    let mut keys: Vec<String> = vec![];
+    for (i, record) in records.iter_mut().enumerate() {
+        if record.key.is_empty() || keys.contains(&record.key) {
+            record.key = i.to_string();
+        }
+        keys.push(record.key.clone());
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Vector {
+        input: u32,
+        expected: String,
+    }
+
+    /// A directory of data files used by a single test, which is removed once the test completes
+    struct DataDir(PathBuf);
+
+    impl DataDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "integra8_data_source_{}_{}",
+                name,
+                std::process::id()
+            ));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl std::ops::Deref for DataDir {
+        type Target = Path;
+
+        fn deref(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for DataDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn data_source(dir: &Path, file_pattern: &str) -> DataSource {
+        let pattern = dir.join(file_pattern).to_string_lossy().to_string();
+        DataSource::new(Box::leak(pattern.into_boxed_str()))
+    }
+
+    fn test_decoration(name: Option<&'static str>) -> TestDecoration<()> {
+        TestDecoration::<()> {
+            desc: TestAttributesDecoration {
+                name,
+                description: None,
+                doc: None,
+                tags: &[],
+                metadata: &[],
+                location: crate::src_loc!(),
+                allow_fail: None,
+                ignore: None,
+                skip_if: None,
+                filtered: None,
+                data_source: None,
+                warning_time_limit: None,
+                time_limit: None,
+                concurrency_mode: None,
+                retry: None,
+                eventually: None,
+                depends_on: &[],
+            },
+            test_fn: crate::components::Delegate::sync_without_context(|| Ok(())),
+        }
+    }
+
+    fn record_path(file_name: &str, key: &str) -> ComponentPath {
+        ComponentPath::from_string(format!("app::test_a::{}#{}", file_name, key))
+    }
+
+    #[test]
+    fn should_load_records_from_yaml_list() {
+        let dir = DataDir::new("yaml");
+        std::fs::write(
+            dir.join("vectors.yaml"),
+            "- name: first\n  input: 1\n  expected: one\n- input: 2\n  expected: two\n",
+        )
+        .unwrap();
+
+        let source = data_source(&dir, "*.yaml");
+
+        // Assert
+        let first: Vector = source
+            .load_record(&record_path("vectors.yaml", "first"))
+            .unwrap();
+        assert_eq!(first.expected, "one");
+
+        let second: Vector = source
+            .load_record(&record_path("vectors.yaml", "1"))
+            .unwrap();
+        assert_eq!(second.input, 2);
+    }
+
+    #[test]
+    fn should_load_records_from_json_map() {
+        let dir = DataDir::new("json");
+        std::fs::write(
+            dir.join("vectors.json"),
+            r#"{ "a": { "input": 1, "expected": "one" } }"#,
+        )
+        .unwrap();
+
+        let source = data_source(&dir, "vectors.json");
+
+        // Assert
+        let record: Vector = source
+            .load_record(&record_path("vectors.json", "a"))
+            .unwrap();
+        assert_eq!(
+            record,
+            Vector {
+                input: 1,
+                expected: "one".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn should_load_records_from_csv() {
+        let dir = DataDir::new("csv");
+        std::fs::write(dir.join("vectors.csv"), "input,expected\n1,one\n2,two\n").unwrap();
+
+        let source = data_source(&dir, "*.csv");
+
+        // Assert
+        let record: Vector = source
+            .load_record(&record_path("vectors.csv", "1"))
+            .unwrap();
+        assert_eq!(record.expected, "two");
+    }
+
+    #[test]
+    fn should_expand_test_per_record() {
+        let dir = DataDir::new("expand");
+        std::fs::write(dir.join("a.csv"), "name,input,expected\nfirst,1,one\n").unwrap();
+        std::fs::write(dir.join("b.json"), "[ { ").unwrap();
+
+        let test = test_decoration(Some("Test A"));
+
+        // Act
+        let tests = data_source(&dir, "*").expand(test);
+
+        // Assert
+        assert_eq!(tests.len(), 2);
+        assert!(tests[0]
+            .desc
+            .location
+            .path
+            .as_str()
+            .ends_with("::a.csv#first"));
+        assert_eq!(tests[0].desc.name, Some("Test A (a.csv: first)"));
+
+        // Files which can not be parsed are still expanded, so they can fail when run
+        assert!(tests[1].desc.location.path.as_str().ends_with("::b.json"));
+    }
+
+    #[test]
+    fn should_name_records_after_test_function_when_test_has_no_name() {
+        let dir = DataDir::new("unnamed");
+        std::fs::write(dir.join("a.csv"), "name,input,expected\nfirst,1,one\n").unwrap();
+
+        // Act
+        let tests = data_source(&dir, "*").expand(test_decoration(None));
+
+        // Assert
+        assert_eq!(tests[0].desc.name, Some("tests (a.csv: first)"));
+    }
+
+    #[test]
+    fn should_read_files_once_when_loading_each_record() {
+        let dir = DataDir::new("shared");
+        std::fs::write(dir.join("vectors.csv"), "input,expected\n1,one\n2,two\n").unwrap();
+
+        let source = data_source(&dir, "*.csv");
+        let first: Vector = source
+            .load_record(&record_path("vectors.csv", "0"))
+            .unwrap();
+
+        // Act
+        std::fs::remove_file(dir.join("vectors.csv")).unwrap();
+        let second: Vector = source
+            .load_record(&record_path("vectors.csv", "1"))
+            .unwrap();
+
+        // Assert
+        assert_eq!(first.expected, "one");
+        assert_eq!(second.expected, "two");
+    }
+
+    #[test]
+    fn should_return_parse_error_for_invalid_file() {
+        let dir = DataDir::new("invalid");
+        std::fs::write(dir.join("vectors.json"), "[ { ").unwrap();
+
+        let source = data_source(&dir, "*.json");
+        let result: Result<Vector, String> =
+            source.load_record(&ComponentPath::from("app::test_a::vectors.json"));
+
+        // Assert
+        assert!(result.unwrap_err().starts_with("Unable to parse"));
+    }
+}
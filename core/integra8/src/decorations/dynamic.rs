@@ -0,0 +1,390 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::components::delegates::IntoDelegateResult;
+use crate::components::{
+    ComponentLocation, ComponentPath, ConcurrencyMode, Delegate, EventuallyPolicy,
+    ExecutionContext, RetryPolicy,
+};
+use crate::decorations::{ComponentDecoration, TestAttributesDecoration, TestDecoration};
+
+/// A test created at runtime, such as one test for each service listed in a discovery file.
+///
+/// Dynamic tests are returned by a `#[test_factory]`, or registered by a `ResolveDecorationStrategy`
+/// using `DynamicTest::into_decoration`. Each test's path is made up of its parent's path followed by its name.
+/// Tests run in a child process are found again by their path, so the same tests must be produced each time.
+#[derive(Debug)]
+pub struct DynamicTest<TParameters> {
+    name: String,
+    description: Option<String>,
+    tags: Vec<String>,
+    metadata: Vec<(String, String)>,
+    allow_fail: Option<bool>,
+    ignore: Option<bool>,
+    warning_time_limit: Option<Duration>,
+    time_limit: Option<Duration>,
+    concurrency_mode: Option<ConcurrencyMode>,
+    retry: Option<RetryPolicy>,
+    eventually: Option<EventuallyPolicy>,
+    test_fn: Delegate<TParameters>,
+}
+
+impl<TParameters: 'static> DynamicTest<TParameters> {
+    /// Creates a test which runs the given closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test, which must be unique amongst the tests produced alongside it.
+    ///
+    /// * `test_fn` - The closure run by the test. It may return `()`, or a `Result<(), E>`.
+    ///
+    pub fn new<F, R>(name: impl Into<String>, test_fn: F) -> Self
+    where
+        F: Fn(ExecutionContext<TParameters>) -> R + Send + Sync + 'static,
+        R: IntoDelegateResult,
+    {
+        Self::from_delegate(name, Delegate::sync_closure(test_fn))
+    }
+
+    /// Creates a test which awaits the future returned by the given closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test, which must be unique amongst the tests produced alongside it.
+    ///
+    /// * `test_fn` - The closure run by the test. Its future may output `()`, or a `Result<(), E>`.
+    ///
+    pub fn new_async<F, Fut, R>(name: impl Into<String>, test_fn: F) -> Self
+    where
+        F: Fn(ExecutionContext<TParameters>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        R: IntoDelegateResult,
+    {
+        Self::from_delegate(name, Delegate::async_closure(test_fn))
+    }
+}
+
+impl<TParameters> DynamicTest<TParameters> {
+    /// Creates a test which runs the given delegate.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test, which must be unique amongst the tests produced alongside it.
+    ///
+    /// * `test_fn` - The delegate run by the test.
+    ///
+    pub fn from_delegate(name: impl Into<String>, test_fn: Delegate<TParameters>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            tags: Vec::new(),
+            metadata: Vec::new(),
+            allow_fail: None,
+            ignore: None,
+            warning_time_limit: None,
+            time_limit: None,
+            concurrency_mode: None,
+            retry: None,
+            eventually: None,
+            test_fn,
+        }
+    }
+
+    pub fn name(&self) -> &'_ str {
+        &self.name
+    }
+
+    /// The segment of the path which identifies this test beneath its parent,
+    /// being its name with any characters which are not allowed in a path segment replaced.
+    pub fn path_segment(&self) -> String {
+        path_segment(&self.name)
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    pub fn meta(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn allow_fail(mut self) -> Self {
+        self.allow_fail = Some(true);
+        self
+    }
+
+    pub fn ignore(mut self) -> Self {
+        self.ignore = Some(true);
+        self
+    }
+
+    pub fn warning_time_limit(mut self, warning_time_limit: Duration) -> Self {
+        self.warning_time_limit = Some(warning_time_limit);
+        self
+    }
+
+    pub fn time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = Some(time_limit);
+        self
+    }
+
+    pub fn parallel(mut self) -> Self {
+        self.concurrency_mode = Some(ConcurrencyMode::Parallel);
+        self
+    }
+
+    pub fn sequential(mut self) -> Self {
+        self.concurrency_mode = Some(ConcurrencyMode::Sequential);
+        self
+    }
+
+    pub fn retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    pub fn eventually(mut self, eventually: EventuallyPolicy) -> Self {
+        self.eventually = Some(eventually);
+        self
+    }
+
+    /// Converts the test into a decoration, so it can be registered alongside the automatically detected components.
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - The location of the factory or strategy which produced the test. The test's path is nested beneath the location's path.
+    ///
+    pub fn into_decoration(self, parent: ComponentLocation) -> ComponentDecoration<TParameters> {
+        let segment = self.path_segment();
+        self.into_decoration_at(parent, &segment)
+    }
+
+    fn into_decoration_at(
+        self,
+        parent: ComponentLocation,
+        segment: &str,
+    ) -> ComponentDecoration<TParameters> {
+        let path = format!("{}::{}", parent.path, segment);
+        let mut location = parent;
+        location.path = ComponentPath::from_string(path);
+
+        ComponentDecoration::IntegrationTest(TestDecoration {
+            desc: TestAttributesDecoration {
+                // Names are expected to be static, however
+                // dynamic tests are only known at runtime
+                name: Some(leak(self.name)),
+                description: self.description.map(leak),
+                doc: None,
+                tags: Box::leak(
+                    self.tags
+                        .into_iter()
+                        .map(leak)
+                        .collect::<Vec<_>>()
+                        .into_boxed_slice(),
+                ),
+                metadata: Box::leak(
+                    self.metadata
+                        .into_iter()
+                        .map(|(key, value)| (leak(key), leak(value)))
+                        .collect::<Vec<_>>()
+                        .into_boxed_slice(),
+                ),
+                location,
+                allow_fail: self.allow_fail,
+                ignore: self.ignore,
+                skip_if: None,
+                filtered: None,
+                data_source: None,
+                warning_time_limit: self.warning_time_limit,
+                time_limit: self.time_limit,
+                concurrency_mode: self.concurrency_mode,
+                retry: self.retry,
+                eventually: self.eventually,
+                depends_on: &[],
+            },
+            test_fn: self.test_fn,
+        })
+    }
+}
+
+/// A function which produces tests at runtime, as declared by `#[test_factory]`.
+/// Factories are expanded into their tests by `ResolveDecorationStrategy`.
+#[derive(Clone, Debug)]
+pub struct TestFactoryDecoration<TParameters> {
+    /// The source code location of the factory. The factory's tests are nested beneath its path.
+    pub location: ComponentLocation,
+    pub factory_fn: fn(&TParameters) -> Vec<DynamicTest<TParameters>>,
+}
+
+impl<TParameters> TestFactoryDecoration<TParameters> {
+    /// Returns the decorations of the tests produced by the factory.
+    /// Tests whose names can not be told apart by their path are identified by their index instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The parameter type as defined by the test author.
+    ///
+    pub fn expand(self, parameters: &TParameters) -> Vec<ComponentDecoration<TParameters>> {
+        let mut segments: Vec<String> = vec![];
+        (self.factory_fn)(parameters)
+            .into_iter()
+            .enumerate()
+            .map(|(i, test)| {
+                let mut segment = test.path_segment();
+                if segment.is_empty() || segments.contains(&segment) {
+                    segment = i.to_string();
+                }
+                segments.push(segment.clone());
+                test.into_decoration_at(self.location.clone(), &segment)
+            })
+            .collect()
+    }
+}
+
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
+
+pub(crate) fn path_segment(s: &str) -> String {
+    s.chars()
+        .map(
+            |c| match c.is_alphanumeric() || c == '_' || c == '-' || c == '.' {
+                true => c,
+                false => '_',
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::components::{
+        CancellationToken, ComponentDescription, ComponentId, ComponentMetadata, ComponentTags, ComponentType,
+        ExecutionArtifacts, SuiteState,
+    };
+
+    fn services(_parameters: &()) -> Vec<DynamicTest<()>> {
+        vec!["payments", "ledger api", "payments"]
+            .into_iter()
+            .map(|service| {
+                DynamicTest::new(format!("{} is healthy", service), |_| {})
+                    .tag("smoke")
+                    .meta("service", service)
+                    .time_limit(Duration::from_secs(5))
+            })
+            .collect()
+    }
+
+    fn factory() -> TestFactoryDecoration<()> {
+        let mut location = crate::src_loc!();
+        location.path = ComponentPath::from("app::smoke::services");
+        TestFactoryDecoration {
+            location,
+            factory_fn: services,
+        }
+    }
+
+    fn context(test: &TestDecoration<()>) -> ExecutionContext<()> {
+        ExecutionContext {
+            parameters: Arc::new(()),
+            description: ComponentDescription::new(
+                test.desc.name,
+                ComponentId::from(1),
+                ComponentId::from(0),
+                test.desc.location.clone(),
+                crate::src_loc!(),
+                test.desc.description,
+                ComponentTags::from(test.desc.tags),
+                ComponentMetadata::from(test.desc.metadata),
+                ComponentType::Test,
+            ),
+            artifacts: Arc::new(ExecutionArtifacts::new()),
+            state: SuiteState::new(),
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    fn into_test(decoration: ComponentDecoration<()>) -> TestDecoration<()> {
+        match decoration {
+            ComponentDecoration::IntegrationTest(test) => test,
+            _ => panic!("expected a test"),
+        }
+    }
+
+    #[test]
+    fn should_nest_tests_beneath_factory() {
+        // Act
+        let tests: Vec<_> = factory().expand(&()).into_iter().map(into_test).collect();
+
+        // Assert
+        assert_eq!(tests.len(), 3);
+        assert_eq!(
+            tests[0].desc.location.path.as_str(),
+            "app::smoke::services::payments_is_healthy"
+        );
+        assert_eq!(tests[0].desc.name, Some("payments is healthy"));
+        assert_eq!(tests[0].desc.tags, &["smoke"]);
+        assert_eq!(tests[0].desc.metadata, &[("service", "payments")]);
+        assert_eq!(tests[0].desc.time_limit, Some(Duration::from_secs(5)));
+        assert_eq!(
+            tests[1].desc.location.path.as_str(),
+            "app::smoke::services::ledger_api_is_healthy"
+        );
+    }
+
+    #[test]
+    fn should_identify_tests_with_same_name_by_index() {
+        // Act
+        let tests = factory().expand(&());
+
+        // Assert
+        assert_eq!(tests[2].location().path.as_str(), "app::smoke::services::2");
+        assert_eq!(tests[2].name(), Some("payments is healthy"));
+    }
+
+    #[test]
+    fn should_run_closure_with_captured_state() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let test_calls = calls.clone();
+        let test = into_test(
+            DynamicTest::new("counts calls", move |_| {
+                test_calls.fetch_add(1, Ordering::SeqCst);
+            })
+            .into_decoration(crate::src_loc!()),
+        );
+
+        // Act
+        let result = test.test_fn.run_sync(context(&test));
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn should_reject_async_closure_err() {
+        let test = into_test(
+            DynamicTest::new_async("fails", |ctx: ExecutionContext<()>| async move {
+                Err::<(), _>(format!("{} failed", ctx.description.friendly_name()))
+            })
+            .into_decoration(crate::src_loc!()),
+        );
+
+        // Act
+        let result = futures::executor::block_on(test.test_fn.run_async(context(&test)));
+
+        // Assert
+        assert_eq!(result.unwrap_err().to_string(), "fails failed");
+    }
+}
@@ -0,0 +1,377 @@
+use crate::components::{
+    ComponentPath, ComponentTags, Shard, ShardMode, TagExpression, TestParameters,
+};
+use crate::decorations::ComponentDecoration;
+
+/// A pattern used to select components by their path.
+///
+/// Patterns containing a `*` or `?` are treated as a glob, where `*` matches any sequence of characters
+/// and `?` matches any single character. A glob matches a component if it matches its path, or the path of
+/// any of its parent namespaces.
+///
+/// All other patterns match components whose path is equal to the pattern, or is nested beneath it.
+/// For example, `app::suite_a` matches `app::suite_a::test_a`, but not `app::suite_ab`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentPathPattern(String);
+
+impl ComponentPathPattern {
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self(pattern.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_glob(&self) -> bool {
+        self.0.contains(['*', '?'])
+    }
+
+    pub fn is_match(&self, path: &ComponentPath) -> bool {
+        if !self.is_glob() {
+            return path.is_within(&ComponentPath::from_string(self.0.clone()));
+        }
+
+        // A glob which matches a parent namespace, matches all of its children
+        let path = path.as_str();
+        path.match_indices("::")
+            .map(|(i, _)| &path[..i])
+            .chain(std::iter::once(path))
+            .any(|p| glob_match(self.0.as_bytes(), p.as_bytes()))
+    }
+}
+
+pub(crate) fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // The position of the last `*` seen in the pattern, and
+    // the position in the text it was matched against
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == b'?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` consume one more character and try again
+                Some((star_p, star_t)) => {
+                    backtrack = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+/// Command line test selection, used to determine which components are excluded from a run.
+#[derive(Clone, Debug)]
+pub struct ComponentFilter {
+    filters: Vec<ComponentPathPattern>,
+    skips: Vec<ComponentPathPattern>,
+    tags: Option<TagExpression>,
+    rerun: Option<Vec<ComponentPath>>,
+    shard: Option<Shard>,
+}
+
+impl ComponentFilter {
+    pub fn new(
+        filters: Vec<ComponentPathPattern>,
+        skips: Vec<ComponentPathPattern>,
+        tags: Option<TagExpression>,
+    ) -> Self {
+        Self {
+            filters,
+            skips,
+            tags,
+            rerun: None,
+            shard: None,
+        }
+    }
+
+    /// Restricts the selection to the given tests, such as the tests which failed in the previous run.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - The paths of the tests which may be selected.
+    ///
+    pub fn rerun_only(mut self, paths: Vec<ComponentPath>) -> Self {
+        self.rerun = Some(paths);
+        self
+    }
+
+    /// Restricts the selection to the tests assigned to the given shard.
+    ///
+    /// # Arguments
+    ///
+    /// * `shard` - The shard of the run to select tests for.
+    ///
+    pub fn sharded(mut self, shard: Shard) -> Self {
+        self.shard = Some(shard);
+        self
+    }
+
+    pub fn from_parameters<TParameters: TestParameters>(parameters: &TParameters) -> Self {
+        let filter = Self::new(
+            parameters
+                .filter_patterns()
+                .iter()
+                .map(ComponentPathPattern::new)
+                .collect(),
+            parameters
+                .skip_patterns()
+                .iter()
+                .map(ComponentPathPattern::new)
+                .collect(),
+            parameters.tag_expression().cloned(),
+        );
+
+        match parameters.shard() {
+            Some(shard) => filter.sharded(shard),
+            None => filter,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+            && self.skips.is_empty()
+            && self.tags.is_none()
+            && self.rerun.is_none()
+            && self.shard.is_none()
+    }
+
+    /// Returns true if the component is selected by at least one filter (or there are no filters),
+    /// is not matched by any skip, its tags satisfy the tag expression (if there is one)
+    /// and it is one of the tests to rerun (if there are any).
+    pub fn is_selected(&self, path: &ComponentPath, tags: &ComponentTags) -> bool {
+        let included = self.filters.is_empty() || self.filters.iter().any(|f| f.is_match(path));
+        let tagged = self.tags.as_ref().is_none_or(|expr| expr.is_match(tags));
+        let rerun = self
+            .rerun
+            .as_ref()
+            .is_none_or(|paths| paths.iter().any(|p| path.is_within(p)));
+        included && tagged && rerun && !self.skips.iter().any(|s| s.is_match(path))
+    }
+
+    /// Flags each decoration which is excluded from the run.
+    ///
+    /// Tests are excluded if they are not selected, or are assigned to another shard. A test's tags include the tags of the suites it belongs to.
+    /// Suites are excluded if none of their tests (including those of nested suites) are selected.
+    /// Setups, tear downs and before/after each hooks are excluded if none of the tests of the suite they belong to are selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `decorations` - The list of all component decorations to be filtered.
+    ///
+    pub fn apply<TParameters>(
+        &self,
+        mut decorations: Vec<ComponentDecoration<TParameters>>,
+    ) -> Vec<ComponentDecoration<TParameters>> {
+        if self.is_empty() {
+            return decorations;
+        }
+
+        let suites: Vec<(ComponentPath, &'static [&'static str])> = decorations
+            .iter()
+            .filter_map(|d| match d {
+                ComponentDecoration::Suite(suite) => {
+                    Some((suite.location.path.clone(), suite.tags))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for decoration in decorations.iter_mut() {
+            if let ComponentDecoration::IntegrationTest(test) = decoration {
+                let tags = inherited_tags(&suites, &test.desc.location.path, test.desc.tags);
+                let path = &test.desc.location.path;
+                let in_shard = self.shard.as_ref().is_none_or(|shard| {
+                    shard.includes(match shard.mode() {
+                        ShardMode::Suite => outer_most_suite(&suites, path).unwrap_or(path),
+                        ShardMode::Test => path,
+                    })
+                });
+                test.desc.filtered = Some(!(self.is_selected(path, &tags) && in_shard));
+            }
+        }
+
+        let selected_tests: Vec<ComponentPath> = decorations
+            .iter()
+            .filter_map(|d| match d {
+                ComponentDecoration::IntegrationTest(test) if test.desc.filtered == Some(false) => {
+                    Some(test.desc.location.path.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        let has_selected_tests = |scope: Option<&ComponentPath>| match scope {
+            Some(scope) => selected_tests.iter().any(|t| is_within(t, scope)),
+            // Components which are not a part of any suite, belong to the root suite
+            None => !selected_tests.is_empty(),
+        };
+
+        for decoration in decorations.iter_mut() {
+            match decoration {
+                ComponentDecoration::IntegrationTest(_) | ComponentDecoration::TestFactory(_) => {}
+                ComponentDecoration::Suite(suite) => {
+                    suite.filtered = Some(!has_selected_tests(Some(&suite.location.path)));
+                }
+                ComponentDecoration::Setup(bookend)
+                | ComponentDecoration::TearDown(bookend)
+                | ComponentDecoration::BeforeEach(bookend)
+                | ComponentDecoration::AfterEach(bookend) => {
+                    let suite = owning_suite(&suites, &bookend.desc.location.path);
+                    bookend.desc.filtered = Some(!has_selected_tests(suite));
+                }
+            }
+        }
+
+        decorations
+    }
+}
+
+/// Returns true if `path` is the same as, or is nested within `scope`
+fn is_within(path: &ComponentPath, scope: &ComponentPath) -> bool {
+    let (path, scope) = (path.as_str(), scope.as_str());
+    path == scope || (path.starts_with(scope) && path[scope.len()..].starts_with("::"))
+}
+
+/// Returns the inner most suite which the component at `path` belongs to,
+/// or none if the component belongs to the root suite.
+fn owning_suite<'a>(
+    suites: &'a [(ComponentPath, &'static [&'static str])],
+    path: &ComponentPath,
+) -> Option<&'a ComponentPath> {
+    suites
+        .iter()
+        .map(|(s, _)| s)
+        .filter(|s| is_within(path, s) && *s != path)
+        .max_by_key(|s| s.as_str().len())
+}
+
+/// Returns the outer most suite which the component at `path` belongs to,
+/// or none if the component belongs to the root suite.
+fn outer_most_suite<'a>(
+    suites: &'a [(ComponentPath, &'static [&'static str])],
+    path: &ComponentPath,
+) -> Option<&'a ComponentPath> {
+    suites
+        .iter()
+        .map(|(s, _)| s)
+        .filter(|s| is_within(path, s) && *s != path)
+        .min_by_key(|s| s.as_str().len())
+}
+
+/// Returns the tags of the component at `path`, including the tags of all the suites it belongs to.
+fn inherited_tags(
+    suites: &[(ComponentPath, &'static [&'static str])],
+    path: &ComponentPath,
+    tags: &'static [&'static str],
+) -> ComponentTags {
+    let mut parents: Vec<&(ComponentPath, &'static [&'static str])> = suites
+        .iter()
+        .filter(|(s, _)| is_within(path, s) && s != path)
+        .collect();
+
+    // Outer most suites first
+    parents.sort_by_key(|(s, _)| s.as_str().len());
+    parents
+        .into_iter()
+        .fold(ComponentTags::new(), |acc, (_, t)| acc.inherit(t))
+        .inherit(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_match(pattern: &'static str, path: &'static str) -> bool {
+        ComponentPathPattern::new(pattern).is_match(&ComponentPath::from(path))
+    }
+
+    #[test]
+    fn should_match_exact_path() {
+        assert!(is_match("app::suite_a::test_a", "app::suite_a::test_a"));
+        assert!(!is_match("app::suite_a::test_a", "app::suite_a::test_b"));
+    }
+
+    #[test]
+    fn should_match_path_prefix() {
+        assert!(is_match("app::suite_a", "app::suite_a::test_a"));
+        assert!(is_match("app::suite_a::test_a", "app::suite_a::test_a::case_1"));
+        assert!(!is_match("app::suite_b", "app::suite_a::test_a"));
+    }
+
+    #[test]
+    fn should_only_match_path_prefix_on_namespace_boundary() {
+        assert!(!is_match("app::suite_a::test", "app::suite_a::test_a"));
+        assert!(!is_match("app::suite_a::test_a", "app::suite_a::test_ab"));
+    }
+
+    #[test]
+    fn should_match_glob() {
+        assert!(is_match("*::test_a", "app::suite_a::test_a"));
+        assert!(is_match("app::*_a::test_?", "app::suite_a::test_a"));
+        assert!(!is_match("*::test_b", "app::suite_a::test_a"));
+        assert!(!is_match("app::suite_?", "app::suite_ab"));
+    }
+
+    #[test]
+    fn should_match_glob_against_parent_namespace() {
+        assert!(is_match("*::suite_a", "app::suite_a::test_a"));
+        assert!(is_match("app::*", "app::suite_a::test_a"));
+        assert!(!is_match("*::suite_b", "app::suite_a::test_a"));
+    }
+
+    #[test]
+    fn should_select_when_not_skipped() {
+        let filter = ComponentFilter::new(
+            vec![ComponentPathPattern::new("app::suite_a")],
+            vec![ComponentPathPattern::new("*::slow_*")],
+            None,
+        );
+
+        let tags = ComponentTags::new();
+        assert!(filter.is_selected(&ComponentPath::from("app::suite_a::test_a"), &tags));
+        assert!(!filter.is_selected(&ComponentPath::from("app::suite_a::slow_test"), &tags));
+        assert!(!filter.is_selected(&ComponentPath::from("app::suite_b::test_a"), &tags));
+    }
+
+    #[test]
+    fn should_select_when_tags_match() {
+        let filter = ComponentFilter::new(
+            vec![],
+            vec![],
+            Some(std::str::FromStr::from_str("smoke & !slow").unwrap()),
+        );
+
+        let path = ComponentPath::from("app::suite_a::test_a");
+        assert!(filter.is_selected(&path, &ComponentTags::from(&["smoke"])));
+        assert!(!filter.is_selected(&path, &ComponentTags::from(&["smoke", "slow"])));
+        assert!(!filter.is_selected(&path, &ComponentTags::new()));
+    }
+
+    #[test]
+    fn should_select_only_tests_to_rerun() {
+        let filter = ComponentFilter::new(vec![], vec![], None).rerun_only(vec![
+            ComponentPath::from("app::suite_a::test_a"),
+            ComponentPath::from("app::suite_b::test_b"),
+        ]);
+
+        let tags = ComponentTags::new();
+        assert!(filter.is_selected(&ComponentPath::from("app::suite_a::test_a"), &tags));
+        // Each case of a test is nested beneath the test's path
+        assert!(filter.is_selected(&ComponentPath::from("app::suite_b::test_b::0"), &tags));
+        assert!(!filter.is_selected(&ComponentPath::from("app::suite_a::test_ab"), &tags));
+        assert!(!filter.is_selected(&ComponentPath::from("app::suite_a::test_c"), &tags));
+    }
+}
@@ -12,6 +12,8 @@ pub struct ComponentGroup<TParameters> {
     pub tests: Vec<TestDecoration<TParameters>>,
     pub setups: Vec<BookEndDecoration<TParameters>>,
     pub tear_downs: Vec<BookEndDecoration<TParameters>>,
+    pub before_each: Vec<BookEndDecoration<TParameters>>,
+    pub after_each: Vec<BookEndDecoration<TParameters>>,
     pub sub_groups: Vec<ComponentGroup<TParameters>>,
 }
 
@@ -23,9 +25,13 @@ impl<TParameters: TestParameters> ComponentGroup<TParameters> {
     where
         ComponentsIterator: IntoIterator<Item = ComponentDecoration<TParameters>>,
     {
-        ComponentHierarchy::from_decorated_components(components)
+        let mut root = ComponentHierarchy::from_decorated_components(components)
             .into_component_groups()
-            .into_component(&mut ComponentGeneratorId::new(), None, parameters)
+            .into_component(&mut ComponentGeneratorId::new(), None, parameters);
+
+        // Dependencies can refer to tests anywhere in the hierarchy, so are resolved once it is complete
+        root.resolve_dependencies();
+        root
     }
 
     fn into_component(
@@ -49,7 +55,14 @@ impl<TParameters: TestParameters> ComponentGroup<TParameters> {
             .map(|x| x.into_setup_component(id_gen.next(), &suite.description, &suite.attributes))
             .collect();
 
-        // 2: Tests
+        // 2: Before each hooks
+        suite.before_each = self
+            .before_each
+            .into_iter()
+            .map(|x| x.into_setup_component(id_gen.next(), &suite.description, &suite.attributes))
+            .collect();
+
+        // 3: Tests
         suite.tests = self
             .tests
             .into_iter()
@@ -63,7 +76,16 @@ impl<TParameters: TestParameters> ComponentGroup<TParameters> {
             })
             .collect();
 
-        // 3: Nested Suites
+        // 4: After each hooks
+        suite.after_each = self
+            .after_each
+            .into_iter()
+            .map(|x| {
+                x.into_tear_down_component(id_gen.next(), &suite.description, &suite.attributes)
+            })
+            .collect();
+
+        // 5: Nested Suites
         suite.suites = self
             .sub_groups
             .into_iter()
@@ -126,6 +148,8 @@ pub struct HierarchyNode<TParameters> {
     tests: Vec<TestDecoration<TParameters>>,
     setups: Vec<BookEndDecoration<TParameters>>,
     tear_downs: Vec<BookEndDecoration<TParameters>>,
+    before_each: Vec<BookEndDecoration<TParameters>>,
+    after_each: Vec<BookEndDecoration<TParameters>>,
     nodes: HashMap<String, HierarchyNode<TParameters>>,
 }
 
@@ -136,6 +160,8 @@ impl<TParameters> HierarchyNode<TParameters> {
             tests: Vec::<TestDecoration<TParameters>>::new(),
             setups: Vec::<BookEndDecoration<TParameters>>::new(),
             tear_downs: Vec::<BookEndDecoration<TParameters>>::new(),
+            before_each: Vec::<BookEndDecoration<TParameters>>::new(),
+            after_each: Vec::<BookEndDecoration<TParameters>>::new(),
             nodes: HashMap::new(),
         }
     }
@@ -154,6 +180,14 @@ impl<TParameters> HierarchyNode<TParameters> {
             ComponentDecoration::Setup(bookend) => {
                 self.insert_setup(bookend);
             }
+            ComponentDecoration::BeforeEach(bookend) => {
+                self.insert_before_each(bookend);
+            }
+            ComponentDecoration::AfterEach(bookend) => {
+                self.insert_after_each(bookend);
+            }
+            // Factories are expanded into their tests by `ResolveDecorationStrategy`
+            ComponentDecoration::TestFactory(_) => {}
         }
     }
 
@@ -177,6 +211,16 @@ impl<TParameters> HierarchyNode<TParameters> {
         node.tear_downs.push(teardown);
     }
 
+    pub fn insert_before_each(&mut self, hook: BookEndDecoration<TParameters>) {
+        let node = self.find_method_entry(hook.desc.location.path.as_str());
+        node.before_each.push(hook);
+    }
+
+    pub fn insert_after_each(&mut self, hook: BookEndDecoration<TParameters>) {
+        let node = self.find_method_entry(hook.desc.location.path.as_str());
+        node.after_each.push(hook);
+    }
+
     fn find_namespace_entry<'a>(&'a mut self, path: &str) -> &'a mut Self {
         let v: Vec<&str> = path.split("::").collect();
         self.find_entry_from_path_elements(&v)
@@ -210,6 +254,8 @@ impl<TParameters> HierarchyNode<TParameters> {
         let mut tests = std::mem::take(&mut self.tests);
         let mut setups = std::mem::take(&mut self.setups);
         let mut tear_downs = std::mem::take(&mut self.tear_downs);
+        let mut before_each = std::mem::take(&mut self.before_each);
+        let mut after_each = std::mem::take(&mut self.after_each);
 
         for (_, node) in self.nodes {
             let mut group = node.into_component_groups();
@@ -220,6 +266,8 @@ impl<TParameters> HierarchyNode<TParameters> {
                 tests.append(&mut group.tests);
                 tear_downs.append(&mut group.tear_downs);
                 setups.append(&mut group.setups);
+                before_each.append(&mut group.before_each);
+                after_each.append(&mut group.after_each);
                 sub_groups.append(&mut group.sub_groups);
             }
         }
@@ -230,15 +278,20 @@ impl<TParameters> HierarchyNode<TParameters> {
             let b = b.suite.as_ref().map(|x| &x.location);
             a.cmp(&b)
         });
-        tests.sort_unstable_by(|a, b| a.desc.location.cmp(&b.desc.location));
+        // Tests expanded from a data source share a location, so must retain the order they were expanded in
+        tests.sort_by(|a, b| a.desc.location.cmp(&b.desc.location));
         setups.sort_unstable_by(|a, b| a.desc.location.cmp(&b.desc.location));
         tear_downs.sort_unstable_by(|a, b| a.desc.location.cmp(&b.desc.location));
+        before_each.sort_unstable_by(|a, b| a.desc.location.cmp(&b.desc.location));
+        after_each.sort_unstable_by(|a, b| a.desc.location.cmp(&b.desc.location));
 
         return ComponentGroup {
             suite,
             tests,
             setups,
             tear_downs,
+            before_each,
+            after_each,
             sub_groups,
         };
     }
@@ -10,14 +10,28 @@ pub use suite::SuiteAttributesDecoration;
 mod hierarchy;
 pub use hierarchy::{ComponentGroup, ComponentHierarchy};
 
+mod filter;
+pub use filter::{ComponentFilter, ComponentPathPattern};
+
+mod dynamic;
+pub use dynamic::{DynamicTest, TestFactoryDecoration};
+
+#[cfg(feature = "data_source")]
+mod data_source;
+#[cfg(feature = "data_source")]
+pub use data_source::DataSource;
+
 use crate::components::{ComponentLocation, ComponentType, Delegate};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum ComponentDecoration<TParameters> {
     IntegrationTest(TestDecoration<TParameters>),
     Suite(SuiteAttributesDecoration),
     TearDown(BookEndDecoration<TParameters>),
     Setup(BookEndDecoration<TParameters>),
+    BeforeEach(BookEndDecoration<TParameters>),
+    AfterEach(BookEndDecoration<TParameters>),
+    TestFactory(TestFactoryDecoration<TParameters>),
 }
 
 impl<TParameters> ComponentDecoration<TParameters> {
@@ -27,6 +41,9 @@ impl<TParameters> ComponentDecoration<TParameters> {
             ComponentDecoration::Suite(c) => c.name.clone(),
             ComponentDecoration::TearDown(c) => c.desc.name.clone(),
             ComponentDecoration::Setup(c) => c.desc.name.clone(),
+            ComponentDecoration::BeforeEach(c) => c.desc.name,
+            ComponentDecoration::AfterEach(c) => c.desc.name,
+            ComponentDecoration::TestFactory(_) => None,
         }
     }
 
@@ -36,6 +53,9 @@ impl<TParameters> ComponentDecoration<TParameters> {
             ComponentDecoration::Suite(c) => c.description.clone(),
             ComponentDecoration::TearDown(c) => c.desc.description.clone(),
             ComponentDecoration::Setup(c) => c.desc.description.clone(),
+            ComponentDecoration::BeforeEach(c) => c.desc.description,
+            ComponentDecoration::AfterEach(c) => c.desc.description,
+            ComponentDecoration::TestFactory(_) => None,
         }
     }
 
@@ -45,6 +65,9 @@ impl<TParameters> ComponentDecoration<TParameters> {
             ComponentDecoration::Suite(c) => &c.location,
             ComponentDecoration::TearDown(c) => &c.desc.location,
             ComponentDecoration::Setup(c) => &c.desc.location,
+            ComponentDecoration::BeforeEach(c) => &c.desc.location,
+            ComponentDecoration::AfterEach(c) => &c.desc.location,
+            ComponentDecoration::TestFactory(c) => &c.location,
         }
     }
 
@@ -54,15 +77,29 @@ impl<TParameters> ComponentDecoration<TParameters> {
             ComponentDecoration::Suite(_) => ComponentType::Suite,
             ComponentDecoration::TearDown(_) => ComponentType::TearDown,
             ComponentDecoration::Setup(_) => ComponentType::Setup,
+            ComponentDecoration::BeforeEach(_) => ComponentType::Setup,
+            ComponentDecoration::AfterEach(_) => ComponentType::TearDown,
+            ComponentDecoration::TestFactory(_) => ComponentType::Test,
         }
     }
 
+    /// Returns true if the decoration is a hook which is run as part of each test, rather than as a component of its own.
+    pub fn is_each_hook(&self) -> bool {
+        matches!(
+            self,
+            ComponentDecoration::BeforeEach(_) | ComponentDecoration::AfterEach(_)
+        )
+    }
+
     pub fn into_delegate(self) -> Option<Delegate<TParameters>> {
         match self {
             ComponentDecoration::IntegrationTest(c) => Some(c.test_fn),
             ComponentDecoration::Suite(_) => None,
             ComponentDecoration::TearDown(c) => Some(c.bookend_fn),
             ComponentDecoration::Setup(c) => Some(c.bookend_fn),
+            ComponentDecoration::BeforeEach(c) => Some(c.bookend_fn),
+            ComponentDecoration::AfterEach(c) => Some(c.bookend_fn),
+            ComponentDecoration::TestFactory(_) => None,
         }
     }
 }
@@ -80,24 +117,54 @@ pub mod test_rigging {
     #[derive(Clone)]
     pub struct TestAppParameters {
         pub max_concurrency: usize,
+        pub use_child_processes: bool,
         pub default_setup_time_limit: u64,
         pub test_time_limit_seconds: u64,
         pub test_warning_time_threshold_seconds: u64,
         pub tear_down_time_limit_seconds: u64,
+        pub run_time_limit: Option<Duration>,
+        pub cancellation_grace_period: Duration,
+        pub max_failures: Option<usize>,
+        pub rerun_failed: bool,
+        pub repeat: Option<usize>,
+        pub repeat_until_failure: bool,
+        pub shuffle_seed: Option<u64>,
+        pub shard: Option<crate::components::Shard>,
+        pub coordinator_address: Option<String>,
+        pub worker_address: Option<String>,
+        pub list_components: bool,
         pub test_concurrency: crate::components::ConcurrencyMode,
         pub suite_concurrency: crate::components::ConcurrencyMode,
+        pub filter_patterns: Vec<String>,
+        pub skip_patterns: Vec<String>,
+        pub tag_expression: Option<crate::components::TagExpression>,
     }
 
     impl TestAppParameters {
         pub fn default() -> Self {
             Self {
                 max_concurrency: 10,
+                use_child_processes: true,
                 default_setup_time_limit: 20,
                 test_time_limit_seconds: 30,
                 test_warning_time_threshold_seconds: 40,
                 tear_down_time_limit_seconds: 50,
+                run_time_limit: None,
+                cancellation_grace_period: Duration::from_secs(1),
+                max_failures: None,
+                rerun_failed: false,
+                repeat: None,
+                repeat_until_failure: false,
+                shuffle_seed: None,
+                shard: None,
+                coordinator_address: None,
+                worker_address: None,
+                list_components: false,
                 test_concurrency: crate::components::ConcurrencyMode::Parallel,
                 suite_concurrency: crate::components::ConcurrencyMode::Sequential,
+                filter_patterns: Vec::new(),
+                skip_patterns: Vec::new(),
+                tag_expression: None,
             }
         }
     }
@@ -108,13 +175,25 @@ pub mod test_rigging {
         }
 
         fn use_child_processes(&self) -> bool {
-            true // not needed for tests
+            self.use_child_processes
         }
 
         fn max_concurrency(&self) -> usize {
             self.max_concurrency
         }
 
+        fn filter_patterns(&self) -> &'_ [String] {
+            &self.filter_patterns
+        }
+
+        fn skip_patterns(&self) -> &'_ [String] {
+            &self.skip_patterns
+        }
+
+        fn tag_expression(&self) -> Option<&'_ crate::components::TagExpression> {
+            self.tag_expression.as_ref()
+        }
+
         fn test_concurrency(&self) -> crate::components::ConcurrencyMode {
             self.test_concurrency.clone()
         }
@@ -139,6 +218,50 @@ pub mod test_rigging {
             Duration::from_secs(self.test_warning_time_threshold_seconds)
         }
 
+        fn run_time_limit_duration(&self) -> Option<Duration> {
+            self.run_time_limit
+        }
+
+        fn cancellation_grace_period_duration(&self) -> Duration {
+            self.cancellation_grace_period
+        }
+
+        fn max_failures(&self) -> Option<usize> {
+            self.max_failures
+        }
+
+        fn rerun_failed(&self) -> bool {
+            self.rerun_failed
+        }
+
+        fn repeat(&self) -> Option<usize> {
+            self.repeat
+        }
+
+        fn repeat_until_failure(&self) -> bool {
+            self.repeat_until_failure
+        }
+
+        fn shuffle_seed(&self) -> Option<u64> {
+            self.shuffle_seed
+        }
+
+        fn shard(&self) -> Option<crate::components::Shard> {
+            self.shard
+        }
+
+        fn coordinator_address(&self) -> Option<&'_ str> {
+            self.coordinator_address.as_deref()
+        }
+
+        fn worker_address(&self) -> Option<&'_ str> {
+            self.worker_address.as_deref()
+        }
+
+        fn list_components(&self) -> bool {
+            self.list_components
+        }
+
         // Find somewhere else for this
         fn root_namespace(&self) -> &'static str {
             "integra8_decorations"
@@ -161,11 +284,21 @@ pub mod test_rigging {
     }
 
     pub type Parameters = TestAppParameters;
+
+    /// Runs a future to completion on a new runtime.
+    /// A runtime is required, as sync components are run on its blocking thread pool.
+    pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        crate::async_runtime::Runtime::new().unwrap().block_on(future)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::components::ConcurrencyMode;
+    use crate::components::{
+        ComponentMetadata, ComponentPath, ComponentTags, ConcurrencyMode, EventuallyPolicy,
+        RetryBackoff, RetryPolicy,
+    };
+    use std::time::Duration;
     use super::*;
 
     mod mock_app {
@@ -186,6 +319,7 @@ mod tests {
         #[time_limit = "2s"]
         #[ignore]
         #[parallel]
+        #[retry(times = 1)]
         pub fn setup_a_with_decorations() {}
 
         #[setup]
@@ -202,25 +336,49 @@ mod tests {
         #[integra8(crate = crate)]
         pub fn test_a() {}
 
+        /// The doc comment of test A,
+        /// spanning two lines
         #[integration_test]
         #[integra8(crate = crate)]
         #[name = "Test A"]
         #[description = "the description of this test A"]
+        #[tag = "smoke"]
         #[time_limit = "2s"]
         #[warning_time_limit = "1s"]
         #[sequential]
         #[ignore]
         #[allow_fail]
+        #[retry(times = 3, delay = "2s", backoff = "exponential")]
+        #[eventually(timeout = "2m", interval = "5s")]
         pub fn test_a_with_decorations() {}
 
         #[integration_test]
         #[integra8(crate = crate)]
+        #[tag = "smoke"]
+        #[tag = "slow"]
         pub fn test_b() {}
 
         #[integration_test]
         #[integra8(crate = crate)]
         pub fn test_c() {}
 
+        #[integration_test]
+        #[integra8(crate = crate)]
+        #[case(1, "one", name = "First Case")]
+        #[case(2, "two")]
+        #[case(3, "three", name = "type")]
+        pub fn test_e_with_cases(_number: i32, _text: &str) {}
+
+        #[integration_test]
+        #[integra8(crate = crate)]
+        #[name = "Test F"]
+        #[case("us-east")]
+        pub async fn test_f_with_cases(
+            _ctx: crate::components::ExecutionContext<crate::Parameters>,
+            _region: &'static str,
+        ) {
+        }
+
         // Tear downs
 
         #[teardown]
@@ -244,6 +402,86 @@ mod tests {
         #[integra8(crate = crate)]
         pub fn teardown_c() {}
 
+        // Before and after each hooks
+
+        #[before_each]
+        #[integra8(crate = crate)]
+        pub fn before_each_a() {}
+
+        #[after_each]
+        #[integra8(crate = crate)]
+        #[name = "After Each A"]
+        #[time_limit = "3s"]
+        pub fn after_each_a() {}
+
+        pub mod with_suite_state {
+
+            pub use integra8_decorations_impl::*;
+            use std::sync::Arc;
+
+            #[setup]
+            #[integra8(crate = crate)]
+            pub fn setup_token() -> String {
+                "token".to_string()
+            }
+
+            #[setup]
+            #[integra8(crate = crate)]
+            pub async fn setup_user_id() -> Result<u32, String> {
+                Err("user not found".to_string())
+            }
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            pub fn test_with_token(token: &String) -> Result<(), String> {
+                match token.as_str() {
+                    "token" => Ok(()),
+                    _ => Err(format!("unexpected token {}", token)),
+                }
+            }
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[case("token")]
+            pub async fn test_with_token_and_context(
+                ctx: crate::components::ExecutionContext<crate::Parameters>,
+                token: Arc<String>,
+                expected: &'static str,
+            ) {
+                assert_eq!(*token, expected);
+                assert_eq!(*ctx.state.get::<String>().unwrap(), expected);
+            }
+        }
+
+        #[suite]
+        #[integra8(crate = crate)]
+        #[skip_if(parameters.max_concurrency == 1)]
+        pub mod with_skip {
+
+            pub use integra8_decorations_impl::*;
+
+            #[setup]
+            #[integra8(crate = crate)]
+            #[skip_if(parameters.max_concurrency == 10, "max concurrency is 10")]
+            pub fn setup_skip_if() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[skip_if(parameters.max_concurrency == 10, "max concurrency is 10")]
+            pub fn test_skip_if() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            #[skip_if(parameters.max_concurrency > 10)]
+            pub fn test_skip_if_without_reason() {}
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            pub fn test_skips_itself(ctx: crate::components::ExecutionContext<crate::Parameters>) {
+                ctx.skip("skipped at runtime");
+            }
+        }
+
         pub mod nested_namespace {
 
             pub use integra8_decorations_impl::*;
@@ -280,6 +518,8 @@ mod tests {
 
         #[suite]
         #[integra8(crate = crate)]
+        #[tag = "smoke"]
+        #[meta(owner = "payments", severity = "p2")]
         pub mod nested_suite_z {
 
             pub use integra8_decorations_impl::*;
@@ -297,6 +537,8 @@ mod tests {
             #[sequential]
             #[ignore]
             #[allow_fail]
+            #[depends_on(test_az)]
+            #[meta(severity = "p1", ticket = "PAY-123")]
             pub fn test_az_with_decorations() {}
 
             #[setup]
@@ -310,6 +552,7 @@ mod tests {
             #[time_limit = "2s"]
             #[ignore]
             #[parallel]
+            #[meta(ticket = "PAY-456")]
             pub fn setup_az_with_decorations() {}
 
             #[teardown]
@@ -338,6 +581,8 @@ mod tests {
         #[setup_time_limit = "12s"]
         #[test_time_limit = "13s"]
         #[test_warning_time_limit = "14s"]
+        #[suite_warning_time_limit = "15s"]
+        #[suite_time_limit = "16s"]
         pub mod nested_suite_y {
 
             pub use integra8_decorations_impl::*;
@@ -400,6 +645,14 @@ mod tests {
                 #[teardown]
                 #[integra8(crate = crate)]
                 pub fn teardown_ax() {}
+
+                #[before_each]
+                #[integra8(crate = crate)]
+                pub fn before_each_ax() {}
+
+                #[after_each]
+                #[integra8(crate = crate)]
+                pub fn after_each_ax() {}
             }
         }
     }
@@ -438,6 +691,7 @@ mod tests {
         assert_eq!(root.attributes.test_time_limit.as_secs(), 30);
         assert_eq!(root.attributes.test_warning_time_limit.as_secs(), 40);
         assert_eq!(root.attributes.tear_down_time_limit.as_secs(), 50);
+        assert_eq!(root.attributes.suite_time_limit, None);
         assert_eq!(
             root.attributes.suite_concurrency_mode,
             ConcurrencyMode::Sequential
@@ -448,6 +702,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_limit_root_suite_by_run_time_limit() {
+        // Arrange
+        let mut parameters = crate::Parameters::default();
+        parameters.run_time_limit = Some(Duration::from_secs(60));
+
+        // Act
+        let root = ComponentGroup::into_root_component(vec![], &parameters);
+
+        // Assert
+        assert_eq!(root.attributes.suite_time_limit, Some(Duration::from_secs(60)));
+    }
+
     mod should_initialize_with_a_single_component {
         use super::*;
         use super::test_rigging::Parameters;
@@ -618,6 +885,8 @@ mod tests {
             assert_eq!(suite1.attributes.test_warning_time_limit.as_secs(), 40);
             assert_eq!(suite1.attributes.setup_time_limit.as_secs(), 20);
             assert_eq!(suite1.attributes.tear_down_time_limit.as_secs(), 50);
+            assert_eq!(suite1.attributes.suite_warning_time_limit, None);
+            assert_eq!(suite1.attributes.suite_time_limit, None);
             assert_eq!(
                 suite1.attributes.suite_concurrency_mode,
                 ConcurrencyMode::Sequential
@@ -793,7 +1062,12 @@ mod tests {
                 test1.description.description(),
                 Some("the description of this test A")
             );
+            assert_eq!(
+                test1.description.doc(),
+                Some("The doc comment of test A,\nspanning two lines")
+            );
             assert_eq!(test1.description.component_type(), &ComponentType::Test);
+            assert_eq!(test1.description.tags(), &ComponentTags::from(&["smoke"]));
             assert_eq!(test1.attributes.allow_fail, true);
             assert_eq!(test1.attributes.ignore, true);
             assert_eq!(test1.attributes.time_limit.as_secs(), 2);
@@ -802,6 +1076,21 @@ mod tests {
                 test1.attributes.concurrency_mode,
                 ConcurrencyMode::Sequential
             );
+            assert_eq!(
+                test1.attributes.retry,
+                Some(RetryPolicy::new(
+                    3,
+                    Duration::from_secs(2),
+                    RetryBackoff::Exponential
+                ))
+            );
+            assert_eq!(
+                test1.attributes.eventually,
+                Some(EventuallyPolicy::new(
+                    Duration::from_secs(120),
+                    Duration::from_secs(5)
+                ))
+            );
         }
 
         #[test]
@@ -844,6 +1133,10 @@ mod tests {
                 setup1.attributes.concurrency_mode,
                 ConcurrencyMode::Parallel
             );
+            assert_eq!(
+                setup1.attributes.retry,
+                Some(RetryPolicy::new(1, Duration::ZERO, RetryBackoff::Fixed))
+            );
         }
 
         #[test]
@@ -931,6 +1224,14 @@ mod tests {
             assert_eq!(suite1.attributes.test_warning_time_limit.as_secs(), 14);
             assert_eq!(suite1.attributes.setup_time_limit.as_secs(), 12);
             assert_eq!(suite1.attributes.tear_down_time_limit.as_secs(), 11);
+            assert_eq!(
+                suite1.attributes.suite_warning_time_limit,
+                Some(Duration::from_secs(15))
+            );
+            assert_eq!(
+                suite1.attributes.suite_time_limit,
+                Some(Duration::from_secs(16))
+            );
             assert_eq!(
                 suite1.attributes.suite_concurrency_mode,
                 ConcurrencyMode::Parallel
@@ -1131,6 +1432,22 @@ mod tests {
                 test1.attributes.concurrency_mode,
                 ConcurrencyMode::Sequential
             );
+            assert_eq!(
+                test1.attributes.depends_on,
+                vec![ComponentPath::from(
+                    "integra8::decorations::tests::mock_app::nested_suite_z::test_az"
+                )]
+            );
+
+            // Metadata is inherited from the suite, and overridden by the test's own metadata
+            assert_eq!(
+                test1.description.metadata(),
+                &ComponentMetadata::from(&[
+                    ("owner", "payments"),
+                    ("severity", "p1"),
+                    ("ticket", "PAY-123")
+                ])
+            );
         }
 
         #[test]
@@ -1177,6 +1494,14 @@ mod tests {
                 setup1.attributes.concurrency_mode,
                 ConcurrencyMode::Parallel
             );
+            assert_eq!(
+                setup1.description.metadata(),
+                &ComponentMetadata::from(&[
+                    ("owner", "payments"),
+                    ("severity", "p2"),
+                    ("ticket", "PAY-456")
+                ])
+            );
         }
 
         #[test]
@@ -1784,4 +2109,692 @@ mod tests {
             "integra8::decorations::tests::mock_app::teardown_c"
         );
     }
+
+    mod should_apply_filters {
+        use super::*;
+        use super::test_rigging::Parameters;
+        use crate::components::{Shard, ShardMode};
+
+        fn filtered_root(
+            filter_patterns: Vec<&str>,
+            skip_patterns: Vec<&str>,
+            tag_expression: Option<&str>,
+        ) -> crate::components::Suite<Parameters> {
+            let mut parameters = Parameters::default();
+            parameters.filter_patterns = filter_patterns.into_iter().map(String::from).collect();
+            parameters.skip_patterns = skip_patterns.into_iter().map(String::from).collect();
+            parameters.tag_expression = tag_expression.map(|s| std::str::FromStr::from_str(s).unwrap());
+
+            let decorations = ComponentFilter::from_parameters(&parameters).apply(vec![
+                mock_app::setup_a::setup_def(),
+                mock_app::test_a::test_def(),
+                mock_app::test_b::test_def(),
+                mock_app::teardown_a::teardown_def(),
+                mock_app::nested_suite_z::__suite_def(),
+                mock_app::nested_suite_z::setup_az::setup_def(),
+                mock_app::nested_suite_z::test_az::test_def(),
+                mock_app::nested_suite_z::teardown_az::teardown_def(),
+            ]);
+
+            ComponentGroup::into_root_component(decorations, &parameters)
+        }
+
+        #[test]
+        fn for_no_filters() {
+            // Act
+            let root = filtered_root(vec![], vec![], None);
+
+            // Assert
+            assert!(!root.attributes.filtered);
+            assert!(!root.setups[0].attributes.filtered);
+            assert!(!root.tests[0].attributes.filtered);
+            assert!(!root.tests[1].attributes.filtered);
+            assert!(!root.tear_downs[0].attributes.filtered);
+            assert!(!root.suites[0].attributes.filtered);
+            assert!(!root.suites[0].setups[0].attributes.filtered);
+            assert!(!root.suites[0].tests[0].attributes.filtered);
+            assert!(!root.suites[0].tear_downs[0].attributes.filtered);
+        }
+
+        #[test]
+        fn for_exact_path() {
+            // Act
+            let root = filtered_root(
+                vec!["integra8::decorations::tests::mock_app::nested_suite_z::test_az"],
+                vec![],
+                None,
+            );
+
+            // Assert
+            assert!(!root.setups[0].attributes.filtered);
+            assert!(root.tests[0].attributes.filtered);
+            assert!(root.tests[1].attributes.filtered);
+            assert!(!root.tear_downs[0].attributes.filtered);
+            assert!(!root.suites[0].attributes.filtered);
+            assert!(!root.suites[0].setups[0].attributes.filtered);
+            assert!(!root.suites[0].tests[0].attributes.filtered);
+            assert!(!root.suites[0].tear_downs[0].attributes.filtered);
+        }
+
+        #[test]
+        fn for_path_prefix() {
+            // Act
+            let root = filtered_root(vec!["integra8::decorations::tests::mock_app::nested_suite_z"], vec![], None);
+
+            // Assert
+            assert!(!root.setups[0].attributes.filtered);
+            assert!(root.tests[0].attributes.filtered);
+            assert!(root.tests[1].attributes.filtered);
+            assert!(!root.tear_downs[0].attributes.filtered);
+            assert!(!root.suites[0].attributes.filtered);
+            assert!(!root.suites[0].setups[0].attributes.filtered);
+            assert!(!root.suites[0].tests[0].attributes.filtered);
+            assert!(!root.suites[0].tear_downs[0].attributes.filtered);
+        }
+
+        #[test]
+        fn for_partial_path_segment() {
+            // Act
+            let root = filtered_root(vec!["integra8::decorations::tests::mock_app::test_"], vec![], None);
+
+            // Assert
+            assert!(root.tests[0].attributes.filtered);
+            assert!(root.tests[1].attributes.filtered);
+
+            // Suites with no selected tests are filtered along with their setups and tear downs
+            assert!(root.suites[0].attributes.filtered);
+            assert!(root.suites[0].setups[0].attributes.filtered);
+            assert!(root.suites[0].tests[0].attributes.filtered);
+            assert!(root.suites[0].tear_downs[0].attributes.filtered);
+        }
+
+        #[test]
+        fn for_listing() {
+            // Act
+            let root = filtered_root(vec!["*::test_b"], vec![], None);
+            let listing = crate::components::ComponentListing::from_suite(&root);
+
+            // Assert
+            let listed = listing
+                .all()
+                .map(|c| c.description.path().as_str().to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                listed,
+                vec![
+                    "integra8_decorations",
+                    "integra8::decorations::tests::mock_app::setup_a",
+                    "integra8::decorations::tests::mock_app::test_b",
+                    "integra8::decorations::tests::mock_app::teardown_a",
+                ]
+            );
+        }
+
+        #[test]
+        fn for_glob() {
+            // Act
+            let root = filtered_root(vec!["*::test_b"], vec![], None);
+
+            // Assert
+            assert!(root.tests[0].attributes.filtered);
+            assert!(!root.tests[1].attributes.filtered);
+            assert!(root.suites[0].attributes.filtered);
+        }
+
+        #[test]
+        fn for_skip() {
+            // Act
+            let root = filtered_root(vec![], vec!["*::mock_app::test_?", "*::nested_suite_z"], None);
+
+            // Assert
+            assert!(root.tests[0].attributes.filtered);
+            assert!(root.tests[1].attributes.filtered);
+            assert!(root.suites[0].attributes.filtered);
+            assert!(root.suites[0].tests[0].attributes.filtered);
+
+            // The root suite has no selected tests, so its setups and tear downs are also filtered
+            assert!(root.setups[0].attributes.filtered);
+            assert!(root.tear_downs[0].attributes.filtered);
+        }
+
+        #[test]
+        fn for_tags() {
+            // Act
+            let root = filtered_root(vec![], vec![], Some("smoke"));
+
+            // Assert
+            assert!(root.tests[0].attributes.filtered);
+            assert!(!root.tests[1].attributes.filtered);
+
+            // Tests inherit the tags of their suite
+            assert_eq!(
+                root.suites[0].tests[0].description.tags(),
+                &ComponentTags::from(&["smoke"])
+            );
+            assert!(!root.suites[0].attributes.filtered);
+            assert!(!root.suites[0].tests[0].attributes.filtered);
+        }
+
+        #[test]
+        fn for_tag_expression() {
+            // Act
+            let root = filtered_root(vec![], vec![], Some("smoke & !slow"));
+
+            // Assert
+            assert!(root.tests[0].attributes.filtered);
+            assert!(root.tests[1].attributes.filtered);
+            assert!(!root.suites[0].attributes.filtered);
+            assert!(!root.suites[0].tests[0].attributes.filtered);
+
+            // The root suite still has a selected test within a nested suite
+            assert!(!root.setups[0].attributes.filtered);
+            assert!(!root.tear_downs[0].attributes.filtered);
+        }
+
+        fn sharded_roots(mode: ShardMode) -> Vec<crate::components::Suite<Parameters>> {
+            (1..=3)
+                .map(|index| {
+                    let mut parameters = Parameters::default();
+                    parameters.shard = Some(Shard::new(index, 3, mode).unwrap());
+
+                    let decorations = ComponentFilter::from_parameters(&parameters).apply(vec![
+                        mock_app::setup_a::setup_def(),
+                        mock_app::test_a::test_def(),
+                        mock_app::test_b::test_def(),
+                        mock_app::test_c::test_def(),
+                        mock_app::nested_suite_z::__suite_def(),
+                        mock_app::nested_suite_z::setup_az::setup_def(),
+                        mock_app::nested_suite_z::test_az::test_def(),
+                        mock_app::nested_suite_z::test_az_with_decorations::test_def(),
+                        mock_app::nested_suite_z::teardown_az::teardown_def(),
+                    ]);
+                    ComponentGroup::into_root_component(decorations, &parameters)
+                })
+                .collect()
+        }
+
+        #[test]
+        fn for_each_test_on_exactly_one_shard() {
+            // Act
+            let roots = sharded_roots(ShardMode::Test);
+
+            // Assert
+            for idx in 0..3 {
+                let runs = roots.iter().filter(|r| !r.tests[idx].attributes.filtered).count();
+                assert_eq!(runs, 1);
+            }
+            for idx in 0..2 {
+                let runs = roots.iter().filter(|r| !r.suites[0].tests[idx].attributes.filtered).count();
+                assert_eq!(runs, 1);
+            }
+
+            // Suites are run by every shard which runs any of their tests
+            for root in &roots {
+                let has_tests = root.suites[0].tests.iter().any(|t| !t.attributes.filtered);
+                assert_eq!(!root.suites[0].attributes.filtered, has_tests);
+                assert_eq!(!root.suites[0].setups[0].attributes.filtered, has_tests);
+                assert_eq!(!root.suites[0].tear_downs[0].attributes.filtered, has_tests);
+            }
+        }
+
+        #[test]
+        fn for_each_suite_on_exactly_one_shard() {
+            // Act
+            let roots = sharded_roots(ShardMode::Suite);
+
+            // Assert
+            let suite_runs = roots
+                .iter()
+                .filter(|r| !r.suites[0].attributes.filtered)
+                .collect::<Vec<_>>();
+            assert_eq!(suite_runs.len(), 1);
+            assert!(suite_runs[0].suites[0].tests.iter().all(|t| !t.attributes.filtered));
+            assert!(!suite_runs[0].suites[0].setups[0].attributes.filtered);
+            assert!(!suite_runs[0].suites[0].tear_downs[0].attributes.filtered);
+        }
+    }
+
+    mod should_expand_test_cases {
+        use super::*;
+        use super::test_rigging::Parameters;
+
+        #[test]
+        fn into_a_test_per_case() {
+            // Act
+            let root = ComponentGroup::into_root_component(
+                vec![
+                    mock_app::test_e_with_cases::first_case::test_def(),
+                    mock_app::test_e_with_cases::case_2::test_def(),
+                    mock_app::test_f_with_cases::case_1::test_def(),
+                    mock_app::test_e_with_cases::case_type::test_def(),
+                ],
+                &Parameters::default(),
+            );
+
+            // Assert
+            assert_eq!(root.tests.len(), 4);
+
+            let case1 = &root.tests[0];
+            assert_eq!(
+                case1.description.path().as_str(),
+                "integra8::decorations::tests::mock_app::test_e_with_cases::first_case",
+            );
+            assert_eq!(case1.description.friendly_name(), "test_e_with_cases (First Case)");
+
+            let case2 = &root.tests[1];
+            assert_eq!(
+                case2.description.path().as_str(),
+                "integra8::decorations::tests::mock_app::test_e_with_cases::case_2",
+            );
+            assert_eq!(case2.description.friendly_name(), "test_e_with_cases (2, \"two\")");
+
+            // Case names which are keywords are prefixed, to be valid module names
+            let case3 = &root.tests[2];
+            assert_eq!(
+                case3.description.path().as_str(),
+                "integra8::decorations::tests::mock_app::test_e_with_cases::case_type",
+            );
+            assert_eq!(case3.description.friendly_name(), "test_e_with_cases (type)");
+
+            let case4 = &root.tests[3];
+            assert_eq!(
+                case4.description.path().as_str(),
+                "integra8::decorations::tests::mock_app::test_f_with_cases::case_1",
+            );
+            assert_eq!(case4.description.friendly_name(), "Test F (\"us-east\")");
+        }
+    }
+
+    mod should_schedule_dependencies {
+        use super::*;
+        use super::test_rigging::Parameters;
+        use crate::scheduling::{IntoTaskStateMachine, TaskStream};
+
+        #[test]
+        fn when_dependency_is_within_the_same_suite() {
+            // Act
+            let root = ComponentGroup::into_root_component(
+                vec![
+                    mock_app::nested_suite_z::__suite_def(),
+                    mock_app::nested_suite_z::test_az_with_decorations::test_def(),
+                    mock_app::nested_suite_z::test_az::test_def(),
+                ],
+                &Parameters::default(),
+            );
+            let schedule = root.into_task_state_machine();
+
+            // Assert
+            // Both suites are scheduled twice, along side their tests
+            assert_eq!(schedule.len(), 6);
+        }
+
+        #[test]
+        fn unless_dependency_is_unknown() {
+            let root = ComponentGroup::into_root_component(
+                vec![
+                    mock_app::nested_suite_z::__suite_def(),
+                    mock_app::nested_suite_z::test_az_with_decorations::test_def(),
+                ],
+                &Parameters::default(),
+            );
+
+            // Act
+            let result = root.validate_dependencies();
+
+            // Assert
+            assert_eq!(
+                result,
+                Err("integra8::decorations::tests::mock_app::nested_suite_z::test_az_with_decorations depends on \
+                    integra8::decorations::tests::mock_app::nested_suite_z::test_az, which is not a test"
+                    .to_string())
+            );
+        }
+    }
+
+    mod should_wrap_tests_with_hooks {
+        use super::*;
+        use super::test_rigging::Parameters;
+        use crate::scheduling::{
+            IntoTaskStateMachine, PollTaskResult, ScheduledComponent, TaskStream,
+        };
+
+        fn hook_names(hooks: &[crate::components::BookEnd<Parameters>]) -> Vec<String> {
+            hooks
+                .iter()
+                .map(|hook| hook.description.friendly_name())
+                .collect()
+        }
+
+        #[test]
+        fn from_the_suite_they_are_declared_in() {
+            // Act
+            let root = ComponentGroup::into_root_component(
+                vec![
+                    mock_app::before_each_a::hook_def(),
+                    mock_app::after_each_a::hook_def(),
+                ],
+                &Parameters::default(),
+            );
+
+            // Assert
+            assert_eq!(root.setups.len(), 0);
+            assert_eq!(root.tear_downs.len(), 0);
+            assert_eq!(root.before_each.len(), 1);
+            assert_eq!(root.after_each.len(), 1);
+
+            let before_each = &root.before_each[0];
+            assert_eq!(
+                before_each.description.path().as_str(),
+                "integra8::decorations::tests::mock_app::before_each_a",
+            );
+            assert_eq!(before_each.description.id().as_unique_number(), 1);
+            assert_eq!(before_each.attributes.time_limit.as_secs(), 20);
+
+            let after_each = &root.after_each[0];
+            assert_eq!(after_each.description.full_name(), "After Each A");
+            assert_eq!(after_each.description.id().as_unique_number(), 2);
+            assert_eq!(after_each.attributes.time_limit.as_secs(), 3);
+        }
+
+        #[test]
+        fn from_the_outer_most_suite_inwards() {
+            let root = ComponentGroup::into_root_component(
+                vec![
+                    mock_app::before_each_a::hook_def(),
+                    mock_app::after_each_a::hook_def(),
+                    mock_app::nested_suite_y::__suite_def(),
+                    mock_app::nested_suite_y::nested_suite_x::__suite_def(),
+                    mock_app::nested_suite_y::nested_suite_x::before_each_ax::hook_def(),
+                    mock_app::nested_suite_y::nested_suite_x::after_each_ax::hook_def(),
+                    mock_app::nested_suite_y::nested_suite_x::test_ax::test_def(),
+                ],
+                &Parameters::default(),
+            );
+
+            // Act
+            let mut schedule = root.into_task_state_machine();
+            let mut tests = Vec::new();
+            while let PollTaskResult::Next(component, path) = schedule.try_poll() {
+                if let ScheduledComponent::Test(test) = component {
+                    tests.push(test);
+                }
+                schedule.complete_task(path);
+            }
+
+            // Assert
+            assert_eq!(tests.len(), 1);
+            assert_eq!(
+                hook_names(&tests[0].before_each),
+                vec![
+                    "integra8::decorations::tests::mock_app::before_each_a",
+                    "before_each_ax"
+                ]
+            );
+            assert_eq!(
+                hook_names(&tests[0].after_each),
+                vec!["after_each_ax", "After Each A"]
+            );
+        }
+    }
+
+    mod should_shuffle_schedule {
+        use super::*;
+        use super::test_rigging::Parameters;
+        use crate::components::ConcurrencyMode;
+        use crate::scheduling::{ShuffleRng, ShuffleSchedule};
+
+        fn shuffled_root(seed: u64) -> crate::components::Suite<Parameters> {
+            let mut parameters = Parameters::default();
+            parameters.test_concurrency = ConcurrencyMode::Sequential;
+
+            let mut root = ComponentGroup::into_root_component(
+                vec![
+                    mock_app::setup_a::setup_def(),
+                    mock_app::setup_b::setup_def(),
+                    mock_app::setup_c::setup_def(),
+                    mock_app::test_a::test_def(),
+                    mock_app::test_b::test_def(),
+                    mock_app::test_c::test_def(),
+                    mock_app::nested_suite_z::__suite_def(),
+                    mock_app::nested_suite_y::__suite_def(),
+                    mock_app::with_skip::__suite_def(),
+                ],
+                &parameters,
+            );
+            root.shuffle_schedule(&mut ShuffleRng::new(seed));
+            root
+        }
+
+        fn names(root: &crate::components::Suite<Parameters>) -> Vec<String> {
+            root.setups
+                .iter()
+                .map(|c| c.description.path().to_string())
+                .chain(root.tests.iter().map(|c| c.description.path().to_string()))
+                .chain(root.suites.iter().map(|c| c.description.path().to_string()))
+                .collect()
+        }
+
+        #[test]
+        fn in_the_same_order_for_the_same_seed() {
+            // Act
+            let first = shuffled_root(7);
+            let second = shuffled_root(7);
+
+            // Assert
+            assert_eq!(names(&first), names(&second));
+        }
+
+        #[test]
+        fn without_moving_setups() {
+            // Act
+            let orders = (0..10)
+                .map(|seed| names(&shuffled_root(seed)))
+                .collect::<Vec<_>>();
+
+            // Assert
+            assert!(orders.iter().any(|order| order != &orders[0]));
+            for order in &orders {
+                assert_eq!(
+                    &order[0..3],
+                    &[
+                        "integra8::decorations::tests::mock_app::setup_a",
+                        "integra8::decorations::tests::mock_app::setup_b",
+                        "integra8::decorations::tests::mock_app::setup_c",
+                    ]
+                );
+            }
+        }
+    }
+
+    mod should_share_suite_state {
+        use super::*;
+        use super::test_rigging::{block_on, Parameters};
+        use crate::components::{CancellationToken, ExecutionArtifacts, SuiteState};
+        use crate::runner::ComponentFixture;
+        use std::sync::Arc;
+
+        #[test]
+        fn from_setup_to_tests() {
+            let root = ComponentGroup::into_root_component(
+                vec![
+                    mock_app::with_suite_state::setup_token::setup_def(),
+                    mock_app::with_suite_state::test_with_token::test_def(),
+                    mock_app::with_suite_state::test_with_token_and_context::case_1::test_def(),
+                ],
+                &Parameters::default(),
+            );
+            let parameters = Arc::new(Parameters::default());
+            let state = SuiteState::new();
+
+            // Act
+            let setup = ComponentFixture::for_bookend(
+                root.setups[0].clone(),
+                parameters.clone(),
+                state.clone(),
+            );
+            let setup_result = block_on(setup.run(
+                Arc::new(ExecutionArtifacts::new()),
+                CancellationToken::new(),
+            ));
+
+            // Assert
+            assert!(setup_result.is_ok());
+            assert_eq!(*state.get::<String>().unwrap(), "token");
+            for test in &root.tests {
+                let test =
+                    ComponentFixture::for_test(test.clone(), parameters.clone(), state.clone());
+                assert!(block_on(test.run(
+                    Arc::new(ExecutionArtifacts::new()),
+                    CancellationToken::new(),
+                ))
+                .is_ok());
+            }
+        }
+
+        #[test]
+        fn unless_setup_returns_err() {
+            let root = ComponentGroup::into_root_component(
+                vec![mock_app::with_suite_state::setup_user_id::setup_def()],
+                &Parameters::default(),
+            );
+            let state = SuiteState::new();
+
+            // Act
+            let setup = ComponentFixture::for_bookend(
+                root.setups[0].clone(),
+                Arc::new(Parameters::default()),
+                state.clone(),
+            );
+            let setup_result = block_on(setup.run(
+                Arc::new(ExecutionArtifacts::new()),
+                CancellationToken::new(),
+            ));
+
+            // Assert
+            assert_eq!(setup_result.unwrap_err().to_string(), "user not found");
+            assert!(state.get::<u32>().is_err());
+        }
+
+        #[test]
+        fn unless_no_setup_provided_state() {
+            let root = ComponentGroup::into_root_component(
+                vec![mock_app::with_suite_state::test_with_token::test_def()],
+                &Parameters::default(),
+            );
+
+            // Act
+            let test = ComponentFixture::for_test(
+                root.tests[0].clone(),
+                Arc::new(Parameters::default()),
+                SuiteState::new(),
+            );
+            let result = block_on(test.run(
+                Arc::new(ExecutionArtifacts::new()),
+                CancellationToken::new(),
+            ));
+
+            // Assert
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                "No setup has provided suite state of type alloc::string::String"
+            );
+        }
+    }
+
+    mod should_skip {
+        use super::*;
+        use super::test_rigging::{block_on, Parameters};
+        use crate::components::{CancellationToken, ExecutionArtifacts, SkipRequest, SuiteState};
+        use crate::runner::ComponentFixture;
+        use futures::FutureExt;
+        use std::panic::AssertUnwindSafe;
+        use std::sync::Arc;
+
+        #[test]
+        fn when_skip_if_condition_is_met() {
+            let root = ComponentGroup::into_root_component(
+                vec![
+                    mock_app::with_skip::__suite_def(),
+                    mock_app::with_skip::setup_skip_if::setup_def(),
+                    mock_app::with_skip::test_skip_if::test_def(),
+                    mock_app::with_skip::test_skip_if_without_reason::test_def(),
+                ],
+                &Parameters::default(),
+            );
+            let parameters = Arc::new(Parameters::default());
+            let suite = &root.suites[0];
+
+            // Act
+            let setup = ComponentFixture::for_bookend(
+                suite.setups[0].clone(),
+                parameters.clone(),
+                SuiteState::new(),
+            );
+            let tests: Vec<_> = suite
+                .tests
+                .iter()
+                .map(|test| {
+                    ComponentFixture::for_test(test.clone(), parameters.clone(), SuiteState::new())
+                })
+                .collect();
+
+            // Assert
+            assert_eq!(setup.skip_reason(), Some("max concurrency is 10".to_string()));
+            assert_eq!(tests[0].skip_reason(), Some("max concurrency is 10".to_string()));
+            assert_eq!(tests[1].skip_reason(), None);
+        }
+
+        #[test]
+        fn with_condition_as_reason_when_no_reason_given() {
+            let mut parameters = Parameters::default();
+            parameters.max_concurrency = 1;
+            let root = ComponentGroup::into_root_component(
+                vec![mock_app::with_skip::__suite_def()],
+                &parameters,
+            );
+
+            // Act
+            let suite = ComponentFixture::for_suite(
+                root.suites[0].description.clone(),
+                root.suites[0].attributes.clone(),
+                Arc::new(parameters),
+                SuiteState::new(),
+            );
+
+            // Assert
+            assert_eq!(
+                suite.skip_reason(),
+                Some("parameters.max_concurrency == 1".to_string())
+            );
+        }
+
+        #[test]
+        fn when_component_skips_itself() {
+            let root = ComponentGroup::into_root_component(
+                vec![mock_app::with_skip::test_skips_itself::test_def()],
+                &Parameters::default(),
+            );
+
+            // Act
+            let test = ComponentFixture::for_test(
+                root.tests[0].clone(),
+                Arc::new(Parameters::default()),
+                SuiteState::new(),
+            );
+            let result = block_on(
+                AssertUnwindSafe(test.run(
+                    Arc::new(ExecutionArtifacts::new()),
+                    CancellationToken::new(),
+                ))
+                .catch_unwind(),
+            );
+
+            // Assert
+            let panic = result.expect_err("Expected the test to unwind");
+            assert_eq!(
+                SkipRequest::from_panic(panic.as_ref()),
+                Some("skipped at runtime")
+            );
+        }
+    }
 }
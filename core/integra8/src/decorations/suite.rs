@@ -1,8 +1,8 @@
 use std::time::Duration;
 
 use crate::components::{
-    ComponentDescription, ComponentId, ComponentLocation, ComponentPath, ConcurrencyMode, Suite,
-    SuiteAttributes, TestParameters,
+    ComponentDescription, ComponentId, ComponentLocation, ComponentPath, ConcurrencyMode,
+    SkipCondition, Suite, SuiteAttributes, TestParameters,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -13,12 +13,27 @@ pub struct SuiteAttributesDecoration {
     // A description of the suite which can be displayed by the output formatter if it supports it
     pub description: Option<&'static str>,
 
+    /// The doc comments of the suite, if it has any
+    pub doc: Option<&'static str>,
+
+    /// Tags used to select this suite from the command line.
+    /// Tags which are inherited by all components within this suite.
+    pub tags: &'static [&'static str],
+    pub metadata: &'static [(&'static str, &'static str)],
+
     /// The source code location of this test
     pub location: ComponentLocation,
 
     /// Indicates that this entire suite should not be run.
     pub ignore: Option<bool>,
 
+    /// Indicates that this entire suite should not be run, if the condition is met by the parameters.
+    pub skip_if: Option<SkipCondition>,
+
+    /// Indicates that this entire suite was excluded by the test selection filters.
+    /// This is determined by `ResolveDecorationStrategy` and not by the decoration itself.
+    pub filtered: Option<bool>,
+
     /// Indicates that this suite should be run, but failures should be ignored and do not cascade.
     pub allow_suite_fail: Option<bool>,
 
@@ -41,6 +56,14 @@ pub struct SuiteAttributesDecoration {
     /// Tear downs which are a part of this suite, that do not advertize a time limit will inherit this value
     pub tear_down_time_limit: Option<Duration>,
 
+    /// The duration after which this suite is flagged as exceeding its expected duration.
+    /// Unlike test time limits, this is not inherited by child suites.
+    pub suite_warning_time_limit: Option<Duration>,
+
+    /// Describes the maximum duration this suite, including all of its child components, can take before it is aborted.
+    /// Unlike test time limits, this is not inherited by child suites, however child suites are aborted along with this suite.
+    pub suite_time_limit: Option<Duration>,
+
     /// The concurrency model used when executing this suite of tests.
     /// `ConcurrencyMode::Parallel` will allow this suite to be run at the same time as other suites.
     /// `ConcurrencyMode::Sequential` will ensure this suite is only run on its own
@@ -57,6 +80,9 @@ impl SuiteAttributesDecoration {
         Self {
             name: Some(path),
             description: None,
+            doc: None,
+            tags: &[],
+            metadata: &[],
             location: ComponentLocation {
                 path: ComponentPath::from(path),
                 file_name: std::borrow::Cow::from("main.rs"),
@@ -64,11 +90,15 @@ impl SuiteAttributesDecoration {
                 line: 0,
             },
             ignore: None,
+            skip_if: None,
+            filtered: None,
             allow_suite_fail: None,
             test_warning_time_limit: None,
             test_time_limit: None,
             setup_time_limit: None,
             tear_down_time_limit: None,
+            suite_warning_time_limit: None,
+            suite_time_limit: None,
             suite_concurrency_mode: None,
             test_concurrency_mode: None,
         }
@@ -86,13 +116,20 @@ impl SuiteAttributesDecoration {
             id,
             self.name,
             self.description,
+            self.doc,
+            self.tags,
+            self.metadata,
             self.ignore,
+            self.skip_if,
+            self.filtered,
             self.location,
             self.allow_suite_fail,
             self.test_warning_time_limit,
             self.test_time_limit,
             self.setup_time_limit,
             self.tear_down_time_limit,
+            self.suite_warning_time_limit,
+            self.suite_time_limit,
             self.suite_concurrency_mode,
             self.test_concurrency_mode,
         )
@@ -2,10 +2,10 @@ use std::time::Duration;
 
 use crate::components::{
     ComponentDescription, ComponentId, ComponentLocation, ConcurrencyMode, Delegate,
-    SuiteAttributes, Test, TestParameters,
+    EventuallyPolicy, RetryPolicy, SkipCondition, SuiteAttributes, Test, TestParameters,
 };
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TestAttributesDecoration {
     // The name of the test (Default: the tests namespace + test method name)
     pub name: Option<&'static str>,
@@ -13,6 +13,13 @@ pub struct TestAttributesDecoration {
     // A description of the test which can be displayed by the output formatter if it supports it
     pub description: Option<&'static str>,
 
+    /// The doc comments of the test, if it has any
+    pub doc: Option<&'static str>,
+
+    /// Tags used to select this test from the command line.
+    pub tags: &'static [&'static str],
+    pub metadata: &'static [(&'static str, &'static str)],
+
     /// The source code location of this test
     pub location: ComponentLocation,
 
@@ -22,6 +29,17 @@ pub struct TestAttributesDecoration {
     /// Indicates that test should not be run.
     pub ignore: Option<bool>,
 
+    /// Indicates that test should not be run, if the condition is met by the parameters.
+    pub skip_if: Option<SkipCondition>,
+
+    /// Indicates that test was excluded by the test selection filters.
+    /// This is determined by `ResolveDecorationStrategy` and not by the decoration itself.
+    pub filtered: Option<bool>,
+
+    /// The data files this test is run with, one test per record.
+    /// Tests with a data source are expanded by `ResolveDecorationStrategy`.
+    pub data_source: Option<&'static str>,
+
     /// The the duration after which a test is flagged as exceeded is expected duration.
     /// This can be used to give early warnings before a test exceeds some critical threshold.
     /// For example, a HTTP request time out.
@@ -34,9 +52,20 @@ pub struct TestAttributesDecoration {
     /// `ConcurrencyMode::Parallel` will allow this test for be run at the same time as other tests within this tests suite
     /// `ConcurrencyMode::Sequential` will ensure that this test wont run at the same time as any other test from this suite
     pub concurrency_mode: Option<ConcurrencyMode>,
+
+    /// Describes how this test is re-run if it fails.
+    /// Each attempt is subject to the test's time limit.
+    pub retry: Option<RetryPolicy>,
+
+    /// Describes how this test is polled until it passes.
+    /// The time spent polling is subject to the test's time limit.
+    pub eventually: Option<EventuallyPolicy>,
+
+    /// The paths of the tests which must complete before this test is run, relative to this test's suite.
+    pub depends_on: &'static [&'static str],
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct TestDecoration<TParameters> {
     pub desc: TestAttributesDecoration,
     pub test_fn: Delegate<TParameters>,
@@ -57,12 +86,20 @@ impl<TParameters: TestParameters> TestDecoration<TParameters> {
             id,
             self.desc.name,
             self.desc.description,
+            self.desc.doc,
+            self.desc.tags,
+            self.desc.metadata,
             self.desc.location,
             self.desc.ignore,
+            self.desc.skip_if,
+            self.desc.filtered,
             self.desc.allow_fail,
             self.desc.warning_time_limit,
             self.desc.time_limit,
             self.desc.concurrency_mode,
+            self.desc.retry,
+            self.desc.eventually,
+            self.desc.depends_on,
             self.test_fn,
         )
     }
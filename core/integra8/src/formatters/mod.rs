@@ -2,7 +2,8 @@ pub mod none;
 
 pub mod models {
     pub use crate::components::{
-        ComponentDescription, ComponentId, ComponentLocation, ComponentType, TestParameters,
+        ComponentDescription, ComponentId, ComponentListing, ComponentLocation, ComponentType,
+        ListedAttributes, TestParameters,
     };
     pub use crate::results::*;
 }
@@ -11,7 +12,7 @@ use std::error::Error;
 
 use models::report::ComponentRunReport;
 use models::summary::{ComponentTypeCountSummary, RunSummary};
-use models::{ComponentDescription, TestParameters};
+use models::{ComponentDescription, ComponentListing, TestParameters};
 
 pub trait FormatterParameters {
     fn create_formatter(&self) -> Option<Box<dyn OutputFormatter>>;
@@ -71,6 +72,20 @@ pub trait OutputFormatter {
         Ok(())
     }
 
+    /// Writes a notice about the run as a whole, before any of its components are run.
+    /// For example, when the components to rerun from the last run could not be found.
+    fn write_run_notice(&mut self, _notice: &str) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    // List
+
+    /// Writes the components found in the test application, when run with `--list`.
+    /// Nothing else is written, as none of the components are run.
+    fn write_component_list(&mut self, _listing: &ComponentListing) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
     // Component
 
     fn write_component_start(
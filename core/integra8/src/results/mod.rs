@@ -13,6 +13,9 @@ pub enum WarningReason {
     FailureAllowed,
     OvertimeWarning,
     ChildWarning,
+    PassedAfterRetry,
+    /// The component passed, but raised warnings with `ExecutionContext::warn`.
+    UserWarning,
 }
 
 #[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
@@ -27,6 +30,8 @@ pub enum FailureReason {
     Rejected,
     Overtime,
     ChildFailure,
+    /// The run exceeded the time limit given by `--framework:run-time-limit`. Only the root suite fails for this reason.
+    RunTimeLimitExceeded,
 }
 
 #[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
@@ -35,6 +40,11 @@ pub enum DidNotRunReason {
     Ignored,
     Filtered,
     ParentFailure,
+    DependencyFailure,
+    /// The component was skipped by `#[skip_if(...)]` or `ExecutionContext::skip`, for the given reason.
+    Skipped(String),
+    /// The run was aborted before the component started, as the maximum number of failures was reached.
+    RunAborted,
     Undetermined,
 }
 
@@ -64,6 +74,14 @@ impl ComponentResult {
         Self::Warning(WarningReason::ChildWarning)
     }
 
+    pub fn passed_after_retry() -> Self {
+        Self::Warning(WarningReason::PassedAfterRetry)
+    }
+
+    pub fn user_warning() -> Self {
+        Self::Warning(WarningReason::UserWarning)
+    }
+
     pub fn time_out_warning() -> Self {
         Self::Warning(WarningReason::OvertimeWarning)
     }
@@ -76,6 +94,10 @@ impl ComponentResult {
         Self::Fail(FailureReason::Rejected)
     }
 
+    pub fn run_time_limit_exceeded() -> Self {
+        Self::Fail(FailureReason::RunTimeLimitExceeded)
+    }
+
     pub fn ignored() -> Self {
         Self::DidNotRun(DidNotRunReason::Ignored)
     }
@@ -88,6 +110,18 @@ impl ComponentResult {
         Self::DidNotRun(DidNotRunReason::ParentFailure)
     }
 
+    pub fn dependency_failure() -> Self {
+        Self::DidNotRun(DidNotRunReason::DependencyFailure)
+    }
+
+    pub fn skipped(reason: impl Into<String>) -> Self {
+        Self::DidNotRun(DidNotRunReason::Skipped(reason.into()))
+    }
+
+    pub fn run_aborted() -> Self {
+        Self::DidNotRun(DidNotRunReason::RunAborted)
+    }
+
     pub fn undetermined() -> Self {
         Self::DidNotRun(DidNotRunReason::Undetermined)
     }
@@ -127,15 +161,23 @@ impl ComponentResult {
             ComponentResult::Warning(WarningReason::FailureAllowed) => 2,
             ComponentResult::Warning(WarningReason::OvertimeWarning) => 3,
             ComponentResult::Warning(WarningReason::ChildWarning) => 4,
+            ComponentResult::Warning(WarningReason::PassedAfterRetry) => 5,
+            // The warnings are passed back to the parent process via stderr
+            ComponentResult::Warning(WarningReason::UserWarning) => 6,
 
             ComponentResult::Fail(FailureReason::ChildFailure) => 10,
             ComponentResult::Fail(FailureReason::Rejected) => 11,
             ComponentResult::Fail(FailureReason::Overtime) => 12,
+            ComponentResult::Fail(FailureReason::RunTimeLimitExceeded) => 13,
 
             ComponentResult::DidNotRun(DidNotRunReason::Undetermined) => 20,
             ComponentResult::DidNotRun(DidNotRunReason::Filtered) => 21,
             ComponentResult::DidNotRun(DidNotRunReason::Ignored) => 22,
             ComponentResult::DidNotRun(DidNotRunReason::ParentFailure) => 23,
+            ComponentResult::DidNotRun(DidNotRunReason::DependencyFailure) => 24,
+            // The reason is passed back to the parent process separately
+            ComponentResult::DidNotRun(DidNotRunReason::Skipped(_)) => 25,
+            ComponentResult::DidNotRun(DidNotRunReason::RunAborted) => 26,
         }
     }
 
@@ -146,15 +188,21 @@ impl ComponentResult {
             2 => ComponentResult::Warning(WarningReason::FailureAllowed),
             3 => ComponentResult::Warning(WarningReason::OvertimeWarning),
             4 => ComponentResult::Warning(WarningReason::ChildWarning),
+            5 => ComponentResult::Warning(WarningReason::PassedAfterRetry),
+            6 => ComponentResult::Warning(WarningReason::UserWarning),
 
             10 => ComponentResult::Fail(FailureReason::ChildFailure),
             11 => ComponentResult::Fail(FailureReason::Rejected),
-            13 => ComponentResult::Fail(FailureReason::Overtime),
+            12 => ComponentResult::Fail(FailureReason::Overtime),
+            13 => ComponentResult::Fail(FailureReason::RunTimeLimitExceeded),
 
             20 => ComponentResult::DidNotRun(DidNotRunReason::Undetermined),
             21 => ComponentResult::DidNotRun(DidNotRunReason::Filtered),
             22 => ComponentResult::DidNotRun(DidNotRunReason::Ignored),
             23 => ComponentResult::DidNotRun(DidNotRunReason::ParentFailure),
+            24 => ComponentResult::DidNotRun(DidNotRunReason::DependencyFailure),
+            25 => ComponentResult::DidNotRun(DidNotRunReason::Skipped(String::new())),
+            26 => ComponentResult::DidNotRun(DidNotRunReason::RunAborted),
             _ => ComponentResult::DidNotRun(DidNotRunReason::Undetermined),
         }
     }
@@ -230,8 +278,9 @@ mod tests {
     use crate::results::report::ComponentReportBuilder;
     use crate::results::summary::RunSummary;
     use crate::components::{
-        AcceptanceCriteria, ComponentDescription, ComponentId, ComponentLocation, ComponentPath,
-        ComponentType, ExecutionArtifacts, TimingAcceptanceCriteria,
+        AcceptanceCriteria, ComponentDescription, ComponentId, ComponentLocation,
+        ComponentMetadata, ComponentPath, ComponentTags, ComponentType, ExecutionArtifacts,
+        TimingAcceptanceCriteria,
     };
 
     // Component Report Tests
@@ -257,6 +306,8 @@ mod tests {
                     path: ComponentPath::from("crate::results"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Suite,
             ),
             AcceptanceCriteria {
@@ -290,6 +341,8 @@ mod tests {
                     path: ComponentPath::from("crate::results"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Test,
             ),
             AcceptanceCriteria {
@@ -323,6 +376,8 @@ mod tests {
                     path: ComponentPath::from("crate::results"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Setup,
             ),
             AcceptanceCriteria {
@@ -356,6 +411,8 @@ mod tests {
                     path: ComponentPath::from("crate::results"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::TearDown,
             ),
             AcceptanceCriteria {
@@ -389,6 +446,8 @@ mod tests {
                     path: ComponentPath::from("crate::results"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Test,
             ),
             AcceptanceCriteria {
@@ -422,6 +481,8 @@ mod tests {
                     path: ComponentPath::from("crate::results"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Test,
             ),
             AcceptanceCriteria {
@@ -455,6 +516,8 @@ mod tests {
                     path: ComponentPath::from("crate::results"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Suite,
             ),
             AcceptanceCriteria {
@@ -488,6 +551,8 @@ mod tests {
                     path: ComponentPath::from("crate::results::test::suite_1"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Test,
             ),
             AcceptanceCriteria {
@@ -521,6 +586,8 @@ mod tests {
                     path: ComponentPath::from("crate::results::test::suite_1"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Setup,
             ),
             AcceptanceCriteria {
@@ -554,6 +621,8 @@ mod tests {
                     path: ComponentPath::from("crate::results::test::suite_1"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::TearDown,
             ),
             AcceptanceCriteria {
@@ -587,6 +656,8 @@ mod tests {
                     path: ComponentPath::from("crate::results"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Suite,
             ),
             AcceptanceCriteria {
@@ -620,6 +691,8 @@ mod tests {
                     path: ComponentPath::from("crate::results::test::suite_2"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Test,
             ),
             AcceptanceCriteria {
@@ -653,6 +726,8 @@ mod tests {
                     path: ComponentPath::from("crate::results::test::suite_2"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::Setup,
             ),
             AcceptanceCriteria {
@@ -686,6 +761,8 @@ mod tests {
                     path: ComponentPath::from("crate::results::test::suite_2"),
                 },
                 /* description */ None,
+                /* tags */ ComponentTags::new(),
+                /* metadata */ ComponentMetadata::new(),
                 /* component_type */ ComponentType::TearDown,
             ),
             AcceptanceCriteria {
@@ -773,6 +850,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_report_test_1_passed_after_retry() {
+        // Arrange
+        let mut builder = test_1_report_builder();
+
+        // Act
+        builder.rejected_result();
+        builder.record_attempt();
+        builder.reset_attempt();
+        builder.passed_result();
+        builder.record_attempt();
+        let report = builder.build();
+
+        // Assert
+        assert!(report.result.has_warn(), "Expected Test to have warning");
+        assert_eq!(
+            report.result,
+            ComponentResult::Warning(WarningReason::PassedAfterRetry),
+            "Expected Test to have warning, with WarningReason::PassedAfterRetry"
+        );
+        assert_eq!(report.attempts.len(), 2);
+        assert_eq!(report.attempts[0].result, ComponentResult::rejected());
+        assert_eq!(report.attempts[1].result, ComponentResult::passed());
+    }
+
+    #[test]
+    fn can_report_test_1_user_warning() {
+        // Arrange
+        let mut builder = test_1_report_builder();
+
+        let artifacts = ExecutionArtifacts::new();
+        artifacts.include_warning("used a deprecated api");
+        artifacts.include_warning("latency degraded");
+
+        // Act
+        builder.passed_result();
+        builder.with_artifacts(&artifacts);
+        let report = builder.build();
+
+        // Assert
+        assert!(report.result.has_warn(), "Expected Test to have warning");
+        assert_eq!(
+            report.result,
+            ComponentResult::Warning(WarningReason::UserWarning),
+            "Expected Test to have warning, with WarningReason::UserWarning"
+        );
+        assert_eq!(
+            report.artifacts.map["warning"].as_string().unwrap(),
+            "used a deprecated api\nlatency degraded"
+        );
+    }
+
+    #[test]
+    fn should_report_test_1_failure_when_failed_with_user_warning() {
+        // Arrange
+        let mut builder = test_1_report_builder();
+
+        let artifacts = ExecutionArtifacts::new();
+        artifacts.include_warning("used a deprecated api");
+
+        // Act
+        builder.rejected_result();
+        builder.with_artifacts(&artifacts);
+        let report = builder.build();
+
+        // Assert
+        assert_eq!(report.result, ComponentResult::rejected());
+    }
+
+    #[test]
+    fn can_report_test_1_passed_first_attempt_without_attempts() {
+        // Arrange
+        let mut builder = test_1_report_builder();
+
+        // Act
+        builder.passed_result();
+        builder.record_attempt();
+        let report = builder.build();
+
+        // Assert
+        assert_eq!(report.result, ComponentResult::passed());
+        assert!(report.attempts.is_empty());
+    }
+
     #[test]
     fn can_report_test_1_failed_due_to_child_failure() {
         // Arrange
@@ -875,6 +1036,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_report_test_2_skipped_after_exceeding_time_limit() {
+        // Arrange
+        let mut builder = test_2_report_builder();
+
+        // Act
+        builder.skipped_result("feature is disabled");
+        builder.time_taken(Duration::from_secs(3000));
+        let report = builder.build();
+
+        // Assert
+        assert!(
+            report.result.has_not_run(),
+            "Expected Test to have not be run"
+        );
+        assert_eq!(
+            report.result,
+            ComponentResult::skipped("feature is disabled"),
+            "Expected Test to have not be run, with DidNotRunReason::Skipped"
+        );
+    }
+
     // Time result behavior
 
     #[test]
@@ -1096,6 +1279,34 @@ mod tests {
         assert_eq!(time_to_deadline, None);
     }
 
+    #[test]
+    fn should_return_time_to_suite_deadline_when_sooner_than_timing_acceptance_criteria() {
+        // Arrange
+        let mut builder = test_2_report_builder();
+        builder.with_deadline(Some(std::time::Instant::now() + Duration::from_secs(100)));
+
+        // Act
+        let time_to_deadline = builder.time_until_deadline(Duration::from_secs(500)).unwrap();
+
+        // Assert
+        assert!(time_to_deadline <= Duration::from_secs(100));
+        assert!(time_to_deadline > Duration::from_secs(90));
+    }
+
+    #[test]
+    fn should_return_time_to_suite_deadline_when_no_timing_acceptance_criteria_is_defined() {
+        // Arrange
+        let mut builder = test_1_report_builder();
+        builder.with_deadline(Some(std::time::Instant::now()));
+
+        // Act
+        let time_to_deadline = builder.time_until_deadline(Duration::from_secs(0));
+
+        // Assert
+        assert_eq!(time_to_deadline, Some(Duration::from_secs(0)));
+        assert!(builder.has_exceeded_deadline());
+    }
+
     // Artifacts
 
     #[test]
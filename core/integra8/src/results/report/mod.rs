@@ -1,8 +1,10 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::results::artifacts::ComponentRunArtifacts;
 use crate::results::{ComponentResult, ComponentTimeResult};
-use crate::components::{AcceptanceCriteria, ComponentDescription, ExecutionArtifacts};
+use crate::components::{
+    AcceptanceCriteria, ComponentDescription, ComponentType, ExecutionArtifacts,
+};
 
 
 #[cfg(feature = "enable_serde")]
@@ -15,14 +17,58 @@ pub struct ComponentRunReport {
     pub timing: ComponentTimeResult,
     pub description: ComponentDescription,
     pub artifacts: ComponentRunArtifacts,
+
+    /// Each attempt made to run a retried component, in the order they were run.
+    /// Empty if the component was only run once.
+    #[cfg_attr(
+        feature = "enable_serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub attempts: Vec<ComponentAttemptReport>,
+}
+
+impl ComponentRunReport {
+    /// Returns the command line for rerunning only this component, or none if it did not fail.
+    /// Setups, tear downs and before/after each hooks are rerun by rerunning the suite they belong to.
+    /// Suites fail as a result of their children, so are rerun using the commands of their children.
+    pub fn rerun_command(&self) -> Option<String> {
+        if !self.result.has_failed() {
+            return None;
+        }
+
+        let path = match self.description.component_type() {
+            ComponentType::Suite => return None,
+            ComponentType::Test => self.description.path(),
+            _ => &self.description.parent_location().path,
+        };
+
+        let program = std::env::args().next().unwrap_or_default();
+        let program = match program.contains(char::is_whitespace) {
+            true => format!("\"{}\"", program),
+            false => program,
+        };
+        Some(format!("{} --filter {}", program, path))
+    }
+}
+
+/// The outcome of a single attempt to run a component
+#[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct ComponentAttemptReport {
+    pub result: ComponentResult,
+    pub timing: ComponentTimeResult,
+    pub artifacts: ComponentRunArtifacts,
 }
 
 pub struct ComponentReportBuilder {
     description: ComponentDescription,
     acceptance_criteria: AcceptanceCriteria,
+    deadline: Option<Instant>,
     timing: Option<ComponentTimeResult>,
     result: Option<ComponentResult>,
     artifacts: Option<ComponentRunArtifacts>,
+    warnings: Vec<String>,
+    attempts: Vec<ComponentAttemptReport>,
 }
 
 impl ComponentReportBuilder {
@@ -30,9 +76,12 @@ impl ComponentReportBuilder {
         Self {
             acceptance_criteria: acceptance_criteria,
             description: test_desc,
+            deadline: None,
             result: None,
             timing: None,
             artifacts: None,
+            warnings: Vec::new(),
+            attempts: Vec::new(),
         }
     }
 
@@ -56,17 +105,66 @@ impl ComponentReportBuilder {
         self.result = Some(ComponentResult::filtered());
     }
 
+    pub fn skipped_result(&mut self, reason: impl Into<String>) {
+        self.result = Some(ComponentResult::skipped(reason));
+    }
+
     pub fn timed_out_result(&mut self) {
         self.result = Some(ComponentResult::timed_out());
     }
 
+    pub fn run_time_limit_exceeded_result(&mut self) {
+        self.result = Some(ComponentResult::run_time_limit_exceeded());
+    }
+
+    /// Sets the instant the component must complete by, regardless of its own time limit.
+    /// This is the deadline of the suite it belongs to, if the suite has a time limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - The instant the component must complete by, or none if it has no deadline.
+    ///
+    pub fn with_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
     pub fn time_until_deadline(&self, duration: Duration) -> Option<Duration> {
-        match self.acceptance_criteria.timing.time_limit {
-            Some(time_limit) => Some(time_limit.saturating_sub(duration)),
-            None => None,
+        let time_limit = self
+            .acceptance_criteria
+            .timing
+            .time_limit
+            .map(|time_limit| time_limit.saturating_sub(duration));
+
+        let deadline = self
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+        match (time_limit, deadline) {
+            (Some(time_limit), Some(deadline)) => Some(time_limit.min(deadline)),
+            (time_limit, deadline) => time_limit.or(deadline),
         }
     }
 
+    /// Returns true if the current attempt has a result, and that result is a pass.
+    pub fn has_passed(&self) -> bool {
+        self.result
+            .as_ref()
+            .is_some_and(|result| result.has_passed())
+    }
+
+    /// Returns true if the deadline the component must complete by has passed.
+    pub fn has_exceeded_deadline(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Returns true if the time taken exceeds the component's time limit.
+    pub fn has_exceeded_time_limit(&self) -> bool {
+        self.timing
+            .as_ref()
+            .is_some_and(|timing| timing.is_critical())
+    }
+
     pub fn time_taken(&mut self, duration: Duration) {
         self.timing = Some(ComponentTimeResult::new(
             duration,
@@ -76,21 +174,63 @@ impl ComponentReportBuilder {
     }
 
     pub fn with_artifacts(&mut self, artifacts: &ExecutionArtifacts) {
+        // Warnings are taken first, as they are cleared when the artifacts are drained
+        self.warnings = artifacts.warnings();
         self.artifacts = Some(ComponentRunArtifacts::from_execution_artifacts(artifacts));
     }
 
+    /// Records the outcome of the current attempt to run the component, and returns its result.
+    pub fn record_attempt(&mut self) -> ComponentResult {
+        let result = self.build_attempt_result();
+        self.attempts.push(ComponentAttemptReport {
+            result: result.clone(),
+            timing: self.timing.clone().unwrap_or_else(ComponentTimeResult::zero),
+            artifacts: self.artifacts.clone().unwrap_or_else(ComponentRunArtifacts::new),
+        });
+        result
+    }
+
+    /// Clears the outcome of the current attempt, so the component can be run again.
+    /// Previously recorded attempts are kept.
+    pub fn reset_attempt(&mut self) {
+        self.result = None;
+        self.timing = None;
+        self.artifacts = None;
+        self.warnings = Vec::new();
+    }
+
     pub fn build(self) -> ComponentRunReport {
+        let result = self.build_result();
         ComponentRunReport {
-            result: self.build_result(),
+            result,
             timing: self.timing.unwrap_or_else(|| ComponentTimeResult::zero()),
             description: self.description,
             artifacts: self
                 .artifacts
                 .unwrap_or_else(|| ComponentRunArtifacts::new()),
+            attempts: match self.attempts.len() > 1 {
+                true => self.attempts,
+                false => Vec::new(),
+            },
         }
     }
 
     fn build_result(&self) -> ComponentResult {
+        let result = self.build_attempt_result();
+
+        // Components which raised warnings are flagged, even if they were retried
+        if result.has_passed() && !self.warnings.is_empty() {
+            return ComponentResult::user_warning();
+        }
+
+        // Flag components which only passed after being retried, so they are not mistaken for stable ones
+        match result.has_passed() && self.attempts.len() > 1 {
+            true => ComponentResult::passed_after_retry(),
+            false => result,
+        }
+    }
+
+    fn build_attempt_result(&self) -> ComponentResult {
         match &self.result {
             Some(r) => {
                 let mut result = r.clone();
@@ -99,7 +239,8 @@ impl ComponentReportBuilder {
                     result = ComponentResult::rejection_exempt();
                 }
 
-                if result.has_failed() {
+                // Components which skipped themselves are not subject to their time limits
+                if result.has_failed() || result.has_not_run() {
                     return result;
                 }
 
@@ -110,6 +110,8 @@ pub struct WarningResultsCountSummary {
     failure_allowed: usize,
     overtime_warning: usize,
     child_warning: usize,
+    passed_after_retry: usize,
+    user_warning: usize,
 }
 
 impl WarningResultsCountSummary {
@@ -118,6 +120,8 @@ impl WarningResultsCountSummary {
             failure_allowed: 0,
             overtime_warning: 0,
             child_warning: 0,
+            passed_after_retry: 0,
+            user_warning: 0,
         }
     }
 
@@ -126,6 +130,8 @@ impl WarningResultsCountSummary {
             WarningReason::FailureAllowed => self.failure_allowed += 1,
             WarningReason::OvertimeWarning => self.overtime_warning += 1,
             WarningReason::ChildWarning => self.child_warning += 1,
+            WarningReason::PassedAfterRetry => self.passed_after_retry += 1,
+            WarningReason::UserWarning => self.user_warning += 1,
         }
     }
 
@@ -140,6 +146,14 @@ impl WarningResultsCountSummary {
     pub fn child_warning(&self) -> usize {
         self.child_warning
     }
+
+    pub fn passed_after_retry(&self) -> usize {
+        self.passed_after_retry
+    }
+
+    pub fn user_warning(&self) -> usize {
+        self.user_warning
+    }
 }
 
 impl ResultReasonCounter for WarningResultsCountSummary {
@@ -149,6 +163,8 @@ impl ResultReasonCounter for WarningResultsCountSummary {
         self.failure_allowed
             .saturating_add(self.overtime_warning)
             .saturating_add(self.child_warning)
+            .saturating_add(self.passed_after_retry)
+            .saturating_add(self.user_warning)
     }
 
     fn by_reason(&self, reason: &WarningReason) -> usize {
@@ -156,6 +172,8 @@ impl ResultReasonCounter for WarningResultsCountSummary {
             WarningReason::FailureAllowed => self.failure_allowed,
             WarningReason::OvertimeWarning => self.overtime_warning,
             WarningReason::ChildWarning => self.child_warning,
+            WarningReason::PassedAfterRetry => self.passed_after_retry,
+            WarningReason::UserWarning => self.user_warning,
         }
     }
 }
@@ -165,6 +183,7 @@ pub struct FailResultsCountSummary {
     rejected: usize,
     timed_out: usize,
     child_failure: usize,
+    run_time_limit_exceeded: usize,
 }
 
 impl FailResultsCountSummary {
@@ -173,6 +192,7 @@ impl FailResultsCountSummary {
             rejected: 0,
             timed_out: 0,
             child_failure: 0,
+            run_time_limit_exceeded: 0,
         }
     }
 
@@ -181,6 +201,7 @@ impl FailResultsCountSummary {
             FailureReason::Rejected => self.rejected += 1,
             FailureReason::Overtime => self.timed_out += 1,
             FailureReason::ChildFailure => self.child_failure += 1,
+            FailureReason::RunTimeLimitExceeded => self.run_time_limit_exceeded += 1,
         }
     }
 
@@ -195,6 +216,10 @@ impl FailResultsCountSummary {
     pub fn child_failure(&self) -> usize {
         self.child_failure
     }
+
+    pub fn run_time_limit_exceeded(&self) -> usize {
+        self.run_time_limit_exceeded
+    }
 }
 
 impl ResultReasonCounter for FailResultsCountSummary {
@@ -204,6 +229,7 @@ impl ResultReasonCounter for FailResultsCountSummary {
         self.child_failure
             .saturating_add(self.timed_out)
             .saturating_add(self.rejected)
+            .saturating_add(self.run_time_limit_exceeded)
     }
 
     fn by_reason(&self, reason: &FailureReason) -> usize {
@@ -211,6 +237,7 @@ impl ResultReasonCounter for FailResultsCountSummary {
             FailureReason::Rejected => self.rejected,
             FailureReason::Overtime => self.timed_out,
             FailureReason::ChildFailure => self.child_failure,
+            FailureReason::RunTimeLimitExceeded => self.run_time_limit_exceeded,
         }
     }
 }
@@ -221,6 +248,9 @@ pub struct DidNotRunResultsCountSummary {
     undetermined: usize,
     filtered: usize,
     parent_failure: usize,
+    dependency_failure: usize,
+    skipped: usize,
+    run_aborted: usize,
 }
 
 impl DidNotRunResultsCountSummary {
@@ -230,6 +260,9 @@ impl DidNotRunResultsCountSummary {
             undetermined: 0,
             filtered: 0,
             parent_failure: 0,
+            dependency_failure: 0,
+            skipped: 0,
+            run_aborted: 0,
         }
     }
 
@@ -239,6 +272,9 @@ impl DidNotRunResultsCountSummary {
             DidNotRunReason::Filtered => self.filtered += 1,
             DidNotRunReason::Ignored => self.ignored += 1,
             DidNotRunReason::ParentFailure => self.parent_failure += 1,
+            DidNotRunReason::DependencyFailure => self.dependency_failure += 1,
+            DidNotRunReason::Skipped(_) => self.skipped += 1,
+            DidNotRunReason::RunAborted => self.run_aborted += 1,
         }
     }
 
@@ -256,6 +292,18 @@ impl DidNotRunResultsCountSummary {
     pub fn parent_failure(&self) -> usize {
         self.parent_failure
     }
+
+    pub fn dependency_failure(&self) -> usize {
+        self.dependency_failure
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    pub fn run_aborted(&self) -> usize {
+        self.run_aborted
+    }
 }
 
 impl ResultReasonCounter for DidNotRunResultsCountSummary {
@@ -266,6 +314,9 @@ impl ResultReasonCounter for DidNotRunResultsCountSummary {
             .saturating_add(self.undetermined)
             .saturating_add(self.filtered)
             .saturating_add(self.parent_failure)
+            .saturating_add(self.dependency_failure)
+            .saturating_add(self.skipped)
+            .saturating_add(self.run_aborted)
     }
 
     fn by_reason(&self, reason: &DidNotRunReason) -> usize {
@@ -274,6 +325,9 @@ impl ResultReasonCounter for DidNotRunResultsCountSummary {
             DidNotRunReason::Filtered => self.filtered,
             DidNotRunReason::Ignored => self.ignored,
             DidNotRunReason::ParentFailure => self.parent_failure,
+            DidNotRunReason::DependencyFailure => self.dependency_failure,
+            DidNotRunReason::Skipped(_) => self.skipped,
+            DidNotRunReason::RunAborted => self.run_aborted,
         }
     }
 }
@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::components::{ComponentDescription, ComponentPath};
+use crate::results::report::ComponentRunReport;
+use crate::results::{ComponentResult, DidNotRunReason, FailureReason};
+
+#[cfg(feature = "enable_serde")]
+use serde::{Deserialize, Serialize};
+
+/// The results of each component across several runs of the same schedule,
+/// as given by `--framework:repeat` or `--framework:repeat-until-failure`.
+/// Used to find components which only fail some of the time.
+#[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FlakinessSummary {
+    runs: usize,
+    components: BTreeMap<ComponentPath, ComponentFlakiness>,
+}
+
+impl FlakinessSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the results of a single run.
+    /// Components excluded by the test selection are not recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `reports` - The reports of the components of the run.
+    ///
+    pub fn record<'a>(&mut self, reports: impl IntoIterator<Item = &'a ComponentRunReport>) {
+        self.runs += 1;
+        for report in reports {
+            if report.result == ComponentResult::DidNotRun(DidNotRunReason::Filtered) {
+                continue;
+            }
+
+            self.components
+                .entry(report.description.path().clone())
+                .or_insert_with(|| ComponentFlakiness::new(report.description.clone()))
+                .record(report);
+        }
+    }
+
+    /// The number of runs which have been recorded
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    /// Returns the results of each component, ordered by their path.
+    pub fn components(&self) -> impl Iterator<Item = &'_ ComponentFlakiness> {
+        self.components.values()
+    }
+
+    /// Returns the results of the component with the given path, if it was run.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the component.
+    ///
+    pub fn component(&self, path: &ComponentPath) -> Option<&'_ ComponentFlakiness> {
+        self.components.get(path)
+    }
+}
+
+/// The results of a single component across several runs.
+#[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentFlakiness {
+    pub description: ComponentDescription,
+
+    /// The number of runs the component passed, including those which passed with a warning.
+    pub passed: usize,
+
+    /// The number of runs the component failed, excluding those where it timed out.
+    pub failed: usize,
+    pub timed_out: usize,
+
+    /// The number of runs the component did not run, for example as its suite failed.
+    pub skipped: usize,
+
+    pub min_duration: Option<Duration>,
+    pub median_duration: Option<Duration>,
+    pub max_duration: Option<Duration>,
+
+    /// The durations of the runs the component ran, ordered from shortest to longest
+    #[cfg_attr(feature = "enable_serde", serde(skip))]
+    durations: Vec<Duration>,
+}
+
+impl ComponentFlakiness {
+    pub fn new(description: ComponentDescription) -> Self {
+        Self {
+            description,
+            passed: 0,
+            failed: 0,
+            timed_out: 0,
+            skipped: 0,
+            min_duration: None,
+            median_duration: None,
+            max_duration: None,
+            durations: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, report: &ComponentRunReport) {
+        match &report.result {
+            ComponentResult::Pass(_) | ComponentResult::Warning(_) => self.passed += 1,
+            ComponentResult::Fail(FailureReason::Overtime)
+            | ComponentResult::Fail(FailureReason::RunTimeLimitExceeded) => self.timed_out += 1,
+            ComponentResult::Fail(_) => self.failed += 1,
+            ComponentResult::DidNotRun(_) => {
+                self.skipped += 1;
+                return;
+            }
+        }
+
+        let duration = report.timing.duration();
+        let i = self.durations.partition_point(|d| *d <= duration);
+        self.durations.insert(i, duration);
+
+        self.min_duration = self.durations.first().cloned();
+        self.max_duration = self.durations.last().cloned();
+        self.median_duration = Some(match self.durations.len() % 2 {
+            0 => {
+                let upper = self.durations.len() / 2;
+                (self.durations[upper - 1] + self.durations[upper]) / 2
+            }
+            _ => self.durations[self.durations.len() / 2],
+        });
+    }
+
+    /// The number of runs which have been recorded for this component
+    pub fn runs(&self) -> usize {
+        self.passed + self.failed + self.timed_out + self.skipped
+    }
+
+    /// Returns true if the component passed in some runs, and failed or timed out in others.
+    pub fn is_flaky(&self) -> bool {
+        self.passed > 0 && self.failed + self.timed_out > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{
+        AcceptanceCriteria, ComponentId, ComponentMetadata, ComponentTags, ComponentType,
+        TimingAcceptanceCriteria,
+    };
+    use crate::results::report::ComponentReportBuilder;
+
+    fn report(path: &'static str, result: ComponentResult, millis: u64) -> ComponentRunReport {
+        let mut location = crate::src_loc!();
+        location.path = ComponentPath::from(path);
+
+        let mut builder = ComponentReportBuilder::new(
+            ComponentDescription::new(
+                None,
+                ComponentId::from(1),
+                ComponentId::from(0),
+                location,
+                crate::src_loc!(),
+                None,
+                ComponentTags::new(),
+                ComponentMetadata::new(),
+                ComponentType::Test,
+            ),
+            AcceptanceCriteria {
+                allowed_fail: false,
+                timing: TimingAcceptanceCriteria {
+                    warning_time_limit: None,
+                    time_limit: None,
+                },
+            },
+        );
+        builder.with_result(result);
+        builder.time_taken(Duration::from_millis(millis));
+        builder.build()
+    }
+
+    #[test]
+    fn should_count_results_of_each_run() {
+        let mut summary = FlakinessSummary::new();
+
+        // Act
+        summary.record(&[
+            report("app::flaky", ComponentResult::passed(), 30),
+            report("app::filtered", ComponentResult::filtered(), 0),
+        ]);
+        summary.record(&[report("app::flaky", ComponentResult::rejected(), 10)]);
+        summary.record(&[report("app::flaky", ComponentResult::timed_out(), 50)]);
+        summary.record(&[report("app::flaky", ComponentResult::parent_failure(), 0)]);
+
+        // Assert
+        let flaky = summary
+            .component(&ComponentPath::from("app::flaky"))
+            .unwrap();
+        assert_eq!(summary.runs(), 4);
+        assert_eq!(summary.components().count(), 1);
+        assert_eq!(
+            (flaky.passed, flaky.failed, flaky.timed_out, flaky.skipped),
+            (1, 1, 1, 1)
+        );
+        assert!(flaky.is_flaky());
+    }
+
+    #[test]
+    fn should_summarize_durations_of_runs() {
+        let mut summary = FlakinessSummary::new();
+
+        // Act
+        for millis in [40, 10, 30, 20] {
+            summary.record(&[report("app::test", ComponentResult::passed(), millis)]);
+        }
+
+        // Assert
+        let test = summary
+            .component(&ComponentPath::from("app::test"))
+            .unwrap();
+        assert_eq!(test.min_duration, Some(Duration::from_millis(10)));
+        assert_eq!(test.median_duration, Some(Duration::from_millis(25)));
+        assert_eq!(test.max_duration, Some(Duration::from_millis(40)));
+        assert!(!test.is_flaky());
+    }
+}
@@ -9,6 +9,11 @@ pub use summary::SuiteSummary;
 #[doc(inline)]
 pub use summary::ComponentResultSummary;
 
+mod flakiness;
+
+#[doc(inline)]
+pub use flakiness::{ComponentFlakiness, FlakinessSummary};
+
 mod results_iter;
 
 #[doc(inline)]
@@ -292,6 +292,37 @@ impl<'a> WarningResults<'a> {
         self.due_to_reason(WarningReason::ChildWarning)
     }
 
+    /// Returns a iterator of only the waring results with an *passed after retry* reason.
+    ///
+    /// # Example of test with this result and reason:
+    ///
+    /// ```rust,ignore
+    /// static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// #[integration_test]
+    /// #[retry(times = 2)]
+    /// fn this_test_will_pass_on_its_second_attempt() {
+    ///    assert!(ATTEMPTS.fetch_add(1, Ordering::SeqCst) > 0);
+    /// }
+    ///```
+    pub fn due_to_passed_after_retry(self) -> WarningReasonResults<'a> {
+        self.due_to_reason(WarningReason::PassedAfterRetry)
+    }
+
+    /// Returns a iterator of only the waring results with an *user warning* reason.
+    ///
+    /// # Example of test with this result and reason:
+    ///
+    /// ```rust,ignore
+    /// #[integration_test]
+    /// fn this_test_will_pass_with_a_warning(ctx: crate::ExecutionContext) {
+    ///    ctx.warn("the response used a deprecated api");
+    /// }
+    ///```
+    pub fn due_to_user_warning(self) -> WarningReasonResults<'a> {
+        self.due_to_reason(WarningReason::UserWarning)
+    }
+
     /// Returns a iterator for only the warning results which matches the give warning reason
     ///
     /// # Arguments
@@ -580,6 +611,55 @@ impl<'a> NotRunResults<'a> {
         self.due_to_reason(DidNotRunReason::Ignored)
     }
 
+    /// Returns a iterator of only the not run results with a *dependency failure* reason.
+    /// These tests were not run, as a test they depend on failed.
+    ///
+    /// # Examples of tests with this result and reason:
+    ///
+    /// ```rust,ignore
+    /// #[integration_test]
+    /// fn create_order() {
+    ///     panic!("if this test fails");
+    /// }
+    ///
+    /// #[integration_test]
+    /// #[depends_on(create_order)]
+    /// fn cancel_order() {
+    ///     // then this test will never be run
+    /// }
+    ///```
+    pub fn due_to_dependency_failure(self) -> NotRunReasonResults<'a> {
+        self.due_to_reason(DidNotRunReason::DependencyFailure)
+    }
+
+    /// Returns a iterator of only the not run results with a *skipped* reason, regardless of the reason text.
+    /// These components were skipped by a condition evaluated against the parameters, or skipped themselves.
+    ///
+    /// # Examples of tests with this result and reason:
+    ///
+    /// ```rust,ignore
+    /// #[integration_test]
+    /// #[skip_if(!parameters.app.payments_enabled, "payments are disabled")]
+    /// fn refund_payment() {
+    ///
+    /// }
+    ///
+    /// #[integration_test]
+    /// fn cancel_payment(ctx: crate::ExecutionContext) {
+    ///     ctx.skip("payments can not be cancelled in this environment");
+    /// }
+    ///```
+    pub fn due_to_skipped(self) -> NotRunReasonResults<'a> {
+        self.due_to_reason(DidNotRunReason::Skipped(String::new()))
+    }
+
+    /// Returns a iterator of only the not run results with a *run aborted* reason.
+    /// These components had yet to start when the run reached its maximum number of failures,
+    /// as given by `--framework:fail-fast` or `--framework:max-failures`.
+    pub fn due_to_run_aborted(self) -> NotRunReasonResults<'a> {
+        self.due_to_reason(DidNotRunReason::RunAborted)
+    }
+
     /// Returns a iterator of only the not run results with a *undetermined* reason.
     /// This result should never be returned in normal operation and will only be assigned
     /// if a component was scheduled but never run.
@@ -649,7 +729,12 @@ impl<'a> Iterator for NotRunReasonResults<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         let filter_by_reason = self.filter_by_reason.clone();
         self.iter.find_map(|report| match &report.result {
-            ComponentResult::DidNotRun(reason) if reason == &filter_by_reason => Some(report),
+            // Compare only the kind of reason, so skipped results match regardless of their reason text
+            ComponentResult::DidNotRun(reason)
+                if std::mem::discriminant(reason) == std::mem::discriminant(&filter_by_reason) =>
+            {
+                Some(report)
+            }
             _ => None,
         })
     }
@@ -2,7 +2,7 @@ use std::collections::hash_map::Values;
 use std::collections::HashMap;
 
 use crate::results::report::ComponentRunReport;
-use crate::results::summary::ResultsCountSummary;
+use crate::results::summary::{FlakinessSummary, ResultsCountSummary};
 use crate::results::summary::{
     CompleteResults, FailedResults, NotRunResults, PassedResults, WarningResults,
 };
@@ -103,15 +103,39 @@ impl SuiteSummary {
 #[derive(Clone, Debug)]
 pub struct RunSummary {
     suite_summaries: HashMap<ComponentId, SuiteSummary>,
+    flakiness: Option<FlakinessSummary>,
+    shuffle_seed: Option<u64>,
 }
 
 impl RunSummary {
     pub fn new() -> Self {
         Self {
             suite_summaries: HashMap::new(),
+            flakiness: None,
+            shuffle_seed: None,
         }
     }
 
+    /// Returns the results of each component across all the runs, if the schedule was run more than once.
+    /// The rest of the summary is of the last run.
+    pub fn flakiness(&self) -> Option<&'_ FlakinessSummary> {
+        self.flakiness.as_ref()
+    }
+
+    pub fn with_flakiness(&mut self, flakiness: FlakinessSummary) {
+        self.flakiness = Some(flakiness);
+    }
+
+    /// Returns the seed the order of the tests and suites was shuffled with, if it was shuffled.
+    /// Passing the seed to `--framework:seed` replays the same order.
+    pub fn shuffle_seed(&self) -> Option<u64> {
+        self.shuffle_seed
+    }
+
+    pub fn with_shuffle_seed(&mut self, shuffle_seed: Option<u64>) {
+        self.shuffle_seed = shuffle_seed;
+    }
+
     pub fn suites<'a>(&'a self) -> Values<'a, ComponentId, SuiteSummary> {
         self.suite_summaries.values()
     }
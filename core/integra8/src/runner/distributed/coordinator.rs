@@ -0,0 +1,573 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use futures::channel::oneshot;
+
+use crate::components::ChildProcessComponentArgs;
+use crate::runner::distributed::{write_message, CoordinatorMessage, WorkerJob, WorkerJobComplete};
+use crate::runner::executor::{forwarded_args, ChildProcessOutcome};
+
+/// The number of workers a component is sent to before it is failed,
+/// when each worker disconnects before the component completes.
+const MAX_WORKER_ATTEMPTS: usize = 2;
+
+/// The duration a worker is given on top of the time out of a component to send its outcome,
+/// to allow for starting the child process and sending the outcome, before the worker is treated as disconnected.
+const WORKER_RESPONSE_MARGIN: Duration = Duration::from_secs(30);
+
+/// The duration the coordinator waits for a worker to connect, while none are connected,
+/// before the components waiting to be run are failed.
+const NO_WORKERS_TIME_OUT: Duration = Duration::from_secs(60);
+
+/// How often a component waiting to be run checks whether any workers are connected
+const NO_WORKERS_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How often the coordinator checks whether the result of the component a worker is running is still needed
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The coordinator of the current process, if components are run by remote workers.
+static CURRENT: RwLock<Option<Coordinator>> = RwLock::new(None);
+
+/// Dispatches components to the workers connected to it.
+/// Components are queued until a worker is free to run them, so workers may connect at any time during the run.
+#[derive(Clone)]
+pub struct Coordinator {
+    address: SocketAddr,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    changed: Condvar,
+    response_margin: Duration,
+    no_workers_time_out: Duration,
+}
+
+struct Queue {
+    jobs: VecDeque<PendingJob>,
+    next_id: u64,
+    shutdown: bool,
+    workers: usize,
+    workers_changed_at: Instant,
+}
+
+struct PendingJob {
+    job: WorkerJob,
+    attempts: usize,
+    reply: oneshot::Sender<Result<ChildProcessOutcome, String>>,
+}
+
+impl Coordinator {
+    /// Starts listening for workers on the given address.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address to listen on, for example `0.0.0.0:7878`.
+    ///
+    pub fn start(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Self::listen(address, WORKER_RESPONSE_MARGIN, NO_WORKERS_TIME_OUT)
+    }
+
+    fn listen(
+        address: impl ToSocketAddrs,
+        response_margin: Duration,
+        no_workers_time_out: Duration,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let coordinator = Self {
+            address: listener.local_addr()?,
+            shared: Arc::new(Shared {
+                queue: Mutex::new(Queue {
+                    jobs: VecDeque::new(),
+                    next_id: 0,
+                    shutdown: false,
+                    workers: 0,
+                    workers_changed_at: Instant::now(),
+                }),
+                changed: Condvar::new(),
+                response_margin,
+                no_workers_time_out,
+            }),
+        };
+
+        // The listener is closed once the thread returns, which `shutdown` wakes it to do by connecting to it
+        let shared = coordinator.shared.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if shared.queue.lock().unwrap().shutdown {
+                    return;
+                }
+                let shared = shared.clone();
+                std::thread::spawn(move || shared.serve(stream));
+            }
+        });
+        Ok(coordinator)
+    }
+
+    /// Returns the coordinator of the current process, if there is one.
+    pub fn current() -> Option<Self> {
+        CURRENT.read().unwrap().clone()
+    }
+
+    /// Sets the coordinator which components of the current process are dispatched to.
+    pub fn set_current(coordinator: Option<Self>) {
+        *CURRENT.write().unwrap() = coordinator;
+    }
+
+    /// The address workers connect to
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Runs a component on the next free worker, and returns the outcome of its child process.
+    /// Returns an error if each worker it was sent to disconnected before it completed,
+    /// or if no workers were connected for a minute while it waited to be run.
+    /// A worker which does not send the outcome within the time out plus a margin is treated as disconnected,
+    /// though a component with no time out waits on its worker until the connection is closed.
+    ///
+    /// If the returned future is dropped, for example once the run is aborted, the component is cancelled
+    /// and the worker kills its child process.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - The component to run.
+    ///
+    /// * `time_out` - The duration after which the worker kills the child process, or none if it has no time limit.
+    ///
+    pub async fn dispatch(
+        &self,
+        args: ChildProcessComponentArgs,
+        time_out: Option<Duration>,
+    ) -> Result<ChildProcessOutcome, String> {
+        let (reply, outcome) = oneshot::channel();
+        {
+            let mut queue = self.shared.queue.lock().unwrap();
+            let id = queue.next_id;
+            queue.next_id += 1;
+            queue.jobs.push_back(PendingJob {
+                job: WorkerJob {
+                    id,
+                    args,
+                    time_out,
+                    forwarded_args: forwarded_args(),
+                },
+                attempts: 0,
+                reply,
+            });
+        }
+        self.shared.changed.notify_one();
+
+        let mut outcome = outcome;
+        loop {
+            if let Ok(outcome) =
+                crate::async_runtime::timeout(NO_WORKERS_POLL_INTERVAL, &mut outcome).await
+            {
+                return outcome.unwrap_or_else(|_| {
+                    Err("the coordinator shut down before a worker ran the component".to_string())
+                });
+            }
+
+            // The component is dropped from the queue once this returns, as its reply is no longer needed
+            if self.shared.time_without_workers() >= Some(self.shared.no_workers_time_out) {
+                return Err(format!(
+                    "no workers connected to the coordinator at {} within {:?}",
+                    self.address, self.shared.no_workers_time_out
+                ));
+            }
+        }
+    }
+
+    /// Stops dispatching components, and disconnects all workers once they complete their current component.
+    /// Stops listening for workers, so the address can be listened on again.
+    pub fn shutdown(&self) {
+        {
+            let mut queue = self.shared.queue.lock().unwrap();
+            if queue.shutdown {
+                return;
+            }
+            queue.shutdown = true;
+        }
+        self.shared.changed.notify_all();
+
+        // Wake the thread accepting workers, so it sees the coordinator has shut down
+        let mut address = self.address;
+        if address.ip().is_unspecified() {
+            address.set_ip(match address.ip() {
+                IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            });
+        }
+        let _ = TcpStream::connect(address);
+    }
+}
+
+impl Shared {
+    /// Sends queued components to a connected worker, one at a time, until the worker disconnects
+    /// or the coordinator shuts down.
+    fn serve(&self, stream: TcpStream) {
+        self.set_workers(|workers| workers + 1);
+        self.serve_jobs(stream);
+        self.set_workers(|workers| workers - 1);
+    }
+
+    fn set_workers(&self, count: impl FnOnce(usize) -> usize) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.workers = count(queue.workers);
+        queue.workers_changed_at = Instant::now();
+    }
+
+    /// Returns the duration since the last worker disconnected, or since the coordinator started if none have connected,
+    /// or none if any workers are connected.
+    fn time_without_workers(&self) -> Option<Duration> {
+        let queue = self.queue.lock().unwrap();
+        match queue.workers {
+            0 => Some(queue.workers_changed_at.elapsed()),
+            _ => None,
+        }
+    }
+
+    fn serve_jobs(&self, stream: TcpStream) {
+        let mut reader = match stream.try_clone() {
+            Ok(stream) => BufReader::new(stream),
+            Err(_) => return,
+        };
+        let mut writer = stream;
+
+        while let Some(pending) = self.next_job() {
+            let outcome = write_message(
+                &mut writer,
+                &CoordinatorMessage::Run(Box::new(pending.job.clone())),
+            )
+            .and_then(|_| self.wait_for_outcome(&mut reader, &mut writer, &pending));
+
+            match outcome {
+                Ok(Some(outcome)) => {
+                    let _ = pending.reply.send(Ok(outcome));
+                }
+                // The component was cancelled, so no one is waiting for its outcome
+                Ok(None) => {}
+                Err(_) => {
+                    self.reschedule(pending);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Waits for the worker to send the outcome of the component it was sent.
+    /// Returns none if the component was cancelled, or an error if the worker disconnected
+    /// or did not send the outcome within the time out of the component plus the response margin.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The connection to read the outcome from.
+    ///
+    /// * `writer` - The connection to send the worker a cancellation on.
+    ///
+    /// * `pending` - The component the worker is running.
+    ///
+    fn wait_for_outcome(
+        &self,
+        reader: &mut BufReader<TcpStream>,
+        writer: &mut TcpStream,
+        pending: &PendingJob,
+    ) -> io::Result<Option<ChildProcessOutcome>> {
+        let deadline = pending
+            .job
+            .time_out
+            .map(|time_out| Instant::now() + time_out + self.response_margin);
+
+        // Reads time out periodically, so the worker can be told when the component is no longer needed.
+        // Any part of a message read before a read times out is kept in the buffer, until the rest of it is read.
+        reader
+            .get_ref()
+            .set_read_timeout(Some(CANCELLATION_POLL_INTERVAL))?;
+        let mut line = Vec::new();
+        loop {
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(_) => {
+                    let complete = serde_json::from_slice::<WorkerJobComplete>(&line)?;
+                    line.clear();
+
+                    // The outcome of a cancelled component may arrive after it was cancelled
+                    if complete.id == pending.job.id {
+                        return Ok(Some(complete.outcome));
+                    }
+                }
+                Err(e)
+                    if e.kind() == io::ErrorKind::WouldBlock
+                        || e.kind() == io::ErrorKind::TimedOut =>
+                {
+                    if pending.reply.is_canceled() {
+                        write_message(writer, &CoordinatorMessage::Cancel(pending.job.id))?;
+                        return Ok(None);
+                    }
+
+                    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            "the worker did not send the outcome of the component in time",
+                        ));
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Waits for the next queued component, or returns none once the coordinator shuts down.
+    /// Components which were cancelled while queued are dropped.
+    fn next_job(&self) -> Option<PendingJob> {
+        let mut queue = self.queue.lock().unwrap();
+        loop {
+            if queue.shutdown {
+                return None;
+            }
+            match queue.jobs.pop_front() {
+                Some(pending) if pending.reply.is_canceled() => continue,
+                Some(pending) => return Some(pending),
+                None => queue = self.changed.wait(queue).unwrap(),
+            }
+        }
+    }
+
+    /// Queues a component whose worker disconnected to be run by another worker,
+    /// or fails it if it has already been sent to too many workers.
+    fn reschedule(&self, mut pending: PendingJob) {
+        pending.attempts += 1;
+        if pending.attempts >= MAX_WORKER_ATTEMPTS {
+            let _ = pending.reply.send(Err(format!(
+                "{} workers disconnected while running the component",
+                pending.attempts
+            )));
+            return;
+        }
+
+        self.queue.lock().unwrap().jobs.push_front(pending);
+        self.changed.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{
+        ChildProcessComponentMetaArgs, ComponentDescription, ComponentId, ComponentMetadata,
+        ComponentTags, ComponentType, ConcurrencyMode, SuiteState, TestAttributes,
+    };
+    use crate::decorations::test_rigging::block_on;
+    use crate::runner::distributed::read_message;
+    use std::io::Write;
+
+    fn args() -> ChildProcessComponentArgs {
+        let description = ComponentDescription::new(
+            None,
+            ComponentId::from(1),
+            ComponentId::from(0),
+            crate::src_loc!(),
+            crate::src_loc!(),
+            None,
+            ComponentTags::new(),
+            ComponentMetadata::new(),
+            ComponentType::Test,
+        );
+
+        ChildProcessComponentArgs::Test {
+            meta: ChildProcessComponentMetaArgs::from_description(
+                description,
+                &SuiteState::new(),
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap(),
+            attributes: TestAttributes {
+                allow_fail: false,
+                ignore: false,
+                skip_if: None,
+                filtered: false,
+                warning_time_limit: Duration::from_secs(30),
+                time_limit: Duration::from_secs(60),
+                concurrency_mode: ConcurrencyMode::Sequential,
+                retry: None,
+                eventually: None,
+                depends_on: Vec::new(),
+            },
+            before_each: Vec::new(),
+            after_each: Vec::new(),
+        }
+    }
+
+    fn outcome() -> ChildProcessOutcome {
+        ChildProcessOutcome {
+            status_code: Some(0),
+            timed_out: false,
+            duration: Duration::from_millis(10),
+            stdout: b"hello".to_vec(),
+            stderr: Vec::new(),
+            suite_state: None,
+            skip_reason: None,
+            cancellation: None,
+        }
+    }
+
+    /// Connects to the coordinator, and reads the first component it is sent
+    fn connect_worker(coordinator: &Coordinator) -> (TcpStream, WorkerJob) {
+        let stream = TcpStream::connect(coordinator.address()).unwrap();
+        match read_next_message(&stream) {
+            CoordinatorMessage::Run(job) => (stream, *job),
+            message => panic!("Expected a component to run, but received {:?}", message),
+        }
+    }
+
+    fn read_next_message(stream: &TcpStream) -> CoordinatorMessage {
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        read_message::<CoordinatorMessage>(&mut reader)
+            .unwrap()
+            .unwrap()
+    }
+
+    fn send_outcome(stream: &mut TcpStream, job: &WorkerJob) {
+        let complete = WorkerJobComplete {
+            id: job.id,
+            outcome: outcome(),
+        };
+        write_message(stream, &complete).unwrap();
+        stream.flush().unwrap();
+    }
+
+    #[test]
+    fn should_reschedule_component_when_worker_disconnects() {
+        let coordinator = Coordinator::start("127.0.0.1:0").unwrap();
+        let workers = coordinator.clone();
+
+        // Act
+        let worker = std::thread::spawn(move || {
+            let (disconnected, _) = connect_worker(&workers);
+            drop(disconnected);
+
+            let (mut stream, job) = connect_worker(&workers);
+            send_outcome(&mut stream, &job);
+        });
+        let outcome = block_on(coordinator.dispatch(args(), None));
+        worker.join().unwrap();
+        coordinator.shutdown();
+
+        // Assert
+        assert_eq!(outcome.unwrap().stdout, b"hello".to_vec());
+    }
+
+    #[test]
+    fn should_stop_listening_once_shut_down() {
+        let coordinator = Coordinator::start("127.0.0.1:0").unwrap();
+
+        // Act
+        coordinator.shutdown();
+
+        // Assert
+        // The listener is closed by the thread accepting workers, once it is woken
+        let start_time = Instant::now();
+        while TcpListener::bind(coordinator.address()).is_err() {
+            assert!(
+                start_time.elapsed() < Duration::from_secs(5),
+                "The coordinator is still listening on {}",
+                coordinator.address()
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn should_reschedule_component_when_worker_does_not_respond_in_time() {
+        let coordinator =
+            Coordinator::listen("127.0.0.1:0", Duration::ZERO, NO_WORKERS_TIME_OUT).unwrap();
+        let workers = coordinator.clone();
+
+        // Act
+        let worker = std::thread::spawn(move || {
+            // The connection is kept open, but the outcome is never sent
+            let (unresponsive, _) = connect_worker(&workers);
+
+            let (mut stream, job) = connect_worker(&workers);
+            send_outcome(&mut stream, &job);
+            drop(unresponsive);
+        });
+        let outcome = block_on(coordinator.dispatch(args(), Some(Duration::from_millis(50))));
+        worker.join().unwrap();
+        coordinator.shutdown();
+
+        // Assert
+        assert_eq!(outcome.unwrap().stdout, b"hello".to_vec());
+    }
+
+    #[test]
+    fn should_fail_component_when_no_workers_connect() {
+        let coordinator =
+            Coordinator::listen("127.0.0.1:0", WORKER_RESPONSE_MARGIN, Duration::ZERO).unwrap();
+
+        // Act
+        let outcome = block_on(coordinator.dispatch(args(), None));
+        coordinator.shutdown();
+
+        // Assert
+        assert_eq!(
+            outcome.unwrap_err(),
+            format!(
+                "no workers connected to the coordinator at {} within 0ns",
+                coordinator.address()
+            )
+        );
+    }
+
+    #[test]
+    fn should_cancel_component_when_its_outcome_is_no_longer_needed() {
+        let coordinator = Coordinator::start("127.0.0.1:0").unwrap();
+        let workers = coordinator.clone();
+
+        // Act
+        let worker = std::thread::spawn(move || {
+            let (stream, job) = connect_worker(&workers);
+            (job.id, read_next_message(&stream))
+        });
+        let outcome = block_on(async {
+            crate::async_runtime::timeout(
+                Duration::from_millis(200),
+                coordinator.dispatch(args(), None),
+            )
+            .await
+        });
+        let (id, message) = worker.join().unwrap();
+        coordinator.shutdown();
+
+        // Assert
+        assert!(outcome.is_err());
+        assert!(matches!(message, CoordinatorMessage::Cancel(cancelled_id) if cancelled_id == id));
+    }
+
+    #[test]
+    fn should_fail_component_when_too_many_workers_disconnect() {
+        let coordinator = Coordinator::start("127.0.0.1:0").unwrap();
+        let workers = coordinator.clone();
+
+        // Act
+        let worker = std::thread::spawn(move || {
+            for _ in 0..MAX_WORKER_ATTEMPTS {
+                let (disconnected, _) = connect_worker(&workers);
+                drop(disconnected);
+            }
+        });
+        let outcome = block_on(coordinator.dispatch(args(), None));
+        worker.join().unwrap();
+        coordinator.shutdown();
+
+        // Assert
+        assert_eq!(
+            outcome.unwrap_err(),
+            "2 workers disconnected while running the component"
+        );
+    }
+}
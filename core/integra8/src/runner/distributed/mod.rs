@@ -0,0 +1,76 @@
+//! Runs components on remote workers.
+//!
+//! A coordinator process, started with `--coordinator <addr>`, runs the schedule as usual
+//! but dispatches each test, setup and tear down to a connected worker instead of running it itself.
+//! A worker process, being the same test binary started with `--worker <addr>`, connects to the coordinator
+//! and runs each component it is sent in a child process, the same way as `--framework:use-child-process`.
+//! The child process is given the command line of the coordinator rather than of the worker,
+//! so the component is run with the coordinator's parameters.
+//! The outcome of the child process is sent back to the coordinator, which reports it as usual.
+//! If the component's result is no longer needed, for example when the run is aborted, the coordinator
+//! tells the worker to cancel it, and the worker kills its child process.
+//!
+//! Messages are sent as a single line of JSON over TCP.
+
+mod coordinator;
+pub use coordinator::Coordinator;
+
+mod worker;
+pub use worker::run_worker;
+
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::components::ChildProcessComponentArgs;
+use crate::runner::executor::ChildProcessOutcome;
+
+/// A component sent to a worker to run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerJob {
+    pub id: u64,
+    pub args: ChildProcessComponentArgs,
+
+    /// The duration after which the worker kills the child process running the component,
+    /// or none if it has no time limit.
+    pub time_out: Option<Duration>,
+
+    /// The command line of the coordinator, which the child process is run with,
+    /// so the component is given the same parameters as when it is run by the coordinator itself.
+    pub forwarded_args: Vec<String>,
+}
+
+/// A message sent by the coordinator to a worker
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum CoordinatorMessage {
+    /// Runs a component
+    Run(Box<WorkerJob>),
+
+    /// Stops running the component with the given id, as its result is no longer needed
+    Cancel(u64),
+}
+
+/// The outcome of a component run by a worker
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerJobComplete {
+    pub id: u64,
+    pub outcome: ChildProcessOutcome,
+}
+
+fn write_message<T: Serialize>(writer: &mut impl Write, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())?;
+    writer.flush()
+}
+
+/// Reads the next message, or none if the connection was closed.
+fn read_message<T: DeserializeOwned>(reader: &mut impl BufRead) -> io::Result<Option<T>> {
+    let mut line = String::new();
+    match reader.read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(serde_json::from_str(&line)?)),
+    }
+}
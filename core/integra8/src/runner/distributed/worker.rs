@@ -0,0 +1,156 @@
+use std::io::{self, BufReader};
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+use futures::channel::mpsc;
+use futures::future::{select, Either};
+use futures::StreamExt;
+
+use crate::components::TestParameters;
+use crate::results::ComponentResult;
+use crate::runner::distributed::{
+    read_message, write_message, CoordinatorMessage, WorkerJobComplete,
+};
+use crate::runner::executor::{child_process_outputs, ChildProcessOutcome};
+use crate::scheduling::recommended_max_concurrency;
+
+/// The duration a worker retries connecting to its coordinator for, so workers can be started before the coordinator.
+const CONNECT_TIME_OUT: Duration = Duration::from_secs(30);
+
+/// Runs components for a coordinator until it disconnects.
+/// The worker opens one connection to the coordinator for each component it can run at the same time.
+///
+/// # Arguments
+///
+/// * `parameters` - The parameters of the worker process.
+///
+/// * `address` - The address of the coordinator.
+///
+pub async fn run_worker<TParameters: TestParameters>(
+    parameters: &TParameters,
+    address: &str,
+) -> ComponentResult {
+    let connections = match parameters.max_concurrency() {
+        0 => recommended_max_concurrency(),
+        max_concurrency => max_concurrency,
+    };
+
+    let mut streams = Vec::new();
+    for _ in 0..connections {
+        match connect(address).await {
+            Ok(stream) => streams.push(stream),
+            Err(err) => {
+                eprintln!("Failed to connect to coordinator at {}: {}", address, err);
+                return ComponentResult::rejected();
+            }
+        }
+    }
+
+    let results = futures::future::join_all(streams.into_iter().map(serve)).await;
+    match results.into_iter().collect::<io::Result<Vec<_>>>() {
+        Ok(_) => ComponentResult::passed(),
+        Err(err) => {
+            eprintln!("Lost connection to coordinator at {}: {}", address, err);
+            ComponentResult::rejected()
+        }
+    }
+}
+
+async fn connect(address: &str) -> io::Result<TcpStream> {
+    let start_time = Instant::now();
+    loop {
+        match TcpStream::connect(address) {
+            Ok(stream) => return Ok(stream),
+            Err(err) if start_time.elapsed() > CONNECT_TIME_OUT => return Err(err),
+            Err(_) => crate::async_runtime::sleep(Duration::from_millis(250)).await,
+        }
+    }
+}
+
+/// Runs each component sent over the connection in a child process, until the coordinator disconnects.
+async fn serve(stream: TcpStream) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let mut messages = read_messages(stream);
+
+    while let Some(message) = messages.next().await {
+        let job = match message? {
+            CoordinatorMessage::Run(job) => *job,
+            // The component completed before it could be cancelled
+            CoordinatorMessage::Cancel(_) => continue,
+        };
+
+        let (suite_state_output, skip_reason_output, cancellation_output) =
+            child_process_outputs(&job.args.meta().id);
+        let args =
+            job.args
+                .with_outputs(suite_state_output, skip_reason_output, cancellation_output);
+
+        // The child process is killed when it is dropped,
+        // so it is killed if the component is cancelled before it completes
+        let run = ChildProcessOutcome::run(&args, &job.forwarded_args, job.time_out);
+        let cancelled = wait_for_cancellation(&mut messages, job.id);
+        futures::pin_mut!(run);
+        futures::pin_mut!(cancelled);
+
+        match select(run, cancelled).await {
+            Either::Left((outcome, _)) => write_message(
+                &mut writer,
+                &WorkerJobComplete {
+                    id: job.id,
+                    outcome,
+                },
+            )?,
+            Either::Right((cancelled, _)) => cancelled?,
+        }
+    }
+    Ok(())
+}
+
+/// Reads each message sent by the coordinator on a thread of its own,
+/// so the worker can be told to cancel a component while it is running it.
+fn read_messages(stream: TcpStream) -> mpsc::UnboundedReceiver<io::Result<CoordinatorMessage>> {
+    let (sender, receiver) = mpsc::unbounded();
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        loop {
+            let message = match read_message::<CoordinatorMessage>(&mut reader) {
+                Ok(Some(message)) => Ok(message),
+                Ok(None) => return,
+                Err(e) => Err(e),
+            };
+
+            let failed = message.is_err();
+            if sender.unbounded_send(message).is_err() || failed {
+                return;
+            }
+        }
+    });
+    receiver
+}
+
+/// Waits until the coordinator cancels the component with the given id, or disconnects.
+///
+/// # Arguments
+///
+/// * `messages` - The messages sent by the coordinator.
+///
+/// * `id` - The id of the component being run.
+///
+async fn wait_for_cancellation(
+    messages: &mut mpsc::UnboundedReceiver<io::Result<CoordinatorMessage>>,
+    id: u64,
+) -> io::Result<()> {
+    while let Some(message) = messages.next().await {
+        match message? {
+            CoordinatorMessage::Cancel(cancelled_id) if cancelled_id == id => return Ok(()),
+            CoordinatorMessage::Cancel(_) => continue,
+            CoordinatorMessage::Run(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the coordinator sent a component before the last one completed",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
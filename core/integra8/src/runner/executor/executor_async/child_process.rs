@@ -1,119 +1,274 @@
 use std::panic::UnwindSafe;
-use std::time::Instant;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use async_process::{Command, Stdio};
+use serde::{Deserialize, Serialize};
 
-use crate::components::{ExecutionArtifacts, ChildProcessComponentArgs, ChildProcessComponentMetaArgs, ComponentType, TestParameters};
-use crate::results::ComponentResult;
+use crate::components::{ExecutionArtifacts, ChildProcessComponentArgs, ChildProcessComponentMetaArgs, ChildProcessHookArgs, ComponentId, ComponentType, SuiteState, TestParameters};
+use crate::results::{ComponentResult, DidNotRunReason};
 use crate::results::report::ComponentReportBuilder;
 use crate::runner::notify::ComponentProgressNotify;
 use crate::runner::ComponentFixture;
+use crate::runner::executor::executor_async::Cancellation;
 
 pub struct ChildProcessExecutor;
 
 impl ChildProcessExecutor
 {
     /// Executes a fixture it its own process and returns a populated report as a result
-    /// 
+    ///
     /// # Arguments
     ///
-    /// * `progress_notify` - progress observer 
-    /// 
+    /// * `progress_notify` - progress observer
+    ///
     /// * `fixture` - the fixture to be executed
     ///
-    /// * `report_builder` - a report builder, pre populated with test acceptance criteria  
+    /// * `report_builder` - a report builder, pre populated with test acceptance criteria
     ///
     pub async fn execute<
         TParameters: TestParameters + Send + Sync + UnwindSafe + 'static,
         ProgressNotify: ComponentProgressNotify + Send + Sync + 'static,
     >(&self,
-        progress_notify: ProgressNotify,
+        progress_notify: &ProgressNotify,
         fixture: ComponentFixture<TParameters>,
         mut report_builder: ComponentReportBuilder,
     ) -> ComponentReportBuilder {
 
-        let child_process_target_args = fixture_into_child_process_args(fixture).to_string().unwrap();
+        let state = fixture.state().clone();
+        let grace_period = fixture.cancellation_grace_period();
+        let time_out = child_process_time_out(&fixture, &report_builder);
+        let (suite_state_output, skip_reason_output, cancellation_output) = child_process_outputs(fixture.description().id());
+        let child_process_target_args = match fixture_into_child_process_args(fixture, suite_state_output, skip_reason_output, cancellation_output) {
+            Ok(args) => args,
+            Err(reason) => return rejected_before_start(report_builder, reason),
+        };
+
         progress_notify.notify_started().await;
+        let outcome = ChildProcessOutcome::run(&child_process_target_args, &forwarded_args(), time_out).await;
+
+        if outcome.timed_out {
+            progress_notify.notify_timed_out().await;
+        }
+
+        outcome.into_report(&mut report_builder, &state, grace_period);
+        report_builder
+    }
+}
+
+/// The outcome of running a component in a child process.
+/// Serializable, so a component can be run in a child process of a remote worker.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChildProcessOutcome {
+    /// The status code of the child process, or none if it was terminated by a signal or timed out.
+    pub status_code: Option<i32>,
+    pub timed_out: bool,
+    pub duration: Duration,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    /// The serialized suite state inserted by the component, if any.
+    pub suite_state: Option<String>,
+    /// The reason given by the component, if it skipped itself.
+    pub skip_reason: Option<String>,
+    /// How the component was stopped, if the child process cancelled it.
+    pub cancellation: Option<Cancellation>,
+}
+
+impl ChildProcessOutcome {
+    /// Runs a component in a child process of the current executable, and waits for it to complete.
+    /// The child process is killed if it does not complete within the given time out.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - the component to run, along with the files the child process writes its suite state, skip reason and cancellation to
+    ///
+    /// * `forwarded_args` - the command line of the root process, which the child process is run with
+    ///
+    /// * `time_out` - the duration after which the child process is killed, or none if it has no time limit
+    ///
+    pub async fn run(args: &ChildProcessComponentArgs, forwarded_args: &[String], time_out: Option<Duration>) -> Self {
         let start_time = Instant::now();
         let mut child_process = Command::new(std::env::current_exe().unwrap())
             .kill_on_drop(true)
             .arg("--internal:child-process-target")
-            .arg(child_process_target_args)
+            .arg(args.to_string().unwrap())
             // Replicate args passed to the original test runner
-            .args(std::env::args().skip(1))
+            .args(forwarded_args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()
             .unwrap();
 
-
-        let maybe_time_out = report_builder.time_until_deadline(start_time.elapsed());
-        let result = match maybe_time_out {
+        let result = match time_out {
             Some(time_out) => {
                 crate::async_runtime::timeout(time_out, child_process.status()).await
             }
             None => Ok(child_process.status().await),
         };
 
-        report_builder.time_taken(start_time.elapsed());
-
-        if let Err(_) = result {
-            progress_notify.notify_timed_out().await;
+        let duration = start_time.elapsed();
+        let timed_out = result.is_err();
 
+        if timed_out {
             // Make sure the process is killed if we timed out
             child_process.kill().unwrap();
         }
 
         let output = child_process.output().await.unwrap();
 
+        let meta = args.meta();
+        let suite_state = std::fs::read_to_string(&meta.suite_state_output).ok();
+        let _ = std::fs::remove_file(&meta.suite_state_output);
+        let skip_reason = std::fs::read_to_string(&meta.skip_reason_output).ok();
+        let _ = std::fs::remove_file(&meta.skip_reason_output);
+        let cancellation = std::fs::read_to_string(&meta.cancellation_output)
+            .ok()
+            .and_then(|serialized| serde_json::from_str(&serialized).ok());
+        let _ = std::fs::remove_file(&meta.cancellation_output);
+
+        Self {
+            // On Unix `output.status.code()` will be none if
+            // the process was terminated due to a timeout.
+            status_code: result.ok().and_then(|_| output.status.code()),
+            timed_out,
+            duration,
+            stdout: output.stdout,
+            stderr: output.stderr,
+            suite_state,
+            skip_reason,
+            cancellation,
+        }
+    }
+
+    /// Populates a report with the outcome of the child process,
+    /// and shares any suite state inserted by the component with the rest of its suite.
+    ///
+    /// # Arguments
+    ///
+    /// * `report_builder` - the report builder of the component
+    ///
+    /// * `state` - the state of the suite the component belongs to
+    ///
+    /// * `grace_period` - the duration the child process was given to stop once cancelled
+    ///
+    /// The cancellation written by the child process is reported if it cancelled the component,
+    /// otherwise the component is reported as killed if the child process timed out
+    pub fn into_report(self, report_builder: &mut ComponentReportBuilder, state: &SuiteState, grace_period: Duration) {
+        report_builder.time_taken(self.duration);
+
         let execution_artifacts = ExecutionArtifacts::new();
-        execution_artifacts.include_utf8_text_buffer("stdout", output.stdout);
-        execution_artifacts.include_utf8_text_buffer("stderr", output.stderr);
+        execution_artifacts.include_utf8_text_buffer("stdout", self.stdout);
+        execution_artifacts.include_utf8_text_buffer("stderr", self.stderr);
+        let cancellation = match (self.cancellation, self.timed_out) {
+            (Some(cancellation), _) => Some(cancellation),
+            (None, true) => Some(Cancellation::Killed(grace_period)),
+            (None, false) => None,
+        };
+        if let Some(cancellation) = cancellation {
+            execution_artifacts.include_text("cancellation", cancellation.to_string());
+        }
         report_builder.with_artifacts(&execution_artifacts);
 
-        if let Err(_) = result {
-            // On Unix `output.status.code()` will be none if 
-            // the process was terminated due to a timeout.
+        if let Some(serialized) = self.suite_state {
+            if let Ok(values) = serde_json::from_str(&serialized) {
+                state.extend_serialized(values);
+            }
+        }
+
+        if self.timed_out {
             report_builder.timed_out_result();
-        } else {
-            match output.status.code() {
-                Some(status) => {
-                    report_builder.with_result(ComponentResult::from_status_code(status));
-                },
-                None => {
-                    // On Unix, this will return None if the process was terminated by a signal.
-                    report_builder.rejected_result();
+            return;
+        }
+
+        match self.status_code {
+            Some(status) => {
+                match ComponentResult::from_status_code(status) {
+                    ComponentResult::DidNotRun(DidNotRunReason::Skipped(_)) => {
+                        report_builder.skipped_result(self.skip_reason.unwrap_or_default());
+                    },
+                    result => report_builder.with_result(result),
                 }
+            },
+            None => {
+                // On Unix, this will return None if the process was terminated by a signal.
+                report_builder.rejected_result();
             }
         }
+    }
+}
 
-       
+/// Returns the duration after which the child process running the fixture is killed, or none if it has no time limit.
+/// Hooks are run by the child process along with the test, so are given time to complete on top of the test's time limit.
+/// The child process is also given the grace period to stop the test once it is cancelled
+pub fn child_process_time_out<TParameters: TestParameters>(fixture: &ComponentFixture<TParameters>, report_builder: &ComponentReportBuilder) -> Option<Duration> {
+    report_builder
+        .time_until_deadline(Duration::ZERO)
+        .map(|time_out| time_out + fixture.hooks_time_limit() + fixture.cancellation_grace_period())
+}
 
-        report_builder
+/// Returns the command line of the current process, which child processes are run with so they are given the same parameters.
+/// The program name and the address to listen for workers on are left out, as child processes do not dispatch components to workers.
+pub fn forwarded_args() -> Vec<String> {
+    let mut forwarded_args = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--coordinator" => {
+                args.next();
+            }
+            _ if arg.starts_with("--coordinator=") => {}
+            _ => forwarded_args.push(arg),
+        }
     }
+    forwarded_args
 }
 
+/// Returns the files which the child process running the given component writes
+/// any suite state it inserts, the reason it skipped itself, and how it was stopped once cancelled to.
+pub fn child_process_outputs(id: &ComponentId) -> (PathBuf, PathBuf, PathBuf) {
+    let suite_state_output = std::env::temp_dir().join(format!(
+        "integra8-{}-{}.state.json",
+        std::process::id(),
+        id.as_unique_number()
+    ));
+    let skip_reason_output = suite_state_output.with_extension("skip.txt");
+    let cancellation_output = suite_state_output.with_extension("cancellation.json");
+    (suite_state_output, skip_reason_output, cancellation_output)
+}
+
+/// Returns a report of a component which was rejected before its child process could be started.
+pub fn rejected_before_start(mut report_builder: ComponentReportBuilder, reason: String) -> ComponentReportBuilder {
+    let execution_artifacts = ExecutionArtifacts::new();
+    execution_artifacts.include_text("stderr", reason);
+    report_builder.with_artifacts(&execution_artifacts);
+    report_builder.rejected_result();
+    report_builder
+}
 
-fn fixture_into_child_process_args<TParameters>(fixture: ComponentFixture<TParameters>) -> ChildProcessComponentArgs {
-    match fixture {
-        ComponentFixture::Test { test, .. } => {
+/// Returns the args for running the fixture in a child process,
+/// or an error if the state of its suite can not be serialized.
+pub fn fixture_into_child_process_args<TParameters>(fixture: ComponentFixture<TParameters>, suite_state_output: PathBuf, skip_reason_output: PathBuf, cancellation_output: PathBuf) -> Result<ChildProcessComponentArgs, String> {
+    Ok(match fixture {
+        ComponentFixture::Test { test, state, .. } => {
             ChildProcessComponentArgs::Test {
                 attributes: test.attributes,
-                meta: ChildProcessComponentMetaArgs::from_description(test.description)
+                meta: ChildProcessComponentMetaArgs::from_description(test.description, &state, suite_state_output, skip_reason_output, cancellation_output)?,
+                before_each: test.before_each.into_iter().map(ChildProcessHookArgs::from_hook).collect(),
+                after_each: test.after_each.into_iter().map(ChildProcessHookArgs::from_hook).collect(),
             }
         },
-        ComponentFixture::BookEnd { bookend, .. } => {
+        ComponentFixture::BookEnd { bookend, state, .. } => {
             match bookend.description.component_type() {
                 ComponentType::Setup => {
                     ChildProcessComponentArgs::Setup {
                         attributes: bookend.attributes,
-                        meta: ChildProcessComponentMetaArgs::from_description(bookend.description)
+                        meta: ChildProcessComponentMetaArgs::from_description(bookend.description, &state, suite_state_output, skip_reason_output, cancellation_output)?
                     }
                 },
                 ComponentType::TearDown => {
                     ChildProcessComponentArgs::TearDown {
                         attributes: bookend.attributes,
-                        meta: ChildProcessComponentMetaArgs::from_description(bookend.description)
+                        meta: ChildProcessComponentMetaArgs::from_description(bookend.description, &state, suite_state_output, skip_reason_output, cancellation_output)?
                     }
                 },
                 _ => {
@@ -125,5 +280,96 @@ fn fixture_into_child_process_args<TParameters>(fixture: ComponentFixture<TParam
         ComponentFixture::Suite { .. } => {
             panic!("Suites can not be run in a child process");
         }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{
+        AcceptanceCriteria, ComponentDescription, ComponentMetadata, ComponentTags,
+        TimingAcceptanceCriteria,
+    };
+    use crate::results::artifacts::OutputArtifact;
+
+    fn report_builder() -> ComponentReportBuilder {
+        ComponentReportBuilder::new(
+            ComponentDescription::new(
+                None,
+                ComponentId::from(1),
+                ComponentId::from(0),
+                crate::src_loc!(),
+                crate::src_loc!(),
+                None,
+                ComponentTags::new(),
+                ComponentMetadata::new(),
+                ComponentType::Test,
+            ),
+            AcceptanceCriteria {
+                allowed_fail: false,
+                timing: TimingAcceptanceCriteria {
+                    warning_time_limit: None,
+                    time_limit: None,
+                },
+            },
+        )
+    }
+
+    fn outcome(timed_out: bool, cancellation: Option<Cancellation>) -> ChildProcessOutcome {
+        ChildProcessOutcome {
+            status_code: None,
+            timed_out,
+            duration: Duration::from_millis(10),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+            suite_state: None,
+            skip_reason: None,
+            cancellation,
+        }
+    }
+
+    fn cancellation_artifact(outcome: ChildProcessOutcome) -> Option<String> {
+        let mut report_builder = report_builder();
+        outcome.into_report(&mut report_builder, &SuiteState::new(), Duration::from_secs(1));
+        match report_builder.build().artifacts.map.get("cancellation") {
+            Some(OutputArtifact::Text(text)) => Some(text.clone()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn should_report_cancellation_written_by_child_process() {
+        // Arrange
+        let outcome = outcome(false, Some(Cancellation::Stopped));
+
+        // Act
+        let cancellation = cancellation_artifact(outcome);
+
+        // Assert
+        assert_eq!(cancellation.unwrap(), "stopped cooperatively after being cancelled");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn should_report_child_process_killed_when_it_times_out_without_a_cancellation() {
+        // Arrange
+        let outcome = outcome(true, None);
+
+        // Act
+        let cancellation = cancellation_artifact(outcome);
+
+        // Assert
+        assert_eq!(cancellation.unwrap(), "killed after not stopping within the 1s grace period");
+    }
+
+    #[test]
+    fn should_not_report_cancellation_when_child_process_was_not_cancelled() {
+        // Arrange
+        let outcome = outcome(false, None);
+
+        // Act
+        let cancellation = cancellation_artifact(outcome);
+
+        // Assert
+        assert!(cancellation.is_none());
+    }
+}
@@ -4,10 +4,12 @@ use std::sync::Arc;
 
 use futures::FutureExt;
 
-use crate::components::{TestParameters, ExecutionArtifacts};
+use crate::components::{CancellationToken, TestParameters, ExecutionArtifacts};
+use crate::components::context::format_error;
 use crate::results::report::ComponentReportBuilder;
 use crate::runner::notify::ComponentProgressNotify;
 use crate::runner::ComponentFixture;
+use crate::runner::executor::executor_async::{report_panic, run_after_each, run_before_each, run_until_cancelled};
 
 pub struct CurrentThreadExecutor;
 
@@ -29,7 +31,7 @@ impl CurrentThreadExecutor
         ProgressNotify: ComponentProgressNotify + Send + Sync + 'static,
     >(
         &self,
-        progress_notify: ProgressNotify,
+        progress_notify: &ProgressNotify,
         fixture: ComponentFixture<TParameters>,
         mut report_builder: ComponentReportBuilder,
     ) -> ComponentReportBuilder {
@@ -37,34 +39,66 @@ impl CurrentThreadExecutor
         let execution_artifacts_test = execution_artifacts_local.clone();
         
         progress_notify.notify_started().await;
-        let start_time = Instant::now();
 
-        let may_panic = std::panic::AssertUnwindSafe(fixture.run(execution_artifacts_test)).catch_unwind();
+        let is_child_process = fixture.parameters().is_child_process();
 
-        let maybe_time_out = report_builder.time_until_deadline(start_time.elapsed());
+        // The test is only run if all of its before each hooks pass
+        if run_before_each(&fixture, &execution_artifacts_local, &mut report_builder).await {
+            let start_time = Instant::now();
+            let cancellation = CancellationToken::new();
+            let may_panic = std::panic::AssertUnwindSafe(fixture.run(execution_artifacts_test, cancellation.clone())).catch_unwind();
+            futures::pin_mut!(may_panic);
 
-        let result = match maybe_time_out {
-            Some(time_out) => crate::async_runtime::timeout(time_out, may_panic).await,
-            None => Ok(may_panic.await),
-        };
+            let maybe_time_out = report_builder.time_until_deadline(start_time.elapsed());
 
-        report_builder.time_taken(start_time.elapsed());
+            // The test is cancelled once it times out, and dropped if it does not stop within the grace period
+            let result = run_until_cancelled(may_panic, maybe_time_out, &cancellation, fixture.cancellation_grace_period()).await;
 
-        match result {
-            Ok(Ok(_)) => {
-                report_builder.passed_result();
-            }
+            report_builder.time_taken(start_time.elapsed());
+
+            match result {
+                Ok(Ok(Ok(_))) => {
+                    report_builder.passed_result();
+                }
+
+                // Returned an error
+                Ok(Ok(Err(error))) => {
+                    if is_child_process {
+                        // Artifacts are not returned from child processes,
+                        // so write the error to stderr as a panic would
+                        eprintln!("{}", format_error(error.as_ref()));
+                    }
+                    execution_artifacts_local.include_error("error", error.as_ref());
+                    report_builder.rejected_result();
+                }
 
-            // Panic
-            Ok(Err(panic)) => {
-                execution_artifacts_local.include_panic("panic", &panic);
-                report_builder.rejected_result();
+                // Panic
+                Ok(Err(panic)) => {
+                    report_panic(&execution_artifacts_local, &mut report_builder, panic);
+                }
+
+                // Timeout
+                Err(cancellation) => {     
+                    progress_notify.notify_timed_out().await;      
+                    if let Some(args) = fixture.parameters().child_process_target() {
+                        // Artifacts are not returned from child processes,
+                        // so write how the test was stopped for the parent process to report
+                        std::fs::write(&args.meta().cancellation_output, serde_json::to_string(&cancellation).unwrap()).unwrap();
+                    }
+                    execution_artifacts_local.include_text("cancellation", cancellation.to_string());
+                    report_builder.timed_out_result();
+                }
             }
+        }
+
+        // After each hooks are run even if the test failed or timed out
+        run_after_each(&fixture, &execution_artifacts_local, &mut report_builder).await;
 
-            // Timeout
-            Err(_) => {     
-                progress_notify.notify_timed_out().await;      
-                report_builder.timed_out_result();
+        if is_child_process {
+            // Artifacts are not returned from child processes,
+            // so write any warnings to stderr
+            for warning in execution_artifacts_local.warnings() {
+                eprintln!("warning: {}", warning);
             }
         }
 
@@ -4,10 +4,11 @@ use std::sync::Arc;
 
 use futures::FutureExt;
 
-use crate::components::{TestParameters, ExecutionArtifacts};
+use crate::components::{CancellationToken, TestParameters, ExecutionArtifacts};
 use crate::results::report::ComponentReportBuilder;
 use crate::runner::notify::ComponentProgressNotify;
 use crate::runner::ComponentFixture;
+use crate::runner::executor::executor_async::{report_panic, run_after_each, run_before_each, run_until_cancelled, Cancellation};
 
 pub struct GreenThreadExecutor;
 
@@ -28,7 +29,7 @@ impl GreenThreadExecutor
         ProgressNotify: ComponentProgressNotify + Send + Sync + 'static,
     >(
         &self,
-        progress_notify: ProgressNotify,
+        progress_notify: &ProgressNotify,
         fixture: ComponentFixture<TParameters>,
         mut report_builder: ComponentReportBuilder,
     ) -> ComponentReportBuilder {
@@ -36,46 +37,76 @@ impl GreenThreadExecutor
         let execution_artifacts_test = execution_artifacts_local.clone();
         
         progress_notify.notify_started().await;
-        let start_time = Instant::now();
 
-        let may_panic = crate::async_runtime::spawn(async move {
-            std::panic::AssertUnwindSafe(fixture.run(execution_artifacts_test)).catch_unwind().await
-        });
+        // The test is only run if all of its before each hooks pass
+        if run_before_each(&fixture, &execution_artifacts_local, &mut report_builder).await {
+            let test_fixture = fixture.clone();
+            let start_time = Instant::now();
+            let cancellation = CancellationToken::new();
+            let test_cancellation = cancellation.clone();
 
-        let maybe_time_out = report_builder.time_until_deadline(start_time.elapsed());
+            let mut may_panic = crate::async_runtime::spawn(async move {
+                std::panic::AssertUnwindSafe(test_fixture.run(execution_artifacts_test, test_cancellation)).catch_unwind().await
+            });
 
-        let result = match maybe_time_out {
-            Some(time_out) => crate::async_runtime::timeout(time_out, may_panic).await,
-            None => Ok(may_panic.await),
-        };
+            let maybe_time_out = report_builder.time_until_deadline(start_time.elapsed());
 
-        report_builder.time_taken(start_time.elapsed());
+            // The test is cancelled once it times out, and aborted if it does not stop within the grace period
+            let result = run_until_cancelled(&mut may_panic, maybe_time_out, &cancellation, fixture.cancellation_grace_period()).await;
+            if let Err(Cancellation::Killed(_)) = result {
+                crate::async_runtime::abort(may_panic).await;
+            }
 
-        match result {
-            // Panic
+            report_builder.time_taken(start_time.elapsed());
 
-            #[cfg(feature = "tokio-runtime")]
-            Ok(Ok(Err(panic))) => {
-                execution_artifacts_local.include_panic("panic", &panic);
-                report_builder.rejected_result();
-            }
+            match result {
+                // Panic
 
-            Ok(Err(panic)) => {
-                execution_artifacts_local.include_panic("panic", &panic);
-                report_builder.rejected_result();
-            }
+                #[cfg(feature = "tokio-runtime")]
+                Ok(Ok(Err(panic))) => {
+                    report_panic(&execution_artifacts_local, &mut report_builder, panic);
+                }
 
-            // Timeout
-            Err(_) => {     
-                progress_notify.notify_timed_out().await;      
-                report_builder.timed_out_result();
-            }
+                // Returned an error
+                #[cfg(feature = "tokio-runtime")]
+                Ok(Ok(Ok(Err(error)))) => {
+                    execution_artifacts_local.include_error("error", error.as_ref());
+                    report_builder.rejected_result();
+                }
+
+                #[cfg(not(feature = "tokio-runtime"))]
+                Ok(Ok(Err(error))) => {
+                    execution_artifacts_local.include_error("error", error.as_ref());
+                    report_builder.rejected_result();
+                }
 
-            Ok(Ok(_)) => {
-                report_builder.passed_result();
+                #[cfg(feature = "tokio-runtime")]
+                Ok(Err(panic)) => {
+                    execution_artifacts_local.include_panic("panic", &panic);
+                    report_builder.rejected_result();
+                }
+
+                #[cfg(not(feature = "tokio-runtime"))]
+                Ok(Err(panic)) => {
+                    report_panic(&execution_artifacts_local, &mut report_builder, panic);
+                }
+
+                // Timeout
+                Err(cancellation) => {     
+                    progress_notify.notify_timed_out().await;      
+                    execution_artifacts_local.include_text("cancellation", cancellation.to_string());
+                    report_builder.timed_out_result();
+                }
+
+                Ok(Ok(_)) => {
+                    report_builder.passed_result();
+                }
             }
         }
 
+        // After each hooks are run even if the test failed or timed out
+        run_after_each(&fixture, &execution_artifacts_local, &mut report_builder).await;
+
         report_builder.with_artifacts(&execution_artifacts_local);
         report_builder
     }
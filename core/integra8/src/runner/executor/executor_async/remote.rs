@@ -0,0 +1,72 @@
+use std::panic::UnwindSafe;
+
+use crate::components::{ExecutionArtifacts, TestParameters};
+use crate::results::report::ComponentReportBuilder;
+use crate::runner::distributed::Coordinator;
+use crate::runner::notify::ComponentProgressNotify;
+use crate::runner::ComponentFixture;
+use crate::runner::executor::executor_async::child_process::{child_process_outputs, child_process_time_out, fixture_into_child_process_args, rejected_before_start, ChildProcessExecutor};
+
+pub struct RemoteExecutor;
+
+impl RemoteExecutor
+{
+    /// Executes a fixture in a child process of a remote worker and returns a populated report as a result.
+    /// Falls back to running the fixture in a local child process if no coordinator has been started.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress_notify` - progress observer
+    ///
+    /// * `fixture` - the fixture to be executed
+    ///
+    /// * `report_builder` - a report builder, pre populated with test acceptance criteria
+    ///
+    pub async fn execute<
+        TParameters: TestParameters + Send + Sync + UnwindSafe + 'static,
+        ProgressNotify: ComponentProgressNotify + Send + Sync + 'static,
+    >(&self,
+        progress_notify: &ProgressNotify,
+        fixture: ComponentFixture<TParameters>,
+        mut report_builder: ComponentReportBuilder,
+    ) -> ComponentReportBuilder {
+
+        let coordinator = match Coordinator::current() {
+            Some(coordinator) => coordinator,
+            None => {
+                return ChildProcessExecutor
+                    .execute(progress_notify, fixture, report_builder)
+                    .await;
+            }
+        };
+
+        let state = fixture.state().clone();
+        let grace_period = fixture.cancellation_grace_period();
+        let time_out = child_process_time_out(&fixture, &report_builder);
+
+        // The worker replaces these with files of its own, as they are local to the machine running the child process
+        let (suite_state_output, skip_reason_output, cancellation_output) = child_process_outputs(fixture.description().id());
+        let child_process_target_args = match fixture_into_child_process_args(fixture, suite_state_output, skip_reason_output, cancellation_output) {
+            Ok(args) => args,
+            Err(reason) => return rejected_before_start(report_builder, reason),
+        };
+
+        progress_notify.notify_started().await;
+
+        match coordinator.dispatch(child_process_target_args, time_out).await {
+            Ok(outcome) => {
+                if outcome.timed_out {
+                    progress_notify.notify_timed_out().await;
+                }
+                outcome.into_report(&mut report_builder, &state, grace_period);
+            },
+            Err(reason) => {
+                let execution_artifacts = ExecutionArtifacts::new();
+                execution_artifacts.include_text("worker", reason);
+                report_builder.with_artifacts(&execution_artifacts);
+                report_builder.rejected_result();
+            }
+        }
+        report_builder
+    }
+}
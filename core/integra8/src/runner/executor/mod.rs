@@ -1,7 +1,18 @@
-use std::panic::UnwindSafe;
+use std::any::Any;
+use std::fmt;
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, UnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::components::{ExecutionStrategy, TestParameters};
+use futures::FutureExt;
+
+use crate::components::context::format_error;
+use crate::components::{
+    BookEnd, CancellationToken, ExecutionArtifacts, ExecutionStrategy, SkipRequest, TestParameters,
+};
 use crate::results::report::ComponentReportBuilder;
+use crate::results::ComponentResult;
 
 use crate::runner::notify::ComponentProgressNotify;
 use crate::runner::ComponentFixture;
@@ -9,20 +20,286 @@ use crate::runner::ComponentFixture;
 #[cfg(feature = "async")]
 pub use executor_async::execute;
 
+#[cfg(feature = "async")]
+pub use executor_async::child_process::{child_process_outputs, forwarded_args, ChildProcessOutcome};
+
 #[cfg(feature = "async")]
 mod executor_async {
     use super::*;
+    use serde::{Deserialize, Serialize};
 
     pub mod child_process;
     pub mod current_thread;
     pub mod green_thread;
+    pub mod remote;
 
+    /// Executes a fixture, re-running it if it fails and has a retry policy.
+    /// Each attempt is run with its own artifacts and is subject to the component's time limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress_notify` - progress observer
+    ///
+    /// * `fixture` - the fixture to be executed
+    ///
+    /// * `report_builder` - a report builder, pre populated with test acceptance criteria
+    ///
     pub async fn execute<
         TParameters: TestParameters + Send + Sync + UnwindSafe + 'static,
         ProgressNotify: ComponentProgressNotify + Send + Sync + 'static,
     >(
         progress_notify: ProgressNotify,
         fixture: ComponentFixture<TParameters>,
+        mut report_builder: ComponentReportBuilder,
+    ) -> ComponentReportBuilder {
+        let retry_policy = match fixture.parameters().is_child_process() {
+            // Retries are made by the parent process, so a child process only makes a single attempt
+            true => None,
+            false => fixture.retry_policy().cloned(),
+        };
+
+        let retry_policy = match retry_policy {
+            Some(retry_policy) => retry_policy,
+            None => return execute_attempt(&progress_notify, fixture, report_builder).await,
+        };
+
+        let mut retry = 0;
+        loop {
+            report_builder = execute_attempt(&progress_notify, fixture.clone(), report_builder).await;
+            let result = report_builder.record_attempt();
+
+            // Tests which allow failure are still retried
+            let failed = result.has_failed() || result == ComponentResult::rejection_exempt();
+            if !failed || retry >= retry_policy.times || report_builder.has_exceeded_deadline() {
+                return report_builder;
+            }
+
+            retry += 1;
+            report_builder.reset_attempt();
+            crate::async_runtime::sleep(retry_policy.delay_before(retry)).await;
+        }
+    }
+
+    /// Reports a component which panicked as rejected,
+    /// unless it unwound to skip itself, in which case it is reported as skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `artifacts` - the artifacts of the component, which the panic is included in
+    ///
+    /// * `report_builder` - the report builder to populate
+    ///
+    /// * `panic` - the payload the component unwound with
+    ///
+    pub fn report_panic(
+        artifacts: &ExecutionArtifacts,
+        report_builder: &mut ComponentReportBuilder,
+        panic: Box<dyn Any + Send>,
+    ) {
+        match SkipRequest::from_panic(panic.as_ref()) {
+            Some(reason) => report_builder.skipped_result(reason),
+            None => {
+                artifacts.include_panic("panic", &panic);
+                report_builder.rejected_result();
+            }
+        }
+    }
+
+    /// How a component which exceeded its time limit was stopped.
+    /// Serializable, so it can be written by a child process for its parent process to report.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub enum Cancellation {
+        /// The component returned within the grace period after it was cancelled.
+        Stopped,
+        /// The component did not return within the grace period, so was aborted.
+        Killed(Duration),
+    }
+
+    impl fmt::Display for Cancellation {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Stopped => write!(f, "stopped cooperatively after being cancelled"),
+                Self::Killed(grace_period) => write!(
+                    f,
+                    "killed after not stopping within the {:?} grace period",
+                    grace_period
+                ),
+            }
+        }
+    }
+
+    /// Awaits a component, cancelling it if it does not complete before it times out.
+    /// Once cancelled, the component is given the grace period to stop, after which it is no longer awaited.
+    ///
+    /// # Arguments
+    ///
+    /// * `future` - the future running the component
+    ///
+    /// * `time_out` - the duration after which the component is cancelled, or none if it has no time limit
+    ///
+    /// * `cancellation` - the token given to the component
+    ///
+    /// * `grace_period` - the duration the component is given to stop once cancelled
+    ///
+    pub async fn run_until_cancelled<F: Future + Unpin>(
+        mut future: F,
+        time_out: Option<Duration>,
+        cancellation: &CancellationToken,
+        grace_period: Duration,
+    ) -> Result<F::Output, Cancellation> {
+        let time_out = match time_out {
+            Some(time_out) => time_out,
+            None => return Ok(future.await),
+        };
+
+        if let Ok(output) = crate::async_runtime::timeout(time_out, &mut future).await {
+            return Ok(output);
+        }
+
+        cancellation.cancel();
+        match crate::async_runtime::timeout(grace_period, &mut future).await {
+            Ok(_) => Err(Cancellation::Stopped),
+            Err(_) => Err(Cancellation::Killed(grace_period)),
+        }
+    }
+
+    /// Runs the before each hooks of a test, in the order they are declared.
+    /// The remaining hooks and the test are not run if a hook fails.
+    /// Returns true if the test should be run, otherwise the report is populated with the outcome of the hook.
+    ///
+    /// # Arguments
+    ///
+    /// * `fixture` - the fixture of the test the hooks are wrapped around
+    ///
+    /// * `artifacts` - the artifacts of the test, which the failure of a hook is included in
+    ///
+    /// * `report_builder` - the report builder of the test
+    ///
+    pub async fn run_before_each<
+        TParameters: TestParameters + Send + Sync + UnwindSafe + 'static,
+    >(
+        fixture: &ComponentFixture<TParameters>,
+        artifacts: &Arc<ExecutionArtifacts>,
+        report_builder: &mut ComponentReportBuilder,
+    ) -> bool {
+        for hook in fixture.before_each() {
+            match run_hook(fixture, hook, "before_each", artifacts).await {
+                HookOutcome::Passed => {}
+                HookOutcome::Failed => {
+                    report_builder.rejected_result();
+                    return false;
+                }
+                HookOutcome::Skipped(reason) => {
+                    report_builder.skipped_result(reason);
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Runs the after each hooks of a test, in the order they are declared.
+    /// All the hooks are run, regardless of the outcome of the test or the other hooks.
+    /// A test which passed is rejected if any of its hooks fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `fixture` - the fixture of the test the hooks are wrapped around
+    ///
+    /// * `artifacts` - the artifacts of the test, which the failure of a hook is included in
+    ///
+    /// * `report_builder` - the report builder of the test
+    ///
+    pub async fn run_after_each<
+        TParameters: TestParameters + Send + Sync + UnwindSafe + 'static,
+    >(
+        fixture: &ComponentFixture<TParameters>,
+        artifacts: &Arc<ExecutionArtifacts>,
+        report_builder: &mut ComponentReportBuilder,
+    ) {
+        let mut failed = false;
+        for hook in fixture.after_each() {
+            // A hook which skips itself has nothing left to clean up
+            if let HookOutcome::Failed = run_hook(fixture, hook, "after_each", artifacts).await {
+                failed = true;
+            }
+        }
+
+        if failed && report_builder.has_passed() {
+            report_builder.rejected_result();
+        }
+    }
+
+    enum HookOutcome {
+        Passed,
+        Failed,
+        Skipped(String),
+    }
+
+    /// Runs a single hook subject to its own time limit.
+    /// Any error, panic or time out of the hook is included in the artifacts of the test.
+    async fn run_hook<TParameters: TestParameters + Send + Sync + UnwindSafe + 'static>(
+        fixture: &ComponentFixture<TParameters>,
+        hook: &BookEnd<TParameters>,
+        hook_kind: &str,
+        artifacts: &Arc<ExecutionArtifacts>,
+    ) -> HookOutcome {
+        if hook.attributes.ignore {
+            return HookOutcome::Passed;
+        }
+
+        let name = format!("{} {}", hook_kind, hook.description.friendly_name());
+        let is_child_process = fixture.parameters().is_child_process();
+        let cancellation = CancellationToken::new();
+        let may_panic =
+            AssertUnwindSafe(fixture.run_hook(hook, artifacts.clone(), cancellation.clone()))
+                .catch_unwind();
+        futures::pin_mut!(may_panic);
+
+        let result = run_until_cancelled(
+            may_panic,
+            Some(hook.attributes.time_limit),
+            &cancellation,
+            fixture.cancellation_grace_period(),
+        )
+        .await;
+
+        match result {
+            Ok(Ok(Ok(_))) => return HookOutcome::Passed,
+            Ok(Ok(Err(error))) => {
+                if is_child_process {
+                    // Artifacts are not returned from child processes,
+                    // so write the error to stderr as a panic would
+                    eprintln!("{}: {}", name, format_error(error.as_ref()));
+                }
+                artifacts.include_error(name, error.as_ref());
+            }
+            Ok(Err(panic)) => {
+                if let Some(reason) = SkipRequest::from_panic(panic.as_ref()) {
+                    return HookOutcome::Skipped(reason.to_string());
+                }
+                artifacts.include_panic(name, &panic);
+            }
+            Err(cancellation) => {
+                let message = format!(
+                    "timed out after {:?}, and {}",
+                    hook.attributes.time_limit, cancellation
+                );
+                if is_child_process {
+                    eprintln!("{}: {}", name, message);
+                }
+                artifacts.include_text(name, message);
+            }
+        }
+        HookOutcome::Failed
+    }
+
+    async fn execute_attempt<
+        TParameters: TestParameters + Send + Sync + UnwindSafe + 'static,
+        ProgressNotify: ComponentProgressNotify + Send + Sync + 'static,
+    >(
+        progress_notify: &ProgressNotify,
+        fixture: ComponentFixture<TParameters>,
         report_builder: ComponentReportBuilder,
     ) -> ComponentReportBuilder {
         match fixture.execution_strategy() {
@@ -31,6 +308,11 @@ mod executor_async {
                     .execute(progress_notify, fixture, report_builder)
                     .await
             }
+            ExecutionStrategy::Remote => {
+                executor_async::remote::RemoteExecutor
+                    .execute(progress_notify, fixture, report_builder)
+                    .await
+            }
             ExecutionStrategy::CurrentThread => {
                 executor_async::current_thread::CurrentThreadExecutor
                     .execute(progress_notify, fixture, report_builder)
@@ -1,55 +1,122 @@
+use futures::FutureExt;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::components::{
-    AcceptanceCriteria, BookEnd, ComponentDescription, ComponentPath, ExecutionArtifacts,
-    ExecutionContext, ExecutionStrategy, SuiteAttributes, Test, TestParameters,
+    AcceptanceCriteria, BookEnd, CancellationToken, ComponentDescription, ComponentPath, Delegate,
+    EventuallyPolicy, ExecutionArtifacts, ExecutionContext, ExecutionStrategy, RetryPolicy,
+    SkipCondition, SkipRequest, SuiteAttributes, SuiteState, Test, TestParameters,
 };
 
+use crate::components::delegates::DelegateResult;
+use crate::runner::panic_output::PanicOutput;
 use crate::scheduling::ScheduledComponent;
 
 pub enum ComponentFixture<TParameters> {
     Test {
         test: Test<TParameters>,
         parameters: Arc<TParameters>,
+        state: SuiteState,
     },
     BookEnd {
         bookend: BookEnd<TParameters>,
         parameters: Arc<TParameters>,
+        state: SuiteState,
     },
     Suite {
         description: ComponentDescription,
         attributes: SuiteAttributes,
         parameters: Arc<TParameters>,
+        state: SuiteState,
     },
 }
 
+// Implemented by hand, as derive would require `TParameters` to be `Clone`
+impl<TParameters> Clone for ComponentFixture<TParameters> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Test {
+                test,
+                parameters,
+                state,
+            } => Self::Test {
+                test: test.clone(),
+                parameters: parameters.clone(),
+                state: state.clone(),
+            },
+            Self::BookEnd {
+                bookend,
+                parameters,
+                state,
+            } => Self::BookEnd {
+                bookend: bookend.clone(),
+                parameters: parameters.clone(),
+                state: state.clone(),
+            },
+            Self::Suite {
+                description,
+                attributes,
+                parameters,
+                state,
+            } => Self::Suite {
+                description: description.clone(),
+                attributes: attributes.clone(),
+                parameters: parameters.clone(),
+                state: state.clone(),
+            },
+        }
+    }
+}
+
 impl<TParameters: TestParameters> ComponentFixture<TParameters> {
+    /// Creates a fixture for the given component
+    ///
+    /// # Arguments
+    ///
+    /// * `scheduled_component` - The component to be executed
+    ///
+    /// * `parameters` - The parameter type as defined by the test author.
+    ///
+    /// * `state` - The state of the suite the component belongs to, or the suite's own state if the component is a suite.
+    ///
     pub fn from_scheduled_component(
         scheduled_component: ScheduledComponent<TParameters>,
         parameters: Arc<TParameters>,
+        state: SuiteState,
     ) -> Self {
         match scheduled_component {
-            ScheduledComponent::Test(c) => Self::for_test(c, parameters),
+            ScheduledComponent::Test(c) => Self::for_test(c, parameters, state),
             ScheduledComponent::Setup(c) | ScheduledComponent::TearDown(c) => {
-                Self::for_bookend(c, parameters)
+                Self::for_bookend(c, parameters, state)
             }
             ScheduledComponent::Suite(description, attributes) => {
-                Self::for_suite(description, attributes, parameters)
+                Self::for_suite(description, attributes, parameters, state)
             }
         }
     }
 
-    pub fn for_test(test: Test<TParameters>, parameters: Arc<TParameters>) -> Self {
+    pub fn for_test(
+        test: Test<TParameters>,
+        parameters: Arc<TParameters>,
+        state: SuiteState,
+    ) -> Self {
         Self::Test {
             test: test,
             parameters: parameters,
+            state,
         }
     }
 
-    pub fn for_bookend(bookend: BookEnd<TParameters>, parameters: Arc<TParameters>) -> Self {
+    pub fn for_bookend(
+        bookend: BookEnd<TParameters>,
+        parameters: Arc<TParameters>,
+        state: SuiteState,
+    ) -> Self {
         Self::BookEnd {
             bookend: bookend,
             parameters: parameters,
+            state,
         }
     }
 
@@ -57,49 +124,179 @@ impl<TParameters: TestParameters> ComponentFixture<TParameters> {
         description: ComponentDescription,
         attributes: SuiteAttributes,
         parameters: Arc<TParameters>,
+        state: SuiteState,
     ) -> Self {
         Self::Suite {
             description: description,
             attributes: attributes,
             parameters: parameters,
+            state,
         }
     }
 
-    pub async fn run(&self, artifacts: Arc<ExecutionArtifacts>) {
+    pub async fn run(
+        &self,
+        artifacts: Arc<ExecutionArtifacts>,
+        cancellation: CancellationToken,
+    ) -> DelegateResult
+    where
+        TParameters: Send + Sync + 'static,
+    {
         match self {
-            Self::Test { test, .. } => match test.test_fn.requires_parameters() {
-                true => {
-                    test.test_fn
-                        .run_async(self.execution_context(artifacts))
+            Self::Test { test, .. } => match &test.attributes.eventually {
+                Some(eventually) => {
+                    self.run_eventually(&test.test_fn, eventually, artifacts, cancellation)
                         .await
                 }
-                false => test.test_fn.run_async_without_parameters().await,
-            },
-            Self::BookEnd { bookend, .. } => match bookend.bookend_fn.requires_parameters() {
-                true => {
-                    bookend
-                        .bookend_fn
-                        .run_async(self.execution_context(artifacts))
-                        .await;
-                }
-                false => {
-                    bookend.bookend_fn.run_async_without_parameters().await;
+                None => {
+                    self.run_delegate(&test.test_fn, artifacts, cancellation)
+                        .await
                 }
             },
+            Self::BookEnd { bookend, .. } => {
+                self.run_delegate(&bookend.bookend_fn, artifacts, cancellation)
+                    .await
+            }
             Self::Suite { .. } => {
                 // Can not run
+                Ok(())
             }
         }
     }
 
+    /// Runs a before or after each hook of a test.
+    /// The hook is run with the execution context of the test it is wrapped around.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The hook to run.
+    ///
+    /// * `artifacts` - The artifacts of the test.
+    ///
+    /// * `cancellation` - Cancelled when the hook should stop.
+    ///
+    pub async fn run_hook(
+        &self,
+        hook: &BookEnd<TParameters>,
+        artifacts: Arc<ExecutionArtifacts>,
+        cancellation: CancellationToken,
+    ) -> DelegateResult
+    where
+        TParameters: Send + Sync + 'static,
+    {
+        self.run_delegate(&hook.bookend_fn, artifacts, cancellation)
+            .await
+    }
+
+    /// Runs a delegate, with sync delegates run on the blocking thread pool.
+    /// This allows other components to make progress, and time limits to be enforced,
+    /// while a sync delegate blocks its thread.
+    async fn run_delegate(
+        &self,
+        delegate: &Delegate<TParameters>,
+        artifacts: Arc<ExecutionArtifacts>,
+        cancellation: CancellationToken,
+    ) -> DelegateResult
+    where
+        TParameters: Send + Sync + 'static,
+    {
+        if delegate.is_sync() {
+            let delegate = delegate.clone();
+            let ctx = self.execution_context(artifacts, cancellation);
+
+            // Panic output captured by the calling task is also captured on the blocking thread
+            let panic_output = PanicOutput::current();
+            return crate::async_runtime::spawn_blocking(move || {
+                let run = || match delegate.requires_parameters() {
+                    true => delegate.run_sync(ctx),
+                    false => delegate.run_sync_without_parameters(),
+                };
+                match panic_output {
+                    Some(panic_output) => panic_output.capture(run),
+                    None => run(),
+                }
+            })
+            .await;
+        }
+
+        match delegate.requires_parameters() {
+            true => {
+                delegate
+                    .run_async(self.execution_context(artifacts, cancellation))
+                    .await
+            }
+            false => delegate.run_async_without_parameters().await,
+        }
+    }
+
+    /// Polls the delegate every interval until it passes, or the policy's timeout is reached.
+    /// Only the artifacts of the last poll are kept, along with the number of polls made.
+    /// The panic output of each poll is held back, and only printed for the last poll.
+    async fn run_eventually(
+        &self,
+        delegate: &Delegate<TParameters>,
+        eventually: &EventuallyPolicy,
+        artifacts: Arc<ExecutionArtifacts>,
+        cancellation: CancellationToken,
+    ) -> DelegateResult
+    where
+        TParameters: Send + Sync + 'static,
+    {
+        let start_time = Instant::now();
+        let mut polls: usize = 0;
+
+        loop {
+            polls += 1;
+
+            // Discard the artifacts of the previous poll
+            artifacts.drain();
+            artifacts.include_value("polls", polls);
+
+            // Hold back the panic output, as the poll is discarded if the delegate is polled again
+            let panic_output = PanicOutput::new();
+            let result = AssertUnwindSafe(panic_output.capture_async(self.run_delegate(
+                delegate,
+                artifacts.clone(),
+                cancellation.clone(),
+            )))
+            .catch_unwind()
+            .await;
+
+            let passed = matches!(result, Ok(Ok(())));
+
+            // A test which skips itself is not polled again
+            let skipped =
+                matches!(&result, Err(panic) if SkipRequest::from_panic(panic.as_ref()).is_some());
+
+            // A test which has been cancelled is not polled again
+            if passed
+                || skipped
+                || cancellation.is_cancelled()
+                || !eventually.can_poll_again(start_time.elapsed())
+            {
+                panic_output.print();
+                return match result {
+                    Ok(result) => result,
+                    // Resume the last poll's panic, so it is reported the same way as a test which is run once
+                    Err(panic) => std::panic::resume_unwind(panic),
+                };
+            }
+
+            crate::async_runtime::sleep(eventually.interval).await;
+        }
+    }
+
     pub fn execution_context(
         &self,
         artifacts: Arc<ExecutionArtifacts>,
+        cancellation: CancellationToken,
     ) -> ExecutionContext<TParameters> {
         ExecutionContext {
             parameters: self.parameters(),
             description: self.description().clone(),
             artifacts: artifacts,
+            state: self.state().clone(),
+            cancellation,
         }
     }
 
@@ -139,6 +336,15 @@ impl<TParameters: TestParameters> ComponentFixture<TParameters> {
         }
     }
 
+    /// Returns the state of the suite the component belongs to, or the suite's own state if the component is a suite.
+    pub fn state(&self) -> &'_ SuiteState {
+        match self {
+            Self::Test { state, .. } => state,
+            Self::BookEnd { state, .. } => state,
+            Self::Suite { state, .. } => state,
+        }
+    }
+
     pub fn execution_strategy(&self) -> ExecutionStrategy {
         match self {
             Self::Test { parameters, .. } => parameters.execution_strategy(),
@@ -155,6 +361,56 @@ impl<TParameters: TestParameters> ComponentFixture<TParameters> {
         }
     }
 
+    /// Returns how the component is re-run if it fails, or none if it is not retried.
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        match self {
+            Self::Test { test, .. } => test.attributes.retry.as_ref(),
+            Self::BookEnd { bookend, .. } => bookend.attributes.retry.as_ref(),
+            Self::Suite { .. } => None,
+        }
+    }
+
+    /// Returns the hooks run before this component, which is empty if the component is not a test.
+    pub fn before_each(&self) -> &'_ [BookEnd<TParameters>] {
+        match self {
+            Self::Test { test, .. } => &test.before_each,
+            _ => &[],
+        }
+    }
+
+    /// Returns the hooks run after this component, which is empty if the component is not a test.
+    pub fn after_each(&self) -> &'_ [BookEnd<TParameters>] {
+        match self {
+            Self::Test { test, .. } => &test.after_each,
+            _ => &[],
+        }
+    }
+
+    /// Returns the sum of the time limits of the hooks wrapped around this component,
+    /// including the grace period each hook is given to stop once cancelled.
+    pub fn hooks_time_limit(&self) -> Duration {
+        let grace_period = self.cancellation_grace_period();
+        self.before_each()
+            .iter()
+            .chain(self.after_each())
+            .filter(|hook| !hook.attributes.ignore)
+            .map(|hook| hook.attributes.time_limit + grace_period)
+            .sum()
+    }
+
+    /// Returns the duration the component is given to stop once cancelled, before it is aborted.
+    pub fn cancellation_grace_period(&self) -> Duration {
+        self.parameters().cancellation_grace_period_duration()
+    }
+
+    /// Returns the paths of the tests which must complete before this component is run.
+    pub fn depends_on(&self) -> &'_ [ComponentPath] {
+        match self {
+            Self::Test { test, .. } => &test.attributes.depends_on,
+            _ => &[],
+        }
+    }
+
     pub fn ignore(&self) -> bool {
         match self {
             Self::Test { test, .. } => {
@@ -169,6 +425,46 @@ impl<TParameters: TestParameters> ComponentFixture<TParameters> {
         }
     }
 
+    /// Returns the reason this component is skipped, or none if it has no skip condition or its condition is not met.
+    pub fn skip_reason(&self) -> Option<String>
+    where
+        TParameters: 'static,
+    {
+        let skip_if: Option<&SkipCondition> = match self {
+            Self::Test { test, .. } => test.attributes.skip_if.as_ref(),
+            Self::BookEnd { bookend, .. } => bookend.attributes.skip_if.as_ref(),
+            Self::Suite { attributes, .. } => attributes.skip_if.as_ref(),
+        };
+        skip_if.and_then(|skip_if| skip_if.evaluate(self.parameters().as_ref()))
+    }
+
+    pub fn filtered(&self) -> bool {
+        match self {
+            Self::Test { test, .. } => test.attributes.filtered,
+            Self::BookEnd { bookend, .. } => bookend.attributes.filtered,
+            Self::Suite { attributes, .. } => attributes.filtered,
+        }
+    }
+
+    /// Returns the maximum duration of a suite, or none if the component is not a suite or it has no time limit.
+    pub fn suite_time_limit(&self) -> Option<Duration> {
+        match self {
+            Self::Suite { attributes, .. } => attributes.suite_time_limit,
+            _ => None,
+        }
+    }
+
+    /// Returns true if the component is a suite with a time limit or a warning time limit.
+    pub fn has_suite_time_limits(&self) -> bool {
+        match self {
+            Self::Suite { attributes, .. } => {
+                attributes.suite_time_limit.is_some()
+                    || attributes.suite_warning_time_limit.is_some()
+            }
+            _ => false,
+        }
+    }
+
     pub fn is_suite(&self) -> bool {
         match self {
             Self::Suite { .. } => true,
@@ -3,6 +3,9 @@ use std::panic::UnwindSafe;
 use std::sync::Arc;
 
 pub mod executor;
+
+#[cfg(feature = "async")]
+pub mod distributed;
 mod state;
 pub use state::{ComponentState, ComponentStateToken, RunStateModel};
 
@@ -10,15 +13,17 @@ pub mod notify;
 pub use notify::{ComponentProgressNotify, RunProgressNotify, NullComponentProgressChannelNotify};
 
 mod fixture;
+mod panic_output;
 pub use fixture::ComponentFixture;
 
 mod schedule_runner;
 pub use schedule_runner::ScheduleRunner;
 
-use crate::components::TestParameters;
+use crate::components::{SuiteState, TestParameters};
 use crate::scheduling::state_machine::TaskStateMachineNode;
 use crate::scheduling::ScheduledComponent;
 use crate::results::report::{ComponentReportBuilder, ComponentRunReport};
+use crate::results::{ComponentResult, DidNotRunReason};
 use crate::results::summary::ComponentTypeCountSummary;
 
 /// IOC code seem for internal test and customization extensions to the framework
@@ -116,14 +121,52 @@ impl<
         parameters: Parameters,
         scheduled_component: ScheduledComponent<Parameters>,
     ) -> ComponentRunReport {
-        let fixture =
-            ComponentFixture::from_scheduled_component(scheduled_component, Arc::new(parameters));
+        // Components run in a child process receive the state of their suite from the parent process,
+        // and write back any state they insert, so it can be shared with the rest of the suite.
+        // The reason a component skipped itself is also written back, as it can not be given by the status code
+        let (state, suite_state_output, skip_reason_output) =
+            match parameters.child_process_target() {
+                Some(args) => (
+                    SuiteState::child_of(&SuiteState::from_serialized(
+                        args.meta().suite_state.clone(),
+                    )),
+                    Some(args.meta().suite_state_output.clone()),
+                    Some(args.meta().skip_reason_output.clone()),
+                ),
+                None => (SuiteState::new(), None, None),
+            };
+
+        let fixture = ComponentFixture::from_scheduled_component(
+            scheduled_component,
+            Arc::new(parameters),
+            state.clone(),
+        );
         let report = ComponentReportBuilder::new(
             fixture.description().clone(),
             fixture.acceptance_criteria(),
         );
-        executor::execute(NullComponentProgressChannelNotify {}, fixture, report)
+        let mut report = executor::execute(NullComponentProgressChannelNotify {}, fixture, report)
             .await
-            .build()
+            .build();
+
+        match state.own_serialized() {
+            Ok(inserted_state) => {
+                if let (Some(path), false) = (suite_state_output, inserted_state.is_empty()) {
+                    std::fs::write(path, serde_json::to_string(&inserted_state).unwrap()).unwrap();
+                }
+            }
+            Err(e) => {
+                // The state can not be shared with the rest of the suite, so the component is rejected
+                eprintln!("{}", e);
+                report.result = ComponentResult::rejected();
+            }
+        }
+
+        if let (Some(path), ComponentResult::DidNotRun(DidNotRunReason::Skipped(reason))) =
+            (skip_reason_output, &report.result)
+        {
+            std::fs::write(path, reason).unwrap();
+        }
+        report
     }
 }
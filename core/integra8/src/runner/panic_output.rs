@@ -0,0 +1,145 @@
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Once};
+use std::task::{Context, Poll};
+
+thread_local! {
+    static CAPTURING: RefCell<Option<PanicOutput>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// The output of panics raised while it was capturing, which would otherwise have been printed to stderr
+/// by the panic hook. Used to hold back the output of a component which may be run again,
+/// so only the output of its last run is reported.
+#[derive(Clone, Default)]
+pub struct PanicOutput(Arc<Mutex<Vec<String>>>);
+
+impl PanicOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the panic output being captured by the current thread, if any.
+    pub fn current() -> Option<Self> {
+        CAPTURING.with(|capturing| capturing.borrow().clone())
+    }
+
+    /// Runs the function, capturing the output of any panic it raises rather than printing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function to run.
+    ///
+    pub fn capture<T>(&self, f: impl FnOnce() -> T) -> T {
+        INSTALL_HOOK.call_once(install_hook);
+
+        let previous = CAPTURING.with(|capturing| capturing.replace(Some(self.clone())));
+        // Restores the previous capture, even when the function panics
+        let _restore = RestoreCapture(previous);
+        f()
+    }
+
+    /// Wraps a future, capturing the output of any panic it raises while it is polled.
+    ///
+    /// # Arguments
+    ///
+    /// * `future` - The future to capture panic output from.
+    ///
+    pub fn capture_async<F: Future>(&self, future: F) -> CapturePanicOutput<F> {
+        CapturePanicOutput {
+            output: self.clone(),
+            future: Box::pin(future),
+        }
+    }
+
+    /// Returns the output of each panic captured, in the order they were raised.
+    pub fn messages(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Prints the captured output to stderr, as the panic hook would have.
+    pub fn print(&self) {
+        for message in self.messages() {
+            eprintln!("{}", message);
+        }
+    }
+}
+
+/// A future which captures the output of any panic raised while it is polled.
+pub struct CapturePanicOutput<F> {
+    output: PanicOutput,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CapturePanicOutput<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = &mut *self;
+        let future = &mut this.future;
+        this.output.capture(|| future.as_mut().poll(cx))
+    }
+}
+
+struct RestoreCapture(Option<PanicOutput>);
+
+impl Drop for RestoreCapture {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CAPTURING.with(|capturing| *capturing.borrow_mut() = previous);
+    }
+}
+
+/// Replaces the panic hook with one which captures the output of panics raised on capturing threads,
+/// and otherwise calls the previous hook.
+fn install_hook() {
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| match PanicOutput::current() {
+        Some(output) => output.0.lock().unwrap().push(format!(
+            "thread '{}' {}",
+            std::thread::current().name().unwrap_or("<unnamed>"),
+            info
+        )),
+        None => previous_hook(info),
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decorations::test_rigging::block_on;
+    use futures::FutureExt;
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn should_capture_panic_output() {
+        let output = PanicOutput::new();
+
+        // Act
+        let result = std::panic::catch_unwind(|| output.capture(|| panic!("poll 1 failed")));
+
+        // Assert
+        assert!(result.is_err());
+        let messages = output.messages();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("poll 1 failed"));
+        assert!(PanicOutput::current().is_none());
+    }
+
+    #[test]
+    fn should_capture_panic_output_of_future() {
+        let output = PanicOutput::new();
+
+        // Act
+        let result = block_on(
+            AssertUnwindSafe(output.capture_async(async { panic!("poll 2 failed") }))
+                .catch_unwind(),
+        );
+
+        // Assert
+        assert!(result.is_err());
+        assert!(output.messages()[0].contains("poll 2 failed"));
+    }
+}
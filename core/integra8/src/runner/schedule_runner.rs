@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::panic::UnwindSafe;
 use std::sync::Arc;
 
@@ -7,7 +8,9 @@ use crate::runner::{
     RunProgressNotify, RunStateModel,
 };
 
-use crate::components::TestParameters;
+use crate::components::{
+    ComponentDescription, ComponentId, ComponentType, SuiteState, TestParameters,
+};
 use crate::scheduling::iter::TaskStreamMap;
 use crate::scheduling::state_machine::TaskStateMachineNode;
 use crate::scheduling::{ScheduledComponent, TaskScheduler};
@@ -18,6 +21,7 @@ use crate::results::summary::ComponentTypeCountSummary;
 pub struct ScheduleRunner<RunProgressNotify> {
     sender: RunProgressNotify,
     status: RunStateModel,
+    suite_states: HashMap<ComponentId, SuiteState>,
 }
 
 impl<ProgressNotify: RunProgressNotify + Sync + Send + Clone + 'static>
@@ -27,6 +31,7 @@ impl<ProgressNotify: RunProgressNotify + Sync + Send + Clone + 'static>
         Self {
             sender: sender,
             status: RunStateModel::new(),
+            suite_states: HashMap::new(),
         }
     }
 
@@ -50,6 +55,7 @@ impl<ProgressNotify: RunProgressNotify + Sync + Send + Clone + 'static>
         let sender = self.sender.clone();
 
         let parameters = Arc::new(parameters);
+        self.status.abort_after_failures(parameters.max_failures());
 
         let scheduled_component_runs =
             schedule.map(|component| self.prepare_component_run(parameters.clone(), component));
@@ -76,10 +82,19 @@ impl<ProgressNotify: RunProgressNotify + Sync + Send + Clone + 'static>
         component: ScheduledComponent<TParameters>,
     ) -> ComponentRunner<TParameters, <ProgressNotify as RunProgressNotify>::ComponentProgressNotify>
     {
-        let fixture = ComponentFixture::from_scheduled_component(component, parameters);
+        let state = self.get_suite_state(component.description());
+        let fixture = ComponentFixture::from_scheduled_component(component, parameters, state);
+
+        // Components which are filtered out or ignored are reported as such, regardless of their dependencies
+        let depends_on = match fixture.filtered() || fixture.ignore() {
+            true => &[],
+            false => fixture.depends_on(),
+        };
 
         ComponentRunner {
-            component_state: self.status.get_status_token(fixture.description()),
+            component_state: self
+                .status
+                .get_status_token(fixture.description(), depends_on),
             progress_notify: self
                 .sender
                 .component_process_notify(fixture.description().clone()),
@@ -90,6 +105,35 @@ impl<ProgressNotify: RunProgressNotify + Sync + Send + Clone + 'static>
             fixture: fixture,
         }
     }
+
+    /// Returns the state of the suite the component belongs to, or the suite's own state if the component is a suite.
+    /// Suites are expected to be scheduled before the components within them.
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the component
+    ///
+    fn get_suite_state(&mut self, description: &ComponentDescription) -> SuiteState {
+        if description.component_type() != &ComponentType::Suite {
+            return self
+                .suite_states
+                .get(description.parent_id())
+                .cloned()
+                .unwrap_or_default();
+        }
+
+        if let Some(state) = self.suite_states.get(description.id()) {
+            return state.clone();
+        }
+
+        let state = match self.suite_states.get(description.parent_id()) {
+            Some(parent) => SuiteState::child_of(parent),
+            None => SuiteState::new(),
+        };
+        self.suite_states
+            .insert(description.id().clone(), state.clone());
+        state
+    }
 }
 
 pub enum ComponentRunResult<Report> {
@@ -141,8 +185,22 @@ impl<
         match self.component_state.state() {
             ComponentState::Undetermined => self.execute().await,
             ComponentState::Tentative(result) => {
-                self.report.time_taken(self.component_state.time_taken());
+                let time_taken = match self.fixture.has_suite_time_limits() {
+                    // Suites with time limits are timed from when they started, as this is what their limits apply to
+                    true => self.component_state.time_since_started(),
+                    false => self.component_state.time_taken(),
+                };
+                self.report.time_taken(time_taken);
                 self.report.with_result(result.clone());
+
+                if self.fixture.is_suite() && self.report.has_exceeded_time_limit() {
+                    // Suites which exceed their time limit are overtime, regardless of the results of their children.
+                    // The time limit of the root suite is the time limit of the run
+                    match self.fixture.description().is_root() {
+                        true => self.report.run_time_limit_exceeded_result(),
+                        false => self.report.timed_out_result(),
+                    }
+                }
                 ComponentRunResult::Ready(self.report)
             }
             ComponentState::Finalized(result) => {
@@ -154,17 +212,274 @@ impl<
     }
 
     async fn execute(mut self) -> ComponentRunResult<ComponentReportBuilder> {
+        if self.fixture.filtered() {
+            self.report.filtered_result();
+            return ComponentRunResult::Ready(self.report);
+        }
+
         if self.fixture.ignore() {
             self.report.ignored_result();
             return ComponentRunResult::Ready(self.report);
         }
 
+        if let Some(reason) = self.fixture.skip_reason() {
+            self.report.skipped_result(reason);
+            return ComponentRunResult::Ready(self.report);
+        }
+
         if self.fixture.is_suite() {
             // Suites cant "run", they are only a projection of their children's results.
+            self.component_state.start(self.fixture.suite_time_limit());
             return ComponentRunResult::WaitingOnChildren;
         }
 
+        // Components are aborted once the suite they belong to exceeds its time limit
+        self.report.with_deadline(self.component_state.deadline());
+
         // execute to determine the components state
         ComponentRunResult::Ready(execute(self.progress_notify, self.fixture, self.report).await)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use crate::decorations::test_rigging::{block_on, Parameters};
+    use crate::decorations::{ComponentDecoration, ComponentGroup};
+    use crate::results::{ComponentResult, DidNotRunReason};
+    use crate::runner::NullComponentProgressChannelNotify;
+    use crate::scheduling::IntoTaskStateMachine;
+
+    mod mock_app {
+        pub use integra8_decorations_impl::*;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::Duration;
+
+        pub static OVERTIME_TEAR_DOWN_RAN: AtomicBool = AtomicBool::new(false);
+        pub static RUN_TIME_LIMIT_TEAR_DOWN_RAN: AtomicBool = AtomicBool::new(false);
+
+        #[suite]
+        #[integra8(crate = crate)]
+        #[suite_time_limit = "300ms"]
+        #[test_sequential]
+        pub mod overtime_suite {
+            use super::*;
+            pub use integra8_decorations_impl::*;
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            pub async fn slow_test() {
+                crate::async_runtime::sleep(Duration::from_secs(10)).await;
+            }
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            pub fn unstarted_test() {}
+
+            #[teardown]
+            #[integra8(crate = crate)]
+            pub fn tear_down() {
+                OVERTIME_TEAR_DOWN_RAN.store(true, Ordering::SeqCst);
+            }
+
+            #[suite]
+            #[integra8(crate = crate)]
+            pub mod unstarted_suite {
+                pub use integra8_decorations_impl::*;
+
+                #[integration_test]
+                #[integra8(crate = crate)]
+                pub fn nested_test() {}
+            }
+        }
+
+        #[suite]
+        #[integra8(crate = crate)]
+        #[test_sequential]
+        pub mod long_running_suite {
+            use super::*;
+            pub use integra8_decorations_impl::*;
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            pub async fn long_running_test() {
+                crate::async_runtime::sleep(Duration::from_secs(10)).await;
+            }
+
+            #[integration_test]
+            #[integra8(crate = crate)]
+            pub fn test_after_run_time_limit() {}
+
+            #[teardown]
+            #[integra8(crate = crate)]
+            pub fn tear_down_after_run_time_limit() {
+                RUN_TIME_LIMIT_TEAR_DOWN_RAN.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// Records the report of each component, as they complete
+    #[derive(Clone, Default)]
+    struct RecordReports(Arc<Mutex<Vec<ComponentRunReport>>>);
+
+    impl RunProgressNotify for RecordReports {
+        type ComponentProgressNotify = NullComponentProgressChannelNotify;
+
+        fn notify_run_start(
+            &self,
+            _summary: ComponentTypeCountSummary,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+            Box::pin(async {})
+        }
+
+        fn notify_run_complete(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+            Box::pin(async {})
+        }
+
+        fn notify_component_report_complete(
+            &self,
+            report: ComponentRunReport,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+            self.0.lock().unwrap().push(report);
+            Box::pin(async {})
+        }
+
+        fn component_process_notify(
+            &self,
+            _description: ComponentDescription,
+        ) -> Self::ComponentProgressNotify {
+            NullComponentProgressChannelNotify
+        }
+    }
+
+    impl RecordReports {
+        /// Returns the result of the component, found by the last segment of its path
+        fn result_of(&self, name: &str) -> ComponentResult {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|report| report.description.path().as_str().rsplit("::").next() == Some(name))
+                .map(|report| report.result.clone())
+                .unwrap_or_else(|| panic!("{} was not reported", name))
+        }
+    }
+
+    /// Parameters for running components within the test process
+    fn parameters() -> Parameters {
+        let mut parameters = Parameters::default();
+        parameters.use_child_processes = false;
+        parameters
+    }
+
+    /// Runs the given components, and returns the reports of each of them
+    fn run(
+        parameters: Parameters,
+        decorations: Vec<ComponentDecoration<Parameters>>,
+    ) -> RecordReports {
+        let schedule =
+            ComponentGroup::into_root_component(decorations, &parameters).into_task_state_machine();
+        let reports = RecordReports::default();
+        block_on(ScheduleRunner::new(reports.clone()).run(
+            parameters,
+            schedule,
+            ComponentTypeCountSummary::new(),
+        ));
+        reports
+    }
+
+    #[test]
+    fn should_abort_suite_once_it_exceeds_its_time_limit() {
+        // Act
+        let reports = run(
+            parameters(),
+            vec![
+                mock_app::overtime_suite::__suite_def(),
+                mock_app::overtime_suite::slow_test::test_def(),
+                mock_app::overtime_suite::unstarted_test::test_def(),
+                mock_app::overtime_suite::tear_down::teardown_def(),
+                mock_app::overtime_suite::unstarted_suite::__suite_def(),
+                mock_app::overtime_suite::unstarted_suite::nested_test::test_def(),
+            ],
+        );
+
+        // Assert
+        // The running test is aborted, and the components yet to start are not run
+        assert_eq!(reports.result_of("slow_test"), ComponentResult::timed_out());
+        assert_eq!(
+            reports.result_of("unstarted_test"),
+            ComponentResult::DidNotRun(DidNotRunReason::ParentFailure)
+        );
+        assert_eq!(
+            reports.result_of("unstarted_suite"),
+            ComponentResult::DidNotRun(DidNotRunReason::ParentFailure)
+        );
+        assert_eq!(
+            reports.result_of("nested_test"),
+            ComponentResult::DidNotRun(DidNotRunReason::ParentFailure)
+        );
+
+        // The tear down is still run
+        assert_eq!(reports.result_of("tear_down"), ComponentResult::passed());
+        assert!(mock_app::OVERTIME_TEAR_DOWN_RAN.load(std::sync::atomic::Ordering::SeqCst));
+
+        assert_eq!(
+            reports.result_of("overtime_suite"),
+            ComponentResult::timed_out()
+        );
+    }
+
+    #[test]
+    fn should_abort_run_once_it_exceeds_its_time_limit() {
+        let mut parameters = parameters();
+        parameters.run_time_limit = Some(Duration::from_millis(300));
+
+        // Act
+        let reports = run(
+            parameters,
+            vec![
+                mock_app::long_running_suite::__suite_def(),
+                mock_app::long_running_suite::long_running_test::test_def(),
+                mock_app::long_running_suite::test_after_run_time_limit::test_def(),
+                mock_app::long_running_suite::tear_down_after_run_time_limit::teardown_def(),
+            ],
+        );
+
+        // Assert
+        // The running test is aborted, and the components yet to start are not run
+        assert_eq!(
+            reports.result_of("long_running_test"),
+            ComponentResult::timed_out()
+        );
+        assert_eq!(
+            reports.result_of("test_after_run_time_limit"),
+            ComponentResult::DidNotRun(DidNotRunReason::ParentFailure)
+        );
+
+        // The tear down is still run
+        assert_eq!(
+            reports.result_of("tear_down_after_run_time_limit"),
+            ComponentResult::passed()
+        );
+        assert!(mock_app::RUN_TIME_LIMIT_TEAR_DOWN_RAN.load(std::sync::atomic::Ordering::SeqCst));
+
+        // Only the root suite fails for exceeding the run time limit
+        assert_eq!(
+            reports.result_of("long_running_suite"),
+            ComponentResult::child_failure()
+        );
+        assert_eq!(
+            reports.result_of("integra8_decorations"),
+            ComponentResult::run_time_limit_exceeded()
+        );
+        assert_eq!(
+            ComponentResult::run_time_limit_exceeded().to_status_code(),
+            13
+        );
+    }
+}
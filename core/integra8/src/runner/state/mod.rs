@@ -1,35 +1,81 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 mod model;
 pub use model::{ComponentResultsModel, ComponentState};
 
-use crate::components::{ComponentDescription, ComponentId, ComponentType};
-use crate::results::ComponentResult;
+use crate::components::{ComponentDescription, ComponentId, ComponentPath, ComponentType};
+use crate::results::{ComponentResult, DidNotRunReason};
 
 pub struct RunStateModel {
     component_result_states: HashMap<ComponentId, Arc<RwLock<ComponentResultsModel>>>,
+    component_paths: HashMap<ComponentPath, ComponentId>,
+    failure_count: Arc<AtomicUsize>,
+    max_failures: Option<usize>,
 }
 
 impl RunStateModel {
     pub fn new() -> Self {
         Self {
             component_result_states: HashMap::new(),
+            component_paths: HashMap::new(),
+            failure_count: Arc::new(AtomicUsize::new(0)),
+            max_failures: None,
         }
     }
 
+    /// Aborts the run once the given number of components have failed.
+    /// Components which have yet to start are then not run, except for the tear downs of suites which have started.
+    /// Expected to be called before any status tokens are returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_failures` - The number of failed components after which the run is aborted, or none if the run is never aborted.
+    ///
+    pub fn abort_after_failures(&mut self, max_failures: Option<usize>) {
+        self.max_failures = max_failures;
+    }
+
     /// Returns a global state token for the given component
     ///
     /// # Arguments
     ///
     /// * `description` - The description of the component for which the returned token will represent
     ///
-    pub fn get_status_token(&mut self, description: &ComponentDescription) -> ComponentStateToken {
+    /// * `depends_on` - The paths of the tests this component depends on.
+    ///   Dependencies are expected to be scheduled before the components which depend on them.
+    ///
+    pub fn get_status_token(
+        &mut self,
+        description: &ComponentDescription,
+        depends_on: &[ComponentPath],
+    ) -> ComponentStateToken {
+        self.component_paths
+            .insert(description.path().clone(), description.id().clone());
+
+        let dependencies: Vec<(ComponentPath, ComponentId)> = self
+            .component_paths
+            .iter()
+            .filter(|(path, _)| {
+                depends_on
+                    .iter()
+                    .any(|dependency| path.is_within(dependency))
+            })
+            .map(|(path, id)| (path.clone(), id.clone()))
+            .collect();
+
         ComponentStateToken {
             component_type: description.component_type().clone(),
             self_token: self.get_token(&description.id()),
             parent_token: self.get_token(&description.parent_id()),
+            dependency_tokens: dependencies
+                .into_iter()
+                .map(|(path, id)| (path, self.get_token(&id)))
+                .collect(),
+            failure_count: self.failure_count.clone(),
+            max_failures: self.max_failures,
         }
     }
 
@@ -48,6 +94,9 @@ pub struct ComponentStateToken {
     component_type: ComponentType,
     self_token: Arc<RwLock<ComponentResultsModel>>,
     parent_token: Arc<RwLock<ComponentResultsModel>>,
+    dependency_tokens: Vec<(ComponentPath, Arc<RwLock<ComponentResultsModel>>)>,
+    failure_count: Arc<AtomicUsize>,
+    max_failures: Option<usize>,
 }
 
 impl ComponentStateToken {
@@ -56,6 +105,48 @@ impl ComponentStateToken {
         self.self_token.read().unwrap().time_taken.clone()
     }
 
+    /// Returns the time since this suite started, or zero if it has not started
+    pub fn time_since_started(&self) -> Duration {
+        self.self_token
+            .read()
+            .unwrap()
+            .started
+            .map_or(Duration::new(0, 0), |started| started.elapsed())
+    }
+
+    /// Returns the instant this component must complete by, being the deadline of its parent suite.
+    /// Tear downs have no deadline, as they are always run.
+    pub fn deadline(&self) -> Option<Instant> {
+        match self.component_type.is_tear_down() {
+            true => None,
+            false => self.parent_token.read().unwrap().deadline,
+        }
+    }
+
+    /// Starts this suite, giving it a deadline if it has a time limit.
+    /// The deadline is the sooner of its own time limit and the deadline of its parent suite.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_limit` - The maximum duration of the suite, or none if it has no limit.
+    ///
+    pub fn start(&self, time_limit: Option<Duration>) {
+        let started = Instant::now();
+        let parent_deadline = match self.is_root() {
+            true => None,
+            false => self.parent_token.read().unwrap().deadline,
+        };
+
+        let deadline = match (time_limit.map(|limit| started + limit), parent_deadline) {
+            (Some(deadline), Some(parent_deadline)) => Some(deadline.min(parent_deadline)),
+            (deadline, parent_deadline) => deadline.or(parent_deadline),
+        };
+
+        let mut model = self.self_token.write().unwrap();
+        model.started = Some(started);
+        model.deadline = deadline;
+    }
+
     /// Returns the type of component this token belongs to
     pub fn component_type(&self) -> ComponentType {
         self.component_type.clone()
@@ -69,23 +160,77 @@ impl ComponentStateToken {
     /// | Pass, Fail or Skipped | Any                | Pass, Fail or Skipped respectively                         |
     /// | Undetermined          | Skipped            | Skipped                                                    |
     /// | Undetermined          | Failed             | Failed, or Undetermined if component type  is tear down    |
+    /// | Undetermined          | Exceeded deadline  | Failed, or Undetermined if component type  is tear down    |
     /// | Undetermined          | Undetermined       | Undetermined                                               |
     ///
+    /// Once the run is aborted, components which are still undetermined are not run, unless they are a tear down.
+    /// Suites which have yet to start are not run either, so neither are their tear downs.
+    ///
     /// *Tear down is always run, even if the parent is in a failed state. This is to ensure a clean environment after running the test.*
     ///
+    /// A test may also inherit its state from the tests it depends on, which takes precedence over a failed parent
+    ///
+    /// | State                 | Dependency State   | Inferred State                                             |
+    /// |-----------------------|--------------------|------------------------------------------------------------|
+    /// | Undetermined          | Filtered, Ignored  | Skipped, naming the dependency which did not run           |
+    /// | Undetermined          | Skipped            | Skipped                                                    |
+    /// | Undetermined          | Failed             | Skipped, due to dependency failure                         |
+    ///
     pub fn state(&self) -> ComponentState {
         match self.self_token.read().unwrap().state.clone() {
             ComponentState::Undetermined => {
-                let state_parent = &self.parent_token.read().unwrap().state;
+                let parent_model = self.parent_token.read().unwrap();
+                let state_parent = &parent_model.state;
 
                 if state_parent.is_skipped() {
                     return ComponentState::Tentative(state_parent.result().unwrap());
                 }
 
+                for (dependency_path, dependency_token) in &self.dependency_tokens {
+                    let state_dependency = &dependency_token.read().unwrap().state;
+
+                    match state_dependency.result() {
+                        Some(ComponentResult::DidNotRun(DidNotRunReason::Filtered)) => {
+                            return ComponentState::Tentative(ComponentResult::skipped(format!(
+                                "depends on {}, which was filtered out",
+                                dependency_path
+                            )));
+                        }
+                        Some(ComponentResult::DidNotRun(DidNotRunReason::Ignored)) => {
+                            return ComponentState::Tentative(ComponentResult::skipped(format!(
+                                "depends on {}, which is ignored",
+                                dependency_path
+                            )));
+                        }
+                        _ => {}
+                    }
+
+                    if state_dependency.is_skipped() {
+                        return ComponentState::Tentative(state_dependency.result().unwrap());
+                    }
+
+                    if state_dependency.is_failed() {
+                        return ComponentState::Tentative(ComponentResult::dependency_failure());
+                    }
+                }
+
                 if state_parent.is_failed() && !self.component_type.is_tear_down() {
                     return ComponentState::Tentative(ComponentResult::parent_failure());
                 }
 
+                let parent_exceeded_deadline = parent_model
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline);
+
+                if parent_exceeded_deadline && !self.component_type.is_tear_down() {
+                    // Components which have yet to start once their suite exceeds its time limit are not run
+                    return ComponentState::Tentative(ComponentResult::parent_failure());
+                }
+
+                if self.is_run_aborted() && !self.component_type.is_tear_down() {
+                    return ComponentState::Tentative(ComponentResult::run_aborted());
+                }
+
                 return ComponentState::Undetermined;
             }
             other => other,
@@ -99,6 +244,7 @@ impl ComponentStateToken {
     /// - If **any** children are pass but with a warning, the parents own status is also pass with warning.
     /// - If **any** children are failed, the parents own status is also failed with the reason `child_failure`
     /// - If **all** children are skipped, the parents status is also skipped.   
+    /// - If a setup skips itself, the parents status is also skipped, so the rest of its children are skipped.
     ///
     /// # Arguments
     ///
@@ -107,6 +253,15 @@ impl ComponentStateToken {
     /// * `time_taken` - The execution time of this component. Can be zero if the component was skipped.
     ///
     pub fn finalize_result(&self, result: ComponentResult, time_taken: Duration) {
+        // Only tests and setups count towards the run's failures. Suites only fail as a result of their children,
+        // and tear downs are run regardless of the run being aborted, so their failures do not abort it
+        let counts_as_failure = matches!(
+            self.component_type,
+            ComponentType::Test | ComponentType::Setup
+        );
+        if result.has_failed() && counts_as_failure {
+            self.failure_count.fetch_add(1, Ordering::SeqCst);
+        }
         self.set_result(ComponentState::Finalized(result), time_taken)
     }
 
@@ -144,6 +299,20 @@ impl ComponentStateToken {
         }
 
         if let Some(child_result) = child_model.state.result() {
+            let skipped_setup = matches!(
+                (&self.component_type, &child_result),
+                (
+                    ComponentType::Setup,
+                    ComponentResult::DidNotRun(DidNotRunReason::Skipped(_))
+                )
+            );
+
+            if skipped_setup && !parent_model.state.is_failed() {
+                // If a setup skipped itself, then we are implicitly skipped for the same reason
+                parent_model.state = ComponentState::Tentative(child_result);
+                return;
+            }
+
             if child_result.has_passed()
                 && !parent_model.state.is_failed()
                 && !parent_model.state.is_warn()
@@ -166,9 +335,204 @@ impl ComponentStateToken {
         }
     }
 
+    /// Returns if the run has reached its maximum number of failures
+    fn is_run_aborted(&self) -> bool {
+        self.max_failures
+            .is_some_and(|max_failures| self.failure_count.load(Ordering::SeqCst) >= max_failures)
+    }
+
     /// Returns if the component is root or not.
     /// *Root component will always have its self as its parent*
     fn is_root(&self) -> bool {
         Arc::ptr_eq(&self.self_token, &self.parent_token)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ComponentMetadata, ComponentTags};
+
+    fn description(
+        id: usize,
+        parent_id: usize,
+        component_type: ComponentType,
+    ) -> ComponentDescription {
+        description_at(module_path!(), id, parent_id, component_type)
+    }
+
+    fn description_at(
+        path: &'static str,
+        id: usize,
+        parent_id: usize,
+        component_type: ComponentType,
+    ) -> ComponentDescription {
+        let mut location = crate::src_loc!();
+        location.path = ComponentPath::from(path);
+        ComponentDescription::new(
+            None,
+            ComponentId::from(id),
+            ComponentId::from(parent_id),
+            location,
+            crate::src_loc!(),
+            None,
+            ComponentTags::default(),
+            ComponentMetadata::default(),
+            component_type,
+        )
+    }
+
+    fn token(
+        model: &mut RunStateModel,
+        id: usize,
+        parent_id: usize,
+        component_type: ComponentType,
+    ) -> ComponentStateToken {
+        model.get_status_token(&description(id, parent_id, component_type), &[])
+    }
+
+    #[test]
+    fn should_abort_run_once_max_failures_reached() {
+        // Arrange
+        let mut model = RunStateModel::new();
+        model.abort_after_failures(Some(1));
+        let root = token(&mut model, 0, 0, ComponentType::Suite);
+        let failing_suite = token(&mut model, 1, 0, ComponentType::Suite);
+        let failing_test = token(&mut model, 2, 1, ComponentType::Test);
+        let started_suite = token(&mut model, 3, 0, ComponentType::Suite);
+        let started_test = token(&mut model, 4, 3, ComponentType::Test);
+        let started_tear_down = token(&mut model, 5, 3, ComponentType::TearDown);
+        let not_started_suite = token(&mut model, 6, 0, ComponentType::Suite);
+        let not_started_tear_down = token(&mut model, 7, 6, ComponentType::TearDown);
+
+        root.start(None);
+        root.tentative_pass();
+        failing_suite.start(None);
+        failing_suite.tentative_pass();
+        started_suite.start(None);
+        started_suite.tentative_pass();
+
+        // Act
+        failing_test.finalize_result(ComponentResult::rejected(), Duration::from_secs(1));
+        // Suites with #[allow_fail] do not cascade their failure
+        failing_suite.finalize_result(ComponentResult::rejection_exempt(), Duration::from_secs(1));
+
+        // Assert
+        assert_eq!(
+            started_test.state(),
+            ComponentState::Tentative(ComponentResult::run_aborted())
+        );
+        assert_eq!(started_tear_down.state(), ComponentState::Undetermined);
+        assert_eq!(
+            not_started_suite.state(),
+            ComponentState::Tentative(ComponentResult::run_aborted())
+        );
+        not_started_suite.finalize_result(ComponentResult::run_aborted(), Duration::new(0, 0));
+        assert_eq!(
+            not_started_tear_down.state(),
+            ComponentState::Tentative(ComponentResult::run_aborted())
+        );
+    }
+
+    #[test]
+    fn should_not_abort_run_before_max_failures_reached() {
+        // Arrange
+        let mut model = RunStateModel::new();
+        model.abort_after_failures(Some(2));
+        let root = token(&mut model, 0, 0, ComponentType::Suite);
+        let suite = token(&mut model, 1, 0, ComponentType::Suite);
+        let failing_test = token(&mut model, 2, 1, ComponentType::Test);
+        let next_suite = token(&mut model, 3, 0, ComponentType::Suite);
+
+        root.start(None);
+        root.tentative_pass();
+        suite.start(None);
+        suite.tentative_pass();
+
+        // Act
+        failing_test.finalize_result(ComponentResult::rejected(), Duration::from_secs(1));
+        suite.finalize_result(ComponentResult::rejection_exempt(), Duration::from_secs(1));
+
+        // Assert
+        assert_eq!(next_suite.state(), ComponentState::Undetermined);
+    }
+
+    #[test]
+    fn should_skip_test_when_dependency_is_filtered_out() {
+        // Arrange
+        let mut model = RunStateModel::new();
+        let root = token(&mut model, 0, 0, ComponentType::Suite);
+        let create_order = model.get_status_token(
+            &description_at("orders::create_order", 1, 0, ComponentType::Test),
+            &[],
+        );
+        let cancel_order = model.get_status_token(
+            &description_at("orders::cancel_order", 2, 0, ComponentType::Test),
+            &[ComponentPath::from("orders::create_order")],
+        );
+
+        root.start(None);
+        root.tentative_pass();
+
+        // Act
+        create_order.finalize_result(ComponentResult::filtered(), Duration::new(0, 0));
+
+        // Assert
+        assert_eq!(
+            cancel_order.state(),
+            ComponentState::Tentative(ComponentResult::skipped(
+                "depends on orders::create_order, which was filtered out"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_not_run_test_when_dependency_fails() {
+        // Arrange
+        let mut model = RunStateModel::new();
+        let root = token(&mut model, 0, 0, ComponentType::Suite);
+        let create_order = model.get_status_token(
+            &description_at("orders::create_order", 1, 0, ComponentType::Test),
+            &[],
+        );
+        let cancel_order = model.get_status_token(
+            &description_at("orders::cancel_order", 2, 0, ComponentType::Test),
+            &[ComponentPath::from("orders::create_order")],
+        );
+
+        root.start(None);
+        root.tentative_pass();
+
+        // Act
+        create_order.finalize_result(ComponentResult::rejected(), Duration::from_secs(1));
+
+        // Assert
+        assert_eq!(
+            cancel_order.state(),
+            ComponentState::Tentative(ComponentResult::dependency_failure())
+        );
+    }
+
+    #[test]
+    fn should_not_count_tear_down_failures_towards_max_failures() {
+        // Arrange
+        let mut model = RunStateModel::new();
+        model.abort_after_failures(Some(1));
+        let root = token(&mut model, 0, 0, ComponentType::Suite);
+        let suite = token(&mut model, 1, 0, ComponentType::Suite);
+        let failing_tear_down = token(&mut model, 2, 1, ComponentType::TearDown);
+        let next_suite = token(&mut model, 3, 0, ComponentType::Suite);
+
+        root.start(None);
+        root.tentative_pass();
+        suite.start(None);
+        suite.tentative_pass();
+
+        // Act
+        failing_tear_down.finalize_result(ComponentResult::rejected(), Duration::from_secs(1));
+        suite.finalize_result(ComponentResult::rejection_exempt(), Duration::from_secs(1));
+
+        // Assert
+        assert_eq!(next_suite.state(), ComponentState::Undetermined);
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use crate::results::ComponentResult;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -43,6 +43,10 @@ impl ComponentState {
 pub struct ComponentResultsModel {
     pub state: ComponentState,
     pub time_taken: Duration,
+    /// When the suite started, or none if the component is not a suite which has started.
+    pub started: Option<Instant>,
+    /// The instant the suite's components must complete by, or none if neither it or its parents have a time limit.
+    pub deadline: Option<Instant>,
 }
 
 impl ComponentResultsModel {
@@ -50,6 +54,8 @@ impl ComponentResultsModel {
         ComponentResultsModel {
             state: ComponentState::Undetermined,
             time_taken: Duration::new(0, 0),
+            started: None,
+            deadline: None,
         }
     }
 }
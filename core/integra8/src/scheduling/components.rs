@@ -1,6 +1,8 @@
 use std::iter::Peekable;
 
-use crate::scheduling::state_machine::{ParallelTaskNode, SerialTaskNode, TaskStateMachineNode};
+use crate::scheduling::state_machine::{
+    DagTaskNode, ParallelTaskNode, SerialTaskNode, TaskStateMachineNode,
+};
 use crate::components::{
     BookEnd, Component, ComponentDescription, ComponentType, ConcurrencyMode, Suite,
     SuiteAttributes, Test, TestParameters,
@@ -23,6 +25,14 @@ impl<TParameters> ScheduledComponent<TParameters> {
             Component::TearDown(c) => ScheduledComponent::TearDown(c),
         }
     }
+
+    pub fn description(&self) -> &'_ ComponentDescription {
+        match self {
+            ScheduledComponent::Suite(description, _) => description,
+            ScheduledComponent::Test(c) => &c.description,
+            ScheduledComponent::Setup(c) | ScheduledComponent::TearDown(c) => &c.description,
+        }
+    }
 }
 
 pub trait IntoTaskStateMachine<Payload> {
@@ -60,24 +70,34 @@ impl<TParameters: TestParameters> IntoTaskStateMachine<ScheduledComponent<TParam
         // 1: Run all setup components in the order they appear
         root_node.enqueue(self.setups.into_task_state_machine());
 
-        // 2: Run all test components in the order they appear
+        // 2: Run all test components in the order they appear,
+        // each wrapped with the before and after each hooks of this suite and its parent suites
+        for test in self.tests.iter_mut() {
+            test.before_each = self.before_each.clone();
+            test.after_each = self.after_each.clone();
+        }
         root_node.enqueue(self.tests.into_task_state_machine());
 
         // 3: Run all child suites of this suite.
         // Queue in groups, depending on the child suites concurrency mode
         let mut parallel_suites = ParallelTaskNode::new();
         let mut serial_suites = SerialTaskNode::new();
+        let (before_each, after_each) = (&self.before_each, &self.after_each);
+
+        self.suites.drain(..).for_each(|mut suite| {
+            // Before each hooks of outer suites are run first, and their after each hooks are run last
+            suite.before_each.splice(0..0, before_each.iter().cloned());
+            suite.after_each.extend(after_each.iter().cloned());
 
-        self.suites
-            .drain(..)
-            .for_each(|suite| match suite.attributes.suite_concurrency_mode {
+            match suite.attributes.suite_concurrency_mode {
                 ConcurrencyMode::Sequential => {
                     serial_suites.enqueue(suite.into_task_state_machine());
                 }
                 ConcurrencyMode::Parallel => {
                     parallel_suites.append(suite.into_task_state_machine());
                 }
-            });
+            }
+        });
 
         // Favor running concurrent suites over serial onces,
         // by running as many tests upfront as posable we can
@@ -99,6 +119,13 @@ impl<TParameters: TestParameters> IntoTaskStateMachine<ScheduledComponent<TParam
     for Vec<Test<TParameters>>
 {
     fn into_task_state_machine(self) -> TaskStateMachineNode<ScheduledComponent<TParameters>> {
+        if self
+            .iter()
+            .any(|test| !test.attributes.depends_on.is_empty())
+        {
+            return into_dependency_graph(self).into();
+        }
+
         IntoComponentTaskStepIterator::from(self.into_iter().map(|x| TaskStepComponent::Test(x)))
             .fold(SerialTaskNode::new(), |mut seq, node| {
                 seq.enqueue(node);
@@ -127,6 +154,67 @@ impl<TParameters: TestParameters> IntoTaskStateMachine<ScheduledComponent<TParam
     }
 }
 
+/// Returns a graph of the given tests, where each test is started once the tests it depends on have completed.
+/// Tests which do not declare any dependencies are ordered as per their concurrency mode, in the order they appear.
+///
+/// Only dependencies on tests within the same suite are part of the graph. Dependencies on the tests of other suites
+/// are met by the order the suites are run in, which along with cycles, is checked by `Suite::validate_dependencies`.
+///
+fn into_dependency_graph<TParameters: TestParameters>(
+    tests: Vec<Test<TParameters>>,
+) -> DagTaskNode<ScheduledComponent<TParameters>> {
+    let declared = tests
+        .iter()
+        .map(|test| {
+            test.attributes
+                .depends_on
+                .iter()
+                .flat_map(|dependency| {
+                    tests
+                        .iter()
+                        .enumerate()
+                        .filter(move |(_, other)| other.description.path().is_within(dependency))
+                        .map(|(idx, _)| idx)
+                })
+                .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Tests without dependencies only ever depend on tests which appear before them,
+    // and so can not introduce a cycle
+    let mut last_sequential = None;
+    let mut since_sequential = Vec::new();
+    let mut dependencies = declared;
+
+    for (idx, test) in tests.iter().enumerate() {
+        if !test.attributes.depends_on.is_empty() {
+            continue;
+        }
+
+        match test.attributes.concurrency_mode {
+            ConcurrencyMode::Parallel => {
+                dependencies[idx].extend(last_sequential);
+                since_sequential.push(idx);
+            }
+            ConcurrencyMode::Sequential => {
+                match since_sequential.is_empty() {
+                    true => dependencies[idx].extend(last_sequential),
+                    false => dependencies[idx].append(&mut since_sequential),
+                }
+                last_sequential = Some(idx);
+            }
+        }
+    }
+
+    tests
+        .into_iter()
+        .zip(dependencies)
+        .fold(DagTaskNode::new(), |mut dag, (test, dependencies)| {
+            dag.add(ScheduledComponent::Test(test), dependencies);
+            dag
+        })
+}
+
 enum TaskStepComponent<TParameters> {
     Test(Test<TParameters>),
     Setup(BookEnd<TParameters>),
@@ -6,6 +6,9 @@ pub use state_machine::{PollTaskResult, TaskNodePath, TaskStateMachineNode, Task
 pub mod components;
 pub use components::{IntoTaskStateMachine, ScheduledComponent};
 
+pub mod shuffle;
+pub use shuffle::{random_seed, ShuffleRng, ShuffleSchedule};
+
 mod scheduler;
 pub use scheduler::TaskScheduler;
 
@@ -0,0 +1,158 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::components::{Component, ComponentPath, ConcurrencyMode, Suite, TestParameters};
+
+/// A seeded pseudo random number generator (SplitMix64), used to shuffle the schedule.
+/// The same seed always produces the same order, so a failing order can be replayed.
+pub struct ShuffleRng {
+    state: u64,
+}
+
+impl ShuffleRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Shuffles the items which match the given predicate amongst their own positions,
+    /// leaving all other items where they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The items to shuffle.
+    ///
+    /// * `predicate` - Returns true for the items which can be moved.
+    ///
+    pub fn shuffle_where<T>(&mut self, items: &mut [T], predicate: impl Fn(&T) -> bool) {
+        let slots = (0..items.len())
+            .filter(|idx| predicate(&items[*idx]))
+            .collect::<Vec<usize>>();
+
+        // Fisher–Yates, over the positions of the matching items
+        for i in (1..slots.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(slots[i], slots[j]);
+        }
+    }
+}
+
+/// Returns a new seed for shuffling the schedule, which differs between runs.
+pub fn random_seed() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+
+    ShuffleRng::new(nanos ^ ((std::process::id() as u64) << 32)).next_u64()
+}
+
+pub trait ShuffleSchedule {
+    /// Randomizes the order of sequential tests and of child suites.
+    /// Setups and tear downs are never moved.
+    fn shuffle_schedule(&mut self, rng: &mut ShuffleRng);
+}
+
+impl<TParameters: TestParameters> ShuffleSchedule for Component<TParameters> {
+    fn shuffle_schedule(&mut self, rng: &mut ShuffleRng) {
+        if let Component::Suite(suite) = self {
+            suite.shuffle_schedule(rng);
+        }
+    }
+}
+
+impl<TParameters: TestParameters> ShuffleSchedule for Suite<TParameters> {
+    fn shuffle_schedule(&mut self, rng: &mut ShuffleRng) {
+        // Parallel tests are grouped by the sequential tests around them,
+        // so only the sequential tests are moved, leaving each parallel group in place.
+        // Tests with dependencies are left in place, as they are ordered by their dependencies.
+        rng.shuffle_where(&mut self.tests, |test| {
+            test.attributes.concurrency_mode == ConcurrencyMode::Sequential
+                && test.attributes.depends_on.is_empty()
+        });
+
+        // Suites with tests which depend on, or are depended on by, the tests of other suites are left in place,
+        // as they rely on the order the suites are run in
+        let mut dependencies = Vec::new();
+        collect_dependencies(self, &mut dependencies);
+        let ordered = self
+            .suites
+            .iter()
+            .map(|suite| suite.description.path().clone())
+            .filter(|suite_path| {
+                dependencies.iter().any(|(test, dependency)| {
+                    test.is_within(suite_path) != dependency.is_within(suite_path)
+                })
+            })
+            .collect::<Vec<ComponentPath>>();
+
+        rng.shuffle_where(&mut self.suites, |suite| {
+            !ordered.contains(suite.description.path())
+        });
+        for suite in self.suites.iter_mut() {
+            suite.shuffle_schedule(rng);
+        }
+    }
+}
+
+/// Collects the path of each test within the suite and its nested suites, paired with the path of each test it depends on.
+fn collect_dependencies<'a, TParameters>(
+    suite: &'a Suite<TParameters>,
+    dependencies: &mut Vec<(&'a ComponentPath, &'a ComponentPath)>,
+) {
+    for test in &suite.tests {
+        dependencies.extend(
+            test.attributes
+                .depends_on
+                .iter()
+                .map(|dependency| (test.description.path(), dependency)),
+        );
+    }
+
+    for child in &suite.suites {
+        collect_dependencies(child, dependencies);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shuffled(seed: u64) -> Vec<usize> {
+        let mut items = (0..20).collect::<Vec<usize>>();
+        ShuffleRng::new(seed).shuffle_where(&mut items, |item| item % 2 == 0);
+        items
+    }
+
+    #[test]
+    fn should_shuffle_in_the_same_order_for_the_same_seed() {
+        // Act
+        let first = shuffled(42);
+        let second = shuffled(42);
+
+        // Assert
+        assert_eq!(first, second);
+        assert_ne!(first, shuffled(43));
+    }
+
+    #[test]
+    fn should_only_move_matching_items() {
+        // Act
+        let items = shuffled(42);
+
+        // Assert
+        assert_ne!(items, (0..20).collect::<Vec<usize>>());
+        for (idx, item) in items.iter().enumerate() {
+            assert_eq!(idx % 2, item % 2);
+            if idx % 2 == 1 {
+                assert_eq!(idx, *item);
+            }
+        }
+    }
+}
@@ -53,6 +53,7 @@ pub enum TaskStateMachineNode<Payload> {
     Single(TaskNode<Payload>),
     Serial(SerialTaskNode<Payload>),
     Parallel(ParallelTaskNode<Payload>),
+    Dag(DagTaskNode<Payload>),
 }
 
 impl<Payload> From<TaskNode<Payload>> for TaskStateMachineNode<Payload> {
@@ -79,6 +80,12 @@ impl<Payload> From<ParallelTaskNode<Payload>> for TaskStateMachineNode<Payload>
     }
 }
 
+impl<Payload> From<DagTaskNode<Payload>> for TaskStateMachineNode<Payload> {
+    fn from(n: DagTaskNode<Payload>) -> TaskStateMachineNode<Payload> {
+        TaskStateMachineNode::Dag(n)
+    }
+}
+
 impl<Payload> TaskStream for TaskStateMachineNode<Payload> {
     type Payload = Payload;
     fn try_poll(&mut self) -> PollTaskResult<Self::Payload> {
@@ -86,6 +93,7 @@ impl<Payload> TaskStream for TaskStateMachineNode<Payload> {
             Self::Single(node) => node.try_poll(),
             Self::Serial(node) => node.try_poll(),
             Self::Parallel(node) => node.try_poll(),
+            Self::Dag(node) => node.try_poll(),
         }
     }
 
@@ -94,6 +102,7 @@ impl<Payload> TaskStream for TaskStateMachineNode<Payload> {
             Self::Single(node) => node.max_concurrency(),
             Self::Serial(node) => node.max_concurrency(),
             Self::Parallel(node) => node.max_concurrency(),
+            Self::Dag(node) => node.max_concurrency(),
         }
     }
 
@@ -102,6 +111,7 @@ impl<Payload> TaskStream for TaskStateMachineNode<Payload> {
             Self::Single(node) => node.complete_task(path),
             Self::Serial(node) => node.complete_task(path),
             Self::Parallel(node) => node.complete_task(path),
+            Self::Dag(node) => node.complete_task(path),
         }
     }
 
@@ -110,6 +120,7 @@ impl<Payload> TaskStream for TaskStateMachineNode<Payload> {
             Self::Single(_) => 1,
             Self::Serial(node) => node.len(),
             Self::Parallel(node) => node.len(),
+            Self::Dag(node) => node.len(),
         }
     }
 }
@@ -203,6 +214,7 @@ impl<Payload> ParallelTaskNode<Payload> {
             TaskStateMachineNode::Single(node) => self.append_task(node),
             TaskStateMachineNode::Serial(node) => self.append_serial(node),
             TaskStateMachineNode::Parallel(node) => self.append_parallel(node),
+            TaskStateMachineNode::Dag(node) => self.append_dag(node),
         }
     }
 
@@ -228,6 +240,13 @@ impl<Payload> ParallelTaskNode<Payload> {
         }
     }
 
+    pub fn append_dag(&mut self, dag: DagTaskNode<Payload>) {
+        if !dag.is_empty() {
+            self.total = self.total.saturating_add(dag.total);
+            self.nodes.push(TaskStateMachineNode::Dag(dag));
+        }
+    }
+
     pub fn append_serial(&mut self, serial: SerialTaskNode<Payload>) {
         if !serial.is_empty() {
             self.total = self.total.saturating_add(serial.total);
@@ -327,6 +346,7 @@ impl<Payload> SerialTaskNode<Payload> {
             TaskStateMachineNode::Single(node) => self.enqueue_task(node),
             TaskStateMachineNode::Serial(node) => self.enqueue_serial(node),
             TaskStateMachineNode::Parallel(node) => self.enqueue_parallel(node),
+            TaskStateMachineNode::Dag(node) => self.enqueue_dag(node),
         }
     }
 
@@ -352,6 +372,13 @@ impl<Payload> SerialTaskNode<Payload> {
         }
     }
 
+    pub fn enqueue_dag(&mut self, dag: DagTaskNode<Payload>) {
+        if !dag.is_empty() {
+            self.total = self.total.saturating_add(dag.total);
+            self.nodes.push(TaskStateMachineNode::Dag(dag));
+        }
+    }
+
     pub fn enqueue_serial(&mut self, mut serial: SerialTaskNode<Payload>) {
         if !serial.is_empty() {
             self.total = self.total.saturating_add(serial.total);
@@ -420,3 +447,195 @@ impl<Payload> TaskStream for SerialTaskNode<Payload> {
         self.total.saturating_sub(self.done)
     }
 }
+
+// Dag Task Node
+
+#[derive(Debug)]
+pub struct DagTaskNode<Payload> {
+    nodes: Vec<TaskStateMachineNode<Payload>>,
+    dependencies: Vec<Vec<usize>>,
+    remaining: Vec<usize>,
+    done: usize,
+    total: usize,
+}
+
+impl<Payload> Default for DagTaskNode<Payload> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Payload> DagTaskNode<Payload> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            dependencies: Vec::new(),
+            remaining: Vec::new(),
+            done: 0,
+            total: 0,
+        }
+    }
+
+    /// Adds a node to the graph, and returns its index.
+    /// The node is not started until all of its dependencies have completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The node to add to the graph.
+    ///
+    /// * `dependencies` - The indexes of the nodes which must complete before this node is started.
+    ///   Dependencies must form an acyclic graph, otherwise this node will never be started.
+    ///
+    pub fn add(
+        &mut self,
+        node: impl Into<TaskStateMachineNode<Payload>>,
+        dependencies: Vec<usize>,
+    ) -> usize {
+        let node = node.into();
+        let len = node.len();
+        self.total = self.total.saturating_add(len);
+        self.nodes.push(node);
+        self.dependencies.push(dependencies);
+        self.remaining.push(len);
+        self.nodes.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    fn is_complete(&self, idx: usize) -> bool {
+        self.remaining
+            .get(idx)
+            .is_none_or(|remaining| *remaining == 0)
+    }
+
+    fn is_ready(&self, idx: usize) -> bool {
+        self.dependencies[idx]
+            .iter()
+            .all(|dependency| self.is_complete(*dependency))
+    }
+}
+
+impl<Payload> TaskStream for DagTaskNode<Payload> {
+    type Payload = Payload;
+
+    fn try_poll(&mut self) -> PollTaskResult<Self::Payload> {
+        if self.done >= self.total {
+            return PollTaskResult::None;
+        }
+
+        for idx in 0..self.nodes.len() {
+            if self.is_complete(idx) || !self.is_ready(idx) {
+                continue;
+            }
+
+            if let PollTaskResult::Next(payload, path) = self.nodes[idx].try_poll() {
+                // Break on first ready
+                return PollTaskResult::Next(payload, path.append(idx));
+            }
+        }
+
+        // Nodes are either running, or waiting on their dependencies to complete
+        PollTaskResult::Busy
+    }
+
+    fn complete_task(&mut self, mut path: TaskNodePath) -> bool {
+        match path.pop() {
+            None => {
+                // This should be some kind of error
+                false
+            }
+            Some(idx) => {
+                if let Some(n) = self.nodes.get_mut(idx) {
+                    if n.complete_task(path) {
+                        self.remaining[idx] = self.remaining[idx].saturating_sub(1);
+                        self.done += 1;
+                        return true;
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    fn max_concurrency(&self) -> usize {
+        self.nodes
+            .iter()
+            .fold(0, |total, x| x.max_concurrency() + total)
+    }
+
+    fn len(&self) -> usize {
+        self.total.saturating_sub(self.done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poll_all<Stream: TaskStream>(stream: &mut Stream) -> Vec<(Stream::Payload, TaskNodePath)> {
+        let mut started = Vec::new();
+        while let PollTaskResult::Next(payload, path) = stream.try_poll() {
+            started.push((payload, path));
+        }
+        started
+    }
+
+    #[test]
+    fn should_not_start_dag_node_until_dependencies_complete() {
+        let mut dag = DagTaskNode::new();
+        let a = dag.add("a", vec![]);
+        let b = dag.add("b", vec![]);
+        dag.add("c", vec![a, b]);
+
+        // Act
+        let started = poll_all(&mut dag);
+
+        // Assert
+        assert_eq!(
+            started.iter().map(|(p, _)| *p).collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert!(matches!(dag.try_poll(), PollTaskResult::Busy));
+
+        dag.complete_task(started[0].1.clone());
+        assert!(matches!(dag.try_poll(), PollTaskResult::Busy));
+
+        dag.complete_task(started[1].1.clone());
+        let started = poll_all(&mut dag);
+        assert_eq!(
+            started.iter().map(|(p, _)| *p).collect::<Vec<_>>(),
+            vec!["c"]
+        );
+
+        dag.complete_task(started[0].1.clone());
+        assert!(matches!(dag.try_poll(), PollTaskResult::None));
+        assert_eq!(dag.len(), 0);
+    }
+
+    #[test]
+    fn should_wait_for_all_tasks_of_a_dag_node_dependency() {
+        let mut serial = SerialTaskNode::new();
+        serial.enqueue("a1");
+        serial.enqueue("a2");
+
+        let mut dag = DagTaskNode::new();
+        let a = dag.add(serial, vec![]);
+        dag.add("b", vec![a]);
+
+        // Act + Assert
+        let started = poll_all(&mut dag);
+        assert_eq!(started[0].0, "a1");
+        assert_eq!(started.len(), 1);
+        dag.complete_task(started[0].1.clone());
+
+        let started = poll_all(&mut dag);
+        assert_eq!(started[0].0, "a2");
+        assert_eq!(started.len(), 1);
+        dag.complete_task(started[0].1.clone());
+
+        let started = poll_all(&mut dag);
+        assert_eq!(started[0].0, "b");
+    }
+}
@@ -1,9 +1,14 @@
 use crate::components::{ChildProcessComponentArgs, Component, TestParameters};
 use crate::core::channel::RunProgressChannelNotify;
-use crate::decorations::{ComponentDecoration, ComponentGroup};
+use crate::core::last_run::LastRunState;
+#[cfg(feature = "data_source")]
+use crate::decorations::DataSource;
+use crate::decorations::{ComponentDecoration, ComponentFilter, ComponentGroup};
 use crate::formatters::{FormatterParameters, OutputFormatter};
 use crate::runner::ResolveRunnerStrategy;
-use crate::scheduling::{IntoTaskStateMachine, ScheduledComponent, TaskStateMachineNode};
+use crate::scheduling::{
+    IntoTaskStateMachine, ScheduledComponent, ShuffleRng, ShuffleSchedule, TaskStateMachineNode,
+};
 
 /// IOC code seem for internal test and customization extensions to the framework
 pub trait ResolveDecorationStrategy<Parameters: TestParameters> {
@@ -17,27 +22,106 @@ pub trait ResolveDecorationStrategy<Parameters: TestParameters> {
         auto_detected: Vec<ComponentDecoration<Parameters>>,
     ) -> Vec<ComponentDecoration<Parameters>> {
         let decorations = self.resolve_additional_decorations(parameters, auto_detected);
+        let decorations = self.expand_test_factories(parameters, decorations);
+        #[cfg(feature = "data_source")]
+        let decorations = self.expand_data_sources(parameters, decorations);
 
         match parameters.child_process_target() {
-            Some(child_process_parameters) => self
-                .filter_child_process(parameters, child_process_parameters, decorations)
-                .into_iter()
-                .collect(),
-            None => decorations,
+            Some(child_process_parameters) => {
+                self.filter_child_process(parameters, child_process_parameters, decorations)
+            }
+            None => self.filter_decorations(parameters, decorations),
         }
     }
 
+    /// Returns the list of decorations with components excluded by the test selection flagged as filtered.
+    /// Filtered components are not run, but are still reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The parameter type as defined by the test author.
+    ///
+    /// * `decorations` - The list of component decorations to be filtered.
+    ///
+    fn filter_decorations(
+        &mut self,
+        parameters: &Parameters,
+        decorations: Vec<ComponentDecoration<Parameters>>,
+    ) -> Vec<ComponentDecoration<Parameters>> {
+        let filter = ComponentFilter::from_parameters(parameters);
+        let filter = match parameters.rerun_failed() {
+            true => filter.rerun_only(LastRunState::load().failed_paths()),
+            false => filter,
+        };
+        filter.apply(decorations)
+    }
+
+    /// Returns the list of decorations with each test factory replaced by the tests it produces.
+    /// Factories are invoked again within a child process, so their tests can be found by their path.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The parameter type as defined by the test author.
+    ///
+    /// * `decorations` - The list of component decorations to be expanded.
+    ///
+    fn expand_test_factories(
+        &mut self,
+        parameters: &Parameters,
+        decorations: Vec<ComponentDecoration<Parameters>>,
+    ) -> Vec<ComponentDecoration<Parameters>> {
+        decorations
+            .into_iter()
+            .flat_map(|decoration| match decoration {
+                ComponentDecoration::TestFactory(factory) => factory.expand(parameters),
+                decoration => vec![decoration],
+            })
+            .collect()
+    }
+
+    /// Returns the list of decorations with each test decorated with a data source
+    /// replaced by a test for each of its records.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The parameter type as defined by the test author.
+    ///
+    /// * `decorations` - The list of component decorations to be expanded.
+    ///
+    #[cfg(feature = "data_source")]
+    fn expand_data_sources(
+        &mut self,
+        _parameters: &Parameters,
+        decorations: Vec<ComponentDecoration<Parameters>>,
+    ) -> Vec<ComponentDecoration<Parameters>> {
+        decorations
+            .into_iter()
+            .flat_map(|decoration| match decoration {
+                ComponentDecoration::IntegrationTest(test) => match test.desc.data_source {
+                    Some(pattern) => DataSource::new(pattern)
+                        .expand(test)
+                        .into_iter()
+                        .map(ComponentDecoration::IntegrationTest)
+                        .collect(),
+                    None => vec![ComponentDecoration::IntegrationTest(test)],
+                },
+                decoration => vec![decoration],
+            })
+            .collect()
+    }
+
     fn filter_child_process(
         &mut self,
         _parameters: &Parameters,
         child_process_parameters: &ChildProcessComponentArgs,
         decorations: Vec<ComponentDecoration<Parameters>>,
-    ) -> Option<ComponentDecoration<Parameters>> {
-        let target_component_path = &child_process_parameters.meta().path;
-        // Child process is used to run a single component in a separate process
+    ) -> Vec<ComponentDecoration<Parameters>> {
+        // Child process is used to run a single component in a separate process,
+        // along with any hooks wrapped around it
         decorations
             .into_iter()
-            .find(|c| &c.location().path == target_component_path)
+            .filter(|c| child_process_parameters.runs_component(&c.location().path))
+            .collect()
     }
 
     /// Returns list of decorated component to be compiled into the test schedule.
@@ -67,17 +151,28 @@ pub trait ResolveComponentHierarchyStrategy<Parameters: TestParameters> {
     fn resolve_component_hierarchy(
         &mut self,
         parameters: &Parameters,
-        mut decorations: Vec<ComponentDecoration<Parameters>>,
+        decorations: Vec<ComponentDecoration<Parameters>>,
     ) -> Component<Parameters> {
         match parameters.child_process_target() {
             Some(child_process_parameters) => {
                 // TODO: Tidy up
-                let d = decorations.pop().unwrap();
+                let target_component_path = &child_process_parameters.meta().path;
+                let (mut target, hooks): (Vec<_>, Vec<_>) = decorations
+                    .into_iter()
+                    .partition(|c| &c.location().path == target_component_path);
+
+                let hook_fns: Vec<_> = hooks
+                    .into_iter()
+                    .map(|h| (h.location().path.clone(), h.into_delegate().unwrap()))
+                    .collect();
+
+                let d = target.pop().unwrap();
                 child_process_parameters.clone().into_component(
                     d.name(),
                     d.description(),
                     d.location().clone(),
                     d.into_delegate().unwrap(),
+                    &hook_fns,
                 )
             }
             None => Component::Suite(ComponentGroup::into_root_component(decorations, parameters)),
@@ -174,9 +269,12 @@ impl<Parameters: TestParameters> ResolveComponentScheduleStrategy<Parameters>
     ///
     fn resolve_schedule(
         &mut self,
-        _parameters: &Parameters,
-        root_component: Component<Parameters>,
+        parameters: &Parameters,
+        mut root_component: Component<Parameters>,
     ) -> TaskStateMachineNode<ScheduledComponent<Parameters>> {
+        if let Some(seed) = parameters.shuffle_seed() {
+            root_component.shuffle_schedule(&mut ShuffleRng::new(seed));
+        }
         root_component.into_task_state_machine()
     }
 }
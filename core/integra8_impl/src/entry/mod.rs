@@ -21,6 +21,17 @@ pub fn main_test(input_tokens: TokenStream) -> TokenStream {
     let test_warning_time_threshold_seconds_expr =
         global_attr.take_test_warning_time_threshold_seconds();
 
+    let run_time_limit_default = global_attr
+        .take_run_time_limit()
+        .map(|expr| quote!(.default_value(#expr)));
+
+    let cancellation_grace_period_expr = global_attr.take_cancellation_grace_period();
+
+    let fail_fast_expr = global_attr.take_fail_fast();
+    let max_failures_default = global_attr
+        .take_max_failures()
+        .map(|expr| quote!(.default_value(#expr)));
+
     let test_concurrency_expr = global_attr.take_test_concurrency();
     let suite_concurrency_expr = global_attr.take_suite_concurrency();
 
@@ -157,9 +168,25 @@ pub fn main_test(input_tokens: TokenStream) -> TokenStream {
                 pub test_time_limit: std::time::Duration,
                 pub test_warning_time_limit: std::time::Duration,
                 pub tear_down_time_limit: std::time::Duration,
+                pub run_time_limit: Option<std::time::Duration>,
+                pub cancellation_grace_period: std::time::Duration,
+                pub fail_fast: bool,
+                pub max_failures: Option<usize>,
+                pub rerun_failed: bool,
+                pub repeat: Option<usize>,
+                pub repeat_until_failure: bool,
+                pub shuffle_seed: Option<u64>,
+                pub shard: Option<#integra8_path ::components::Shard>,
+                pub coordinator_address: Option<String>,
+                pub worker_address: Option<String>,
+                pub list_components: bool,
 
                 pub test_concurrency: #integra8_path ::components::ConcurrencyMode,
-                pub suite_concurrency: #integra8_path ::components::ConcurrencyMode
+                pub suite_concurrency: #integra8_path ::components::ConcurrencyMode,
+
+                pub filter_patterns: Vec<String>,
+                pub skip_patterns: Vec<String>,
+                pub tag_expression: Option<#integra8_path ::components::TagExpression>,
             }
 
 
@@ -258,6 +285,156 @@ pub fn main_test(input_tokens: TokenStream) -> TokenStream {
                         .long("default:test-warn-time-threshold")
                         .default_value(#test_warning_time_threshold_seconds_expr),
                     )
+                    .arg(Arg::with_name("framework:run-time-limit")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            #integra8_path ::humantime::parse_duration(s.as_str())
+                                .map(|_| ())
+                                .map_err(|e| e.to_string())
+                        })
+                        .long("framework:run-time-limit")
+                        #run_time_limit_default,
+                    )
+                    .arg(Arg::with_name("framework:cancellation-grace-period")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            #integra8_path ::humantime::parse_duration(s.as_str())
+                                .map(|_| ())
+                                .map_err(|e| e.to_string())
+                        })
+                        .long("framework:cancellation-grace-period")
+                        .default_value(#cancellation_grace_period_expr),
+                    )
+                    .arg(Arg::with_name("framework:fail-fast")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            ::std::str::FromStr::from_str(s.as_str())
+                                .map(|_: bool| ())
+                                .map_err(|e| e.to_string())
+                        })
+                        .long("framework:fail-fast")
+                        .default_value(#fail_fast_expr),
+                    )
+                    .arg(Arg::with_name("framework:max-failures")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            match ::std::str::FromStr::from_str(s.as_str()) {
+                                Ok(0usize) => Err("must be greater than zero".to_string()),
+                                Ok(_) => Ok(()),
+                                Err(e) => Err(::std::string::ToString::to_string(&e)),
+                            }
+                        })
+                        .long("framework:max-failures")
+                        #max_failures_default,
+                    )
+                    .arg(Arg::with_name("framework:rerun-failed")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            ::std::str::FromStr::from_str(s.as_str())
+                                .map(|_: bool| ())
+                                .map_err(|e| e.to_string())
+                        })
+                        .long("framework:rerun-failed")
+                        .default_value("false"),
+                    )
+                    .arg(Arg::with_name("framework:repeat")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            match ::std::str::FromStr::from_str(s.as_str()) {
+                                Ok(0usize) => Err("must be greater than zero".to_string()),
+                                Ok(_) => Ok(()),
+                                Err(e) => Err(::std::string::ToString::to_string(&e)),
+                            }
+                        })
+                        .long("framework:repeat"),
+                    )
+                    .arg(Arg::with_name("framework:repeat-until-failure")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            ::std::str::FromStr::from_str(s.as_str())
+                                .map(|_: bool| ())
+                                .map_err(|e| e.to_string())
+                        })
+                        .long("framework:repeat-until-failure")
+                        .default_value("false"),
+                    )
+                    .arg(Arg::with_name("framework:shuffle")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            ::std::str::FromStr::from_str(s.as_str())
+                                .map(|_: bool| ())
+                                .map_err(|e| e.to_string())
+                        })
+                        .long("framework:shuffle")
+                        .default_value("false"),
+                    )
+                    .arg(Arg::with_name("framework:seed")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            ::std::str::FromStr::from_str(s.as_str())
+                                .map(|_: u64| ())
+                                .map_err(|e| e.to_string())
+                        })
+                        .long("framework:seed"),
+                    )
+                    .arg(Arg::with_name("framework:shard")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            ::std::str::FromStr::from_str(s.as_str())
+                                .map(|_: #integra8_path ::components::Shard| ())
+                        })
+                        .long("framework:shard"),
+                    )
+                    .arg(Arg::with_name("framework:shard-by")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            ::std::str::FromStr::from_str(s.as_str())
+                                .map(|_: #integra8_path ::components::ShardMode| ())
+                        })
+                        .long("framework:shard-by")
+                        .default_value("Suite"),
+                    )
+                    .arg(Arg::with_name("coordinator")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .conflicts_with("worker")
+                        .long("coordinator"),
+                    )
+                    .arg(Arg::with_name("worker")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .long("worker"),
+                    )
+                    .arg(Arg::with_name("list")
+                        .takes_value(false)
+                        .multiple(false)
+                        .required(false)
+                        .long("list"),
+                    )
                     .arg(Arg::with_name("default:test-concurrency")
                         .takes_value(true)
                         .multiple(false)
@@ -281,6 +458,30 @@ pub fn main_test(input_tokens: TokenStream) -> TokenStream {
                         })
                         .long("default:suite-concurrency")
                         .default_value(#suite_concurrency_expr),
+                    )
+                    .arg(Arg::with_name("filter")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .required(false)
+                        .long("filter"),
+                    )
+                    .arg(Arg::with_name("skip")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .required(false)
+                        .long("skip"),
+                    )
+                    .arg(Arg::with_name("tags")
+                        .takes_value(true)
+                        .multiple(false)
+                        .required(false)
+                        .validator(|s| {
+                            ::std::str::FromStr::from_str(s.as_str())
+                                .map(|_: #integra8_path ::components::TagExpression| ())
+                        })
+                        .long("tags"),
                     );
                     app.version(env!("CARGO_PKG_VERSION"))
                 }
@@ -336,6 +537,61 @@ pub fn main_test(input_tokens: TokenStream) -> TokenStream {
                             .value_of("default:test-warn-time-limit")
                             .map(|s| #integra8_path ::humantime::parse_duration(s).unwrap())
                             .unwrap(),
+                        run_time_limit: matches
+                            .value_of("framework:run-time-limit")
+                            .map(|s| #integra8_path ::humantime::parse_duration(s).unwrap()),
+                        cancellation_grace_period: matches
+                            .value_of("framework:cancellation-grace-period")
+                            .map(|s| #integra8_path ::humantime::parse_duration(s).unwrap())
+                            .unwrap(),
+                        fail_fast: matches
+                            .value_of("framework:fail-fast")
+                            .map(|s| ::std::str::FromStr::from_str(s).unwrap())
+                            .unwrap(),
+                        max_failures: matches
+                            .value_of("framework:max-failures")
+                            .map(|s| ::std::str::FromStr::from_str(s).unwrap()),
+                        rerun_failed: matches
+                            .value_of("framework:rerun-failed")
+                            .map(|s| ::std::str::FromStr::from_str(s).unwrap())
+                            .unwrap(),
+                        repeat: matches
+                            .value_of("framework:repeat")
+                            .map(|s| ::std::str::FromStr::from_str(s).unwrap()),
+                        repeat_until_failure: matches
+                            .value_of("framework:repeat-until-failure")
+                            .map(|s| ::std::str::FromStr::from_str(s).unwrap())
+                            .unwrap(),
+                        // Giving a seed implies the order is shuffled,
+                        // otherwise a new seed is chosen for each run
+                        shuffle_seed: matches
+                            .value_of("framework:seed")
+                            .map(|s| ::std::str::FromStr::from_str(s).unwrap())
+                            .or_else(|| {
+                                let shuffle: bool = matches
+                                    .value_of("framework:shuffle")
+                                    .map(|s| ::std::str::FromStr::from_str(s).unwrap())
+                                    .unwrap();
+                                shuffle.then(|| #integra8_path ::scheduling::random_seed())
+                            }),
+                        shard: matches
+                            .value_of("framework:shard")
+                            .map(|s| {
+                                let shard: #integra8_path ::components::Shard = ::std::str::FromStr::from_str(s).unwrap();
+                                shard.by(
+                                    matches
+                                        .value_of("framework:shard-by")
+                                        .map(|s| ::std::str::FromStr::from_str(s).unwrap())
+                                        .unwrap()
+                                )
+                            }),
+                        coordinator_address: matches
+                            .value_of("coordinator")
+                            .map(|s| s.to_string()),
+                        worker_address: matches
+                            .value_of("worker")
+                            .map(|s| s.to_string()),
+                        list_components: matches.is_present("list"),
                         test_concurrency: matches
                             .value_of("default:test-concurrency")
                             .map(|s| ::std::str::FromStr::from_str(s).unwrap())
@@ -344,6 +600,17 @@ pub fn main_test(input_tokens: TokenStream) -> TokenStream {
                             .value_of("default:suite-concurrency")
                             .map(|s| ::std::str::FromStr::from_str(s).unwrap())
                             .unwrap(),
+                        filter_patterns: matches
+                            .values_of("filter")
+                            .map(|v| v.map(|s| s.to_string()).collect())
+                            .unwrap_or_default(),
+                        skip_patterns: matches
+                            .values_of("skip")
+                            .map(|v| v.map(|s| s.to_string()).collect())
+                            .unwrap_or_default(),
+                        tag_expression: matches
+                            .value_of("tags")
+                            .map(|s| ::std::str::FromStr::from_str(s).unwrap()),
                     }
                 }
             }
@@ -471,6 +738,18 @@ pub fn main_test(input_tokens: TokenStream) -> TokenStream {
                     self.framework.max_concurrency
                 }
 
+                fn filter_patterns(&self) -> &'_ [String] {
+                    &self.framework.filter_patterns
+                }
+
+                fn skip_patterns(&self) -> &'_ [String] {
+                    &self.framework.skip_patterns
+                }
+
+                fn tag_expression(&self) -> Option<&'_ #integra8_path ::components::TagExpression> {
+                    self.framework.tag_expression.as_ref()
+                }
+
                 fn test_concurrency(&self) -> #integra8_path ::components::ConcurrencyMode {
                     self.framework.test_concurrency.clone()
                 }
@@ -495,6 +774,54 @@ pub fn main_test(input_tokens: TokenStream) -> TokenStream {
                     self.framework.test_warning_time_limit
                 }
 
+                fn run_time_limit_duration(&self) -> Option<std::time::Duration> {
+                    self.framework.run_time_limit
+                }
+
+                fn cancellation_grace_period_duration(&self) -> std::time::Duration {
+                    self.framework.cancellation_grace_period
+                }
+
+                fn rerun_failed(&self) -> bool {
+                    self.framework.rerun_failed
+                }
+
+                fn repeat(&self) -> Option<usize> {
+                    self.framework.repeat
+                }
+
+                fn repeat_until_failure(&self) -> bool {
+                    self.framework.repeat_until_failure
+                }
+
+                fn shuffle_seed(&self) -> Option<u64> {
+                    self.framework.shuffle_seed
+                }
+
+                fn shard(&self) -> Option<#integra8_path ::components::Shard> {
+                    self.framework.shard
+                }
+
+                fn coordinator_address(&self) -> Option<&'_ str> {
+                    self.framework.coordinator_address.as_deref()
+                }
+
+                fn worker_address(&self) -> Option<&'_ str> {
+                    self.framework.worker_address.as_deref()
+                }
+
+                fn list_components(&self) -> bool {
+                    self.framework.list_components
+                }
+
+                fn max_failures(&self) -> Option<usize> {
+                    // Failing fast is the same as aborting after the first failure
+                    match self.framework.fail_fast {
+                        true => Some(1),
+                        false => self.framework.max_failures,
+                    }
+                }
+
                 fn root_namespace(&self) -> &'static str {
                     super::__ROOT_NAMESPACE
                 }
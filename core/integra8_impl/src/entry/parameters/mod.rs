@@ -75,6 +75,28 @@ impl ApplicationParameters {
             .unwrap_or_else(|| parse_quote!("30s"))
     }
 
+    pub fn take_cancellation_grace_period(&mut self) -> TokenStream {
+        self.take_string_parameter("cancellation_grace_period")
+            .map(|x| x.render_tokens())
+            .unwrap_or_else(|| parse_quote!("1s"))
+    }
+
+    pub fn take_run_time_limit(&mut self) -> Option<TokenStream> {
+        // No default, as the run is not limited unless a limit is given
+        self.take_string_parameter("run_time_limit").map(|x| x.render_tokens())
+    }
+
+    pub fn take_fail_fast(&mut self) -> TokenStream {
+        self.take_string_parameter("fail_fast")
+            .map(|x| x.render_tokens())
+            .unwrap_or_else(|| parse_quote!("false"))
+    }
+
+    pub fn take_max_failures(&mut self) -> Option<TokenStream> {
+        // No default, as the run is not aborted unless a maximum is given
+        self.take_string_parameter("max_failures").map(|x| x.render_tokens())
+    }
+
     pub fn take_console_output_style(
         &mut self,
         formatter_factory_type: &Box<Expr>,
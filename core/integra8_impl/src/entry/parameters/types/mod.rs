@@ -31,6 +31,10 @@ impl Parse for Parameter {
             | "default_tear_down_time_limit"
             | "default_test_time_limit"
             | "default_test_warning_time_limit"
+            | "run_time_limit"
+            | "cancellation_grace_period"
+            | "fail_fast"
+            | "max_failures"
             | "default_test_concurrency"
             | "default_suite_concurrency"
             | "console_output_level"
@@ -19,10 +19,11 @@ main_test! {
 }
 
 macro_rules! run_tests {
-    ($exe_name:expr, $ctx:expr) => {
+    ($exe_name:expr, $ctx:expr $(, $arg:expr)*) => {
         {
             use async_process::{Command, Stdio};
             match Command::new($exe_name)
+                $(.arg($arg))*
                 .kill_on_drop(true)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
@@ -58,7 +59,20 @@ macro_rules! assert_test_result {
         ctx => $ctx:expr,
         result => $expected:expr,
     ) => {
-        match run_tests!($exe_name, $ctx) {
+        assert_test_result!(
+            exe => $exe_name,
+            args => [],
+            ctx => $ctx,
+            result => $expected,
+        )
+    };
+    (
+        exe => $exe_name:expr,
+        args => [$($arg:expr),*],
+        ctx => $ctx:expr,
+        result => $expected:expr,
+    ) => {
+        match run_tests!($exe_name, $ctx $(, $arg)*) {
             (Some(actual), Ok(report)) => {
                 assert!($expected == actual, "Expected status code {:?}, but received {:?}", $expected, actual);
                 // success!
@@ -1160,6 +1174,98 @@ mod pitfalls {
     }
 }
 
+#[suite]
+mod distributed {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::{TcpListener, TcpStream};
+    use std::process::{Child, Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    /// Connects to the coordinator at the given address as a worker, reads the first component it is sent,
+    /// and then disconnects without running it, as if the worker had crashed.
+    fn disconnect_after_first_component(address: &str) {
+        let start_time = Instant::now();
+        let stream = loop {
+            match TcpStream::connect(address) {
+                Ok(stream) => break stream,
+                Err(e) if start_time.elapsed() > Duration::from_secs(30) => panic!("Failed to connect to coordinator, {}", e),
+                Err(_) => std::thread::sleep(Duration::from_millis(50)),
+            }
+        };
+
+        let mut component = String::new();
+        std::io::BufReader::new(&stream).read_line(&mut component).unwrap();
+        assert!(!component.is_empty(), "Expected the coordinator to send a component");
+    }
+
+    fn start_worker(address: &str) -> Child {
+        Command::new("./test_basics")
+            .arg("--worker")
+            .arg(address)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap()
+    }
+
+    #[integration_test]
+    async fn coordinator_and_workers(ctx : crate::ExecutionContext) {
+        // Arrange
+        // Listen on port 0 to be given a free port for the coordinator
+        let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().to_string();
+
+        // The first worker disconnects once it is sent a component, so the component is run by one of the other workers.
+        // Workers are started once it has, and exit once the coordinator completes its run
+        let workers = {
+            let address = address.clone();
+            std::thread::spawn(move || {
+                disconnect_after_first_component(&address);
+                vec![start_worker(&address), start_worker(&address)]
+            })
+        };
+
+        // Act
+        let r = assert_test_result!(
+            exe => "./test_basics",
+            args => ["--coordinator", address.as_str()],
+            ctx => ctx,
+            result => ComponentResult::Warning(WarningReason::ChildWarning),
+        );
+
+        // Assert
+        for mut worker in workers.join().unwrap() {
+            assert_eq!(worker.wait().unwrap().code(), Some(0));
+        }
+
+        assert_root_suite!(
+            report => r,
+            path => "test_basics",
+            result => ComponentResult::Warning(WarningReason::ChildWarning),
+        );
+
+        // Each component completes, including the one sent to the worker which disconnected.
+        // The output of the child processes run by the workers is reported by the coordinator
+        assert_component!(
+            report => r,
+            path => "test_basics::hello_world_test",
+            result => ComponentResult::Pass(PassReason::Accepted),
+            id => 1,
+            parent_id => 0,
+            component_type => ComponentType::Test,
+            stdout => "Hello world!\n"
+        );
+
+        assert_component!(
+            report => r,
+            path => "test_basics::this_test_is_sus",
+            result => ComponentResult::Warning(WarningReason::FailureAllowed),
+            id => 4,
+            parent_id => 0,
+            component_type => ComponentType::Test,
+        );
+    }
+}
 
 // TODO: Add tests for use_child_process, timeout, failure and warning
 